@@ -6,9 +6,24 @@ pub use solana::idl_parser::{
     construct_custom_idl_records_map_with_overrides, construct_idl_records_map, decode_idl_data,
     find_instruction_by_discriminator, parse_instruction_with_idl,
 };
-pub use solana::parser::{parse_transaction, parse_transaction_with_idls};
+pub use solana::lookup_tables::{
+    AddressLookupTableProvider, DirectoryAddressLookupTableProvider,
+    ExtendedAddressLookupTableProvider,
+};
+pub use solana::parser::{
+    insert_signature, parse_transaction, parse_transaction_auto_detect, parse_transaction_layout,
+    parse_transaction_with_encoding, parse_transaction_with_idls,
+    parse_transaction_with_lookup_tables, SolanaTransaction,
+};
 pub use solana::structs::{
-    CustomIdl, CustomIdlConfig, Idl, IdlInstruction, IdlParseError, IdlSource, ProgramType,
-    SolanaInstruction, SolanaMetadata, SolanaParseResponse, SolanaParsedInstructionData,
-    SolanaParsedTransaction, SolanaParsedTransactionPayload,
+    AddressLookupTableContents, AddressLookupTableDecodeError, AddressLookupTableDump,
+    AddressLookupTableStatus, CustomIdl, CustomIdlConfig, DecodedAddressLookupTable, Idl,
+    IdlInstruction, IdlParseError, IdlSource, InstructionParseError, InvalidInstructionsError,
+    NativeProgram, PayloadKind, ProgramType, SignatureInsertionError, SignatureStatus,
+    SolanaAccountMeta, SolanaAccountSource, SolanaFeeSummary, SolanaInstruction,
+    SolanaLayoutSection, SolanaMemo, SolanaMetadata, SolanaParseResponse,
+    SolanaParsedInstructionData, SolanaParsedTransaction, SolanaParsedTransactionPayload,
+    SolanaSignatureInsertion, SolanaSignatureVerification, SolanaStakeSummary, SplInterface,
+    StakeAuthorityChange, StakeDelegation, StakeWithdrawal, TransactionEncoding,
+    TransactionParseError, TransactionSerializeError, TransactionWarning,
 };
@@ -1,12 +1,20 @@
 use std::collections::HashMap;
 use std::env;
+use std::error::Error;
 use std::fs;
 
 mod solana;
 
-use crate::solana::parser::parse_transaction;
+use crate::solana::lookup_tables::{
+    AddressLookupTableProvider, DirectoryAddressLookupTableProvider,
+};
+use crate::solana::parser::{
+    parse_transaction_auto_detect, parse_transaction_layout, parse_transaction_with_lookup_tables,
+};
 use crate::solana::structs::{
-    IdlSource, SolanaParsedInstructionData, SolanaParsedTransactionPayload,
+    AddressLookupTableContents, CustomIdlConfig, IdlSource, PayloadKind, SolanaLayoutSection,
+    SolanaParsedInstructionData, SolanaParsedTransactionPayload, SolanaStakeSummary,
+    TransactionEncoding, TransactionParseError,
 };
 
 fn main() {
@@ -24,26 +32,71 @@ fn main() {
             let unsigned_tx = &args[3];
 
             match flag.as_str() {
-                "--message" | "--transaction" => {
-                    let is_transaction = flag == "--transaction";
-
+                "--message" | "--transaction" | "--auto" => {
                     // Check for optional custom IDL parameters
                     let custom_idls = parse_custom_idl_args(&args[4..]);
 
-                    let result =
-                        parse_transaction(unsigned_tx.to_string(), is_transaction, custom_idls);
+                    // Check for an optional input encoding, detecting it when not provided
+                    let encoding = match parse_encoding_arg(&args[4..]) {
+                        Ok(encoding) => encoding,
+                        Err(e) => {
+                            println!("Error: {}", e);
+                            print_usage();
+                            return;
+                        }
+                    };
+
+                    // Check for optional lookup table contents used to resolve address table lookups
+                    let lookup_tables = CliLookupTables {
+                        inline: parse_lookup_table_args(&args[4..]),
+                        directory: parse_lookup_table_dir_arg(&args[4..]),
+                    };
+
+                    let custom_idls = custom_idls.map(CustomIdlConfig::from_legacy_map);
+                    let result = if flag == "--auto" {
+                        // Detect whether the input is a message or a full transaction
+                        parse_transaction_auto_detect(
+                            unsigned_tx.to_string(),
+                            encoding,
+                            custom_idls,
+                            Some(&lookup_tables),
+                        )
+                    } else {
+                        parse_transaction_with_lookup_tables(
+                            unsigned_tx.to_string(),
+                            encoding,
+                            flag == "--transaction",
+                            custom_idls,
+                            Some(&lookup_tables),
+                        )
+                    };
 
                     match result {
                         Ok(response) => {
-                            print_parsed_transaction(
-                                response.solana_parsed_transaction.payload.unwrap(),
-                            );
+                            let payload = response.solana_parsed_transaction.payload.unwrap();
+                            let payload_kind = payload.payload_kind;
+                            print_parsed_transaction(payload);
+
+                            // Optionally render the annotated hexdump of the wire format
+                            if args[4..].iter().any(|a| a == "--layout") {
+                                match parse_transaction_layout(
+                                    unsigned_tx,
+                                    encoding,
+                                    payload_kind == PayloadKind::Transaction,
+                                ) {
+                                    Ok(sections) => print_transaction_layout(&sections),
+                                    Err(e) => println!("Error: {}", e),
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            println!("Error: {}", e);
+                            print_parse_error_hint(flag, &e);
                         }
-                        Err(e) => println!("Error: {}", e),
                     }
                 }
                 _ => {
-                    println!("Invalid flag. Use either --message or --transaction.");
+                    println!("Invalid flag. Use one of --message, --transaction or --auto.");
                     print_usage();
                 }
             }
@@ -57,8 +110,19 @@ fn main() {
 
 fn print_usage() {
     println!("Usage:");
-    println!("  cargo run parse --message <unsigned_tx_hex>");
-    println!("  cargo run parse --transaction <unsigned_tx_hex>");
+    println!("  cargo run parse --message <unsigned_tx>");
+    println!("  cargo run parse --transaction <unsigned_tx>");
+    println!("  cargo run parse --auto <unsigned_tx>  (detects message vs full transaction)");
+    println!();
+    println!("Optional input encoding (detected automatically when omitted):");
+    println!("  --encoding <hex|base58|base64|auto>");
+    println!();
+    println!("Optional annotated hexdump of the transaction wire format:");
+    println!("  --layout");
+    println!();
+    println!("Optional address lookup table contents (comma separated addresses, in table order):");
+    println!("  --lookup-table <table_pubkey> <address,address,...>");
+    println!("  --lookup-table-dir <dir>  (holding <table_pubkey>.json dumps from `solana address-lookup-table get --output json`)");
     println!();
     println!("Optional custom IDL parameters:");
     println!("  --custom-idl <program_id> <idl_json_file_or_string> [--override]");
@@ -67,6 +131,93 @@ fn print_usage() {
     println!("  cargo run parse --message <tx_hex>");
     println!("  cargo run parse --message <tx_hex> --custom-idl <program_id> /path/to/idl.json");
     println!("  cargo run parse --message <tx_hex> --custom-idl <program_id> /path/to/idl.json --override");
+    println!("  cargo run parse --transaction <tx_base64> --encoding base64");
+}
+
+fn print_parse_error_hint(flag: &str, error: &TransactionParseError) {
+    // Malformed wire format often just means the payload was parsed with the wrong layout
+    let malformed = matches!(
+        error,
+        TransactionParseError::Truncated { .. }
+            | TransactionParseError::ExtraneousBytes { .. }
+            | TransactionParseError::InvalidCompactU16 { .. }
+            | TransactionParseError::UnsupportedVersion(_)
+            | TransactionParseError::SanitizeFailed(_)
+    );
+    if !malformed {
+        return;
+    }
+    match flag {
+        "--transaction" => println!("If this is just a message instead of a full transaction, parse using the --message flag."),
+        "--message" => println!("If this is a full transaction with signatures or signature placeholders, parse using the --transaction flag."),
+        _ => {}
+    }
+}
+
+fn parse_encoding_arg(args: &[String]) -> Result<TransactionEncoding, String> {
+    match args.iter().position(|a| a == "--encoding") {
+        Some(i) => args
+            .get(i + 1)
+            .ok_or_else(|| "--encoding requires one of hex, base58, base64 or auto".to_string())?
+            .parse(),
+        None => Ok(TransactionEncoding::Auto),
+    }
+}
+
+fn parse_lookup_table_args(args: &[String]) -> HashMap<String, AddressLookupTableContents> {
+    let mut lookup_tables = HashMap::new();
+    let mut i = 0;
+
+    while i < args.len() {
+        if args[i] == "--lookup-table" {
+            if i + 2 >= args.len() {
+                eprintln!("Error: --lookup-table requires <table_pubkey> and <addresses>");
+                break;
+            }
+
+            let addresses = args[i + 2].split(',').map(str::to_string).collect();
+            lookup_tables.insert(
+                args[i + 1].clone(),
+                AddressLookupTableContents::from_addresses(addresses),
+            );
+            i += 3;
+        } else {
+            i += 1;
+        }
+    }
+    lookup_tables
+}
+
+fn parse_lookup_table_dir_arg(args: &[String]) -> Option<DirectoryAddressLookupTableProvider> {
+    let i = args.iter().position(|a| a == "--lookup-table-dir")?;
+    match args.get(i + 1) {
+        Some(dir) => Some(DirectoryAddressLookupTableProvider::new(dir)),
+        None => {
+            eprintln!("Error: --lookup-table-dir requires <dir>");
+            None
+        }
+    }
+}
+
+// Lookup tables passed with --lookup-table take precedence over dumps found in --lookup-table-dir
+struct CliLookupTables {
+    inline: HashMap<String, AddressLookupTableContents>,
+    directory: Option<DirectoryAddressLookupTableProvider>,
+}
+
+impl AddressLookupTableProvider for CliLookupTables {
+    fn lookup_table(
+        &self,
+        table: &str,
+    ) -> Result<Option<AddressLookupTableContents>, Box<dyn Error + Send + Sync>> {
+        if let Some(contents) = self.inline.lookup_table(table)? {
+            return Ok(Some(contents));
+        }
+        match &self.directory {
+            Some(directory) => directory.lookup_table(table),
+            None => Ok(None),
+        }
+    }
 }
 
 fn parse_custom_idl_args(args: &[String]) -> Option<HashMap<String, (String, bool)>> {
@@ -124,15 +275,36 @@ fn parse_custom_idl_args(args: &[String]) -> Option<HashMap<String, (String, boo
     }
 }
 
+#[allow(clippy::too_many_lines)] // Prints every section of the payload in order
 fn print_parsed_transaction(transaction_payload: SolanaParsedTransactionPayload) {
     println!("Solana Parsed Transaction Payload:");
     println!(
         "  Unsigned Payload: {}",
         transaction_payload.unsigned_payload
     );
+    println!("  Payload Kind: {:?}", transaction_payload.payload_kind);
+    println!(
+        "  Signable Payload: {}",
+        transaction_payload.signable_payload
+    );
+    println!("  Message Hash: {}", transaction_payload.message_hash);
     if let Some(metadata) = transaction_payload.transaction_metadata {
         println!("  Transaction Metadata:");
         println!("    Signatures: {:?}", metadata.signatures);
+        if !metadata.signature_verifications.is_empty() {
+            println!("    Signature Verification:");
+            for (i, verification) in metadata.signature_verifications.iter().enumerate() {
+                println!(
+                    "      Signature {}: {:?} (signer: {})",
+                    i + 1,
+                    verification.status,
+                    verification
+                        .signer
+                        .as_deref()
+                        .unwrap_or("NO REQUIRED SIGNER")
+                );
+            }
+        }
         println!("    Account Keys: {:?}", metadata.account_keys);
         println!("    Program Keys: {:?}", metadata.program_keys);
         println!("    Recent Blockhash: {}", metadata.recent_blockhash);
@@ -149,6 +321,9 @@ fn print_parsed_transaction(transaction_payload: SolanaParsedTransactionPayload)
                 "        Address Table Lookups: {:?}",
                 instruction.address_table_lookups
             );
+            if let Some(error) = &instruction.instruction_parse_error {
+                println!("        Instruction Parse Error: {}", error);
+            }
             print_parsed_instruction_data(instruction.parsed_instruction.clone());
         }
         println!("    Transfers:");
@@ -178,10 +353,73 @@ fn print_parsed_transaction(transaction_payload: SolanaParsedTransactionPayload)
                 println!("        Fee: {}", fee);
             }
         }
+        println!("    Memos:");
+        for (i, memo) in metadata.memos.iter().enumerate() {
+            println!("      Memo {}:", i + 1);
+            println!("        Instruction: {}", memo.instruction_index);
+            match &memo.memo {
+                Some(text) => println!("        Text: {}", text),
+                None => println!("        Text: (not valid UTF-8)"),
+            }
+            println!("        Data: {}", memo.memo_hex);
+            for (j, signer) in memo.signers.iter().enumerate() {
+                println!("        Signer {}: {}", j + 1, signer);
+            }
+        }
         println!(
             "    Address Table Lookups: {:?}",
             metadata.address_table_lookups
         );
+        let fee_summary = &metadata.fee_summary;
+        println!("    Fee Summary:");
+        println!(
+            "      Base Fee: {} lamports ({} signatures)",
+            fee_summary.base_fee_lamports, fee_summary.signature_count
+        );
+        println!(
+            "      Priority Fee: {} lamports ({} micro-lamports x {} compute units)",
+            fee_summary.priority_fee_lamports,
+            fee_summary.compute_unit_price_micro_lamports,
+            fee_summary.compute_unit_limit
+        );
+        println!(
+            "      Total Max Fee: {} lamports",
+            fee_summary.total_max_fee_lamports
+        );
+        let stake_summary = &metadata.stake_summary;
+        if stake_summary != &SolanaStakeSummary::default() {
+            println!("    Stake Summary:");
+            for delegation in &stake_summary.delegations {
+                println!(
+                    "      Instruction {}: delegates {} to vote account {}",
+                    delegation.instruction_index, delegation.stake_account, delegation.vote_account
+                );
+            }
+            for change in &stake_summary.authority_changes {
+                println!(
+                    "      Instruction {}: makes {} the {} authority of {}",
+                    change.instruction_index,
+                    change.new_authority,
+                    change.authority_type,
+                    change.stake_account
+                );
+            }
+            for withdrawal in &stake_summary.withdrawals {
+                println!(
+                    "      Instruction {}: withdraws {} lamports from {} to {}",
+                    withdrawal.instruction_index,
+                    withdrawal.lamports,
+                    withdrawal.stake_account,
+                    withdrawal.recipient
+                );
+            }
+        }
+        if !metadata.warnings.is_empty() {
+            println!("    Warnings:");
+            for warning in &metadata.warnings {
+                println!("      {}", warning);
+            }
+        }
     }
 }
 
@@ -204,8 +442,19 @@ fn print_parsed_instruction_data(p_inst_data: Option<SolanaParsedInstructionData
             IdlSource::Custom => {
                 println!("          IDL Source: Custom");
             }
+            IdlSource::Native(program) => {
+                println!("          IDL Source: Native ({})", program.program_name());
+            }
+            IdlSource::Interface(interface) => {
+                println!(
+                    "          IDL Source: Interface ({})",
+                    interface.interface_name()
+                );
+            }
+        }
+        if !parsed_data.idl_hash.is_empty() {
+            println!("          IDL Hash: {}", parsed_data.idl_hash);
         }
-        println!("          IDL Hash: {}", parsed_data.idl_hash);
 
         println!("          Named Accounts:");
         for k in parsed_data.named_accounts.keys() {
@@ -221,3 +470,26 @@ fn print_parsed_instruction_data(p_inst_data: Option<SolanaParsedInstructionData
         println!("          NO PARSED INSTRUCTION DATA -- NO MATCHING IDL")
     }
 }
+
+fn print_transaction_layout(sections: &[SolanaLayoutSection]) {
+    // Number of bytes rendered on each line of the hexdump
+    const BYTES_PER_ROW: usize = 16;
+
+    println!("Transaction Layout:");
+    println!("  {:<8}  {:>6}  Section", "Offset", "Length");
+    for section in sections {
+        println!(
+            "  {:#06x}    {:>6}  {}",
+            section.offset, section.length, section.section
+        );
+        let bytes = hex::decode(&section.hex).unwrap_or_default();
+        for (row, chunk) in bytes.chunks(BYTES_PER_ROW).enumerate() {
+            let row_hex: Vec<String> = chunk.iter().map(|b| format!("{:02x}", b)).collect();
+            println!(
+                "    {:#06x}  {}",
+                section.offset + row * BYTES_PER_ROW,
+                row_hex.join(" ")
+            );
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use crate::solana::structs::{
+    AddressLookupTableContents, AddressLookupTableDump, DecodedAddressLookupTable, IdlSource,
+    NativeProgram, SolanaMetadata,
+};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// A source of address lookup table contents.
+/// The parser consults the provider for every lookup table a transaction references,
+/// so that lookups into known tables resolve to real addresses.
+pub trait AddressLookupTableProvider {
+    /// Returns the contents of the lookup table with the given base58 pubkey,
+    /// or `None` if the table is unknown (its lookups are then left unresolved).
+    /// Errors are `Send + Sync` so that a provider fetching tables over the network can pass them across threads
+    fn lookup_table(
+        &self,
+        table: &str,
+    ) -> Result<Option<AddressLookupTableContents>, Box<dyn Error + Send + Sync>>;
+}
+
+// Tables passed inline, keyed by table pubkey
+impl AddressLookupTableProvider for HashMap<String, AddressLookupTableContents> {
+    fn lookup_table(
+        &self,
+        table: &str,
+    ) -> Result<Option<AddressLookupTableContents>, Box<dyn Error + Send + Sync>> {
+        Ok(self.get(table).cloned())
+    }
+}
+
+/// Reads lookup tables from a directory holding one JSON dump per table, named `<table_pubkey>.json`.
+/// Each dump has the shape printed by `solana address-lookup-table get <table_pubkey> --output json`.
+/// Dumps are read the first time a table is requested and cached for later transactions.
+pub struct DirectoryAddressLookupTableProvider {
+    directory: PathBuf,
+    cache: Mutex<HashMap<String, Option<AddressLookupTableContents>>>,
+}
+
+impl DirectoryAddressLookupTableProvider {
+    /// Create a provider reading dumps from `directory`
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn read_dump(
+        &self,
+        table: &str,
+    ) -> Result<Option<AddressLookupTableContents>, Box<dyn Error + Send + Sync>> {
+        // table keys are base58, so they cannot escape the directory
+        if !table.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(format!("invalid lookup table pubkey: {table}").into());
+        }
+        let path = self.directory.join(format!("{table}.json"));
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let dump: AddressLookupTableDump = serde_json::from_str(&fs::read_to_string(&path)?)
+            .map_err(|e| format!("invalid lookup table dump {}: {e}", path.display()))?;
+        if dump.lookup_table_address != table {
+            return Err(format!(
+                "lookup table dump {} is for table {}",
+                path.display(),
+                dump.lookup_table_address
+            )
+            .into());
+        }
+        Ok(Some(AddressLookupTableContents::Addresses(dump.addresses)))
+    }
+}
+
+impl AddressLookupTableProvider for DirectoryAddressLookupTableProvider {
+    fn lookup_table(
+        &self,
+        table: &str,
+    ) -> Result<Option<AddressLookupTableContents>, Box<dyn Error + Send + Sync>> {
+        if let Some(cached) = self
+            .cache
+            .lock()
+            .map_err(|_| "lookup table cache is poisoned")?
+            .get(table)
+        {
+            return Ok(cached.clone());
+        }
+
+        let contents = self.read_dump(table)?;
+        self.cache
+            .lock()
+            .map_err(|_| "lookup table cache is poisoned")?
+            .insert(table.to_string(), contents.clone());
+        Ok(contents)
+    }
+}
+
+/// Builds lookup tables from the Address Lookup Table program instructions of transactions parsed earlier,
+/// such as the transactions ahead of this one in a bundle.
+/// A table created by a recorded CreateLookupTable starts out empty, and every recorded ExtendLookupTable appends its addresses.
+/// Addresses appended to a table created elsewhere are added after the table contents from the base provider. Without them
+/// the indexes of the new addresses are unknown, so the table is left unresolved.
+pub struct ExtendedAddressLookupTableProvider<'a> {
+    base: Option<&'a dyn AddressLookupTableProvider>,
+    created: HashSet<String>,
+    extensions: HashMap<String, Vec<String>>,
+}
+
+#[allow(dead_code)] // Public API
+impl<'a> ExtendedAddressLookupTableProvider<'a> {
+    /// Create a provider that extends the tables of `base`, if any
+    pub fn new(base: Option<&'a dyn AddressLookupTableProvider>) -> Self {
+        Self {
+            base,
+            created: HashSet::new(),
+            extensions: HashMap::new(),
+        }
+    }
+
+    /// Record the lookup tables a parsed transaction creates and extends, in instruction order
+    pub fn record_transaction(&mut self, metadata: &SolanaMetadata) {
+        let lookup_table_instructions = metadata
+            .instructions
+            .iter()
+            .filter_map(|i| i.parsed_instruction.as_ref())
+            .filter(|p| p.idl_source == IdlSource::Native(NativeProgram::AddressLookupTable));
+        for parsed_instruction in lookup_table_instructions {
+            // an unresolved lookup account is named by a placeholder rather than its pubkey, so the table is unknown
+            let Some(table) = parsed_instruction
+                .named_accounts
+                .get("lookup_table_account")
+                .filter(|table| Pubkey::from_str(table).is_ok())
+            else {
+                continue;
+            };
+            match parsed_instruction.instruction_name.as_str() {
+                "CreateLookupTable" => {
+                    self.created.insert(table.clone());
+                    self.extensions.insert(table.clone(), vec![]);
+                }
+                "ExtendLookupTable" => {
+                    let new_addresses = parsed_instruction
+                        .program_call_args
+                        .get("new_addresses")
+                        .and_then(|a| a.as_array())
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|a| a.as_str().map(str::to_string));
+                    self.extensions
+                        .entry(table.clone())
+                        .or_default()
+                        .extend(new_addresses);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl AddressLookupTableProvider for ExtendedAddressLookupTableProvider<'_> {
+    fn lookup_table(
+        &self,
+        table: &str,
+    ) -> Result<Option<AddressLookupTableContents>, Box<dyn Error + Send + Sync>> {
+        let extension = self.extensions.get(table);
+        if self.created.contains(table) {
+            return Ok(Some(AddressLookupTableContents::Addresses(
+                extension.cloned().unwrap_or_default(),
+            )));
+        }
+
+        let contents = match self.base {
+            Some(base) => base.lookup_table(table)?,
+            None => None,
+        };
+        match (contents, extension) {
+            (Some(contents), Some(extension)) => {
+                let mut addresses = match contents {
+                    AddressLookupTableContents::Addresses(addresses) => addresses,
+                    AddressLookupTableContents::AccountData(data) => {
+                        DecodedAddressLookupTable::decode(&data)?.addresses
+                    }
+                };
+                addresses.extend(extension.iter().cloned());
+                Ok(Some(AddressLookupTableContents::Addresses(addresses)))
+            }
+            (contents, _) => Ok(contents),
+        }
+    }
+}
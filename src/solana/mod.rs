@@ -1,6 +1,8 @@
 pub mod embedded_idls;
 pub mod idl_db;
 pub mod idl_parser;
+pub mod lookup_tables;
+pub mod native_programs;
 pub mod parser;
 pub mod structs;
 
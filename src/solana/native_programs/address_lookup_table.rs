@@ -0,0 +1,111 @@
+use super::{data_parse_error, named_accounts, native_instruction};
+use crate::solana::structs::{
+    AccountAddress, InstructionParseError, NativeProgram, SolanaParsedInstructionData,
+};
+use serde_json::json;
+use solana_sdk::{
+    address_lookup_table::instruction::{derive_lookup_table_address, ProgramInstruction},
+    pubkey::Pubkey,
+};
+use std::str::FromStr;
+
+const PROGRAM: NativeProgram = NativeProgram::AddressLookupTable;
+
+// Address Lookup Table program instructions are bincode encoded, starting with a little endian u32 tag
+const LEN_LOOKUP_TABLE_INSTRUCTION_TAG_BYTES: usize = 4;
+
+/// Decode the data of an Address Lookup Table program instruction
+pub fn decode_address_lookup_table_instruction(
+    instruction_data: &[u8],
+) -> Result<ProgramInstruction, InstructionParseError> {
+    bincode::deserialize(instruction_data).map_err(|e| {
+        data_parse_error(
+            PROGRAM,
+            format!("could not parse address lookup table instruction: {e}"),
+        )
+    })
+}
+
+/*
+Parse Address Lookup Table Instruction
+- Names the accounts and arguments of every Address Lookup Table program instruction
+- CreateLookupTable shows the table address derived from the authority and the recent slot, which the program requires the table account to be
+- ExtendLookupTable lists the appended addresses in table order, so that later transactions can resolve lookups into the table
+*/
+pub fn parse_address_lookup_table_instruction(
+    instruction: &ProgramInstruction,
+    instruction_data: &[u8],
+    accounts: &[AccountAddress],
+) -> Result<SolanaParsedInstructionData, InstructionParseError> {
+    let (instruction_name, required, optional, args): (&str, &[&str], &[&str], _) =
+        match instruction {
+            // the authority signs, so it is always a static account
+            ProgramInstruction::CreateLookupTable {
+                recent_slot,
+                bump_seed,
+            } => {
+                let derived_table_address = accounts
+                    .get(1)
+                    .and_then(|authority| Pubkey::from_str(&authority.to_string()).ok())
+                    .map(|authority| {
+                        derive_lookup_table_address(&authority, *recent_slot)
+                            .0
+                            .to_string()
+                    });
+                (
+                    "CreateLookupTable",
+                    &[
+                        "lookup_table_account",
+                        "authority",
+                        "payer",
+                        "system_program",
+                    ],
+                    &[],
+                    json!({
+                        "recent_slot": recent_slot,
+                        "bump_seed": bump_seed,
+                        "derived_lookup_table_address": derived_table_address,
+                    }),
+                )
+            }
+            ProgramInstruction::FreezeLookupTable => (
+                "FreezeLookupTable",
+                &["lookup_table_account", "authority"],
+                &[],
+                json!({}),
+            ),
+            // the payer and system program are only passed when the table needs more lamports to stay rent exempt
+            ProgramInstruction::ExtendLookupTable { new_addresses } => (
+                "ExtendLookupTable",
+                &["lookup_table_account", "authority"],
+                &["payer", "system_program"],
+                json!({
+                    "new_addresses": new_addresses
+                        .iter()
+                        .map(Pubkey::to_string)
+                        .collect::<Vec<String>>(),
+                }),
+            ),
+            ProgramInstruction::DeactivateLookupTable => (
+                "DeactivateLookupTable",
+                &["lookup_table_account", "authority"],
+                &[],
+                json!({}),
+            ),
+            ProgramInstruction::CloseLookupTable => (
+                "CloseLookupTable",
+                &["lookup_table_account", "authority", "recipient"],
+                &[],
+                json!({}),
+            ),
+        };
+
+    let named_accounts = named_accounts(instruction_name, accounts, required, optional)?;
+    Ok(native_instruction(
+        PROGRAM,
+        instruction_name,
+        &instruction_data[..LEN_LOOKUP_TABLE_INSTRUCTION_TAG_BYTES],
+        named_accounts,
+        args,
+    ))
+}
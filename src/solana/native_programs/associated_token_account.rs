@@ -0,0 +1,137 @@
+use super::{data_parse_error, named_accounts, native_instruction};
+use crate::solana::structs::{
+    AccountAddress, IdlSource, InstructionParseError, NativeProgram, SolanaParsedInstructionData,
+    TransactionWarning,
+};
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+const PROGRAM: NativeProgram = NativeProgram::AssociatedTokenAccount;
+
+const CREATE_ACCOUNTS: [&str; 6] = [
+    "payer",
+    "associated_token_account",
+    "wallet",
+    "mint",
+    "system_program",
+    "token_program",
+];
+
+const RECOVER_NESTED_ACCOUNTS: [&str; 7] = [
+    "nested_associated_token_account",
+    "nested_mint",
+    "destination_associated_token_account",
+    "owner_associated_token_account",
+    "owner_mint",
+    "wallet",
+    "token_program",
+];
+
+/// Derives the associated token account of a wallet for a mint, owned by the given token program
+pub fn associated_token_address(wallet: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    let program_id = Pubkey::from_str_const(PROGRAM.program_id());
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &program_id,
+    )
+    .0
+}
+
+/*
+Parse Associated Token Account Instruction
+- Create and CreateIdempotent create the associated token account of a wallet for a mint, and RecoverNested closes an associated token account owned by another associated token account
+- Empty instruction data is a Create instruction, which is how the program was first called
+- Old clients pass the rent sysvar after the token program, so it is named when present
+*/
+pub fn parse_associated_token_account_instruction(
+    instruction_data: &[u8],
+    accounts: &[AccountAddress],
+) -> Result<SolanaParsedInstructionData, InstructionParseError> {
+    let (instruction_name, named_accounts) = match instruction_data {
+        [] | [0] => (
+            "Create",
+            named_accounts("Create", accounts, &CREATE_ACCOUNTS, &["rent_sysvar"])?,
+        ),
+        [1] => (
+            "CreateIdempotent",
+            named_accounts("CreateIdempotent", accounts, &CREATE_ACCOUNTS, &[])?,
+        ),
+        [2] => (
+            "RecoverNested",
+            named_accounts("RecoverNested", accounts, &RECOVER_NESTED_ACCOUNTS, &[])?,
+        ),
+        _ => {
+            return Err(data_parse_error(
+                PROGRAM,
+                format!(
+                    "unknown associated token account instruction {}",
+                    hex::encode(instruction_data)
+                ),
+            ))
+        }
+    };
+    Ok(native_instruction(
+        PROGRAM,
+        instruction_name,
+        instruction_data,
+        named_accounts,
+        json!({}),
+    ))
+}
+
+/*
+Associated Token Account Warnings
+- Recomputes every associated token account a decoded instruction names from its wallet, mint and token program, and warns about each one that does not match
+- A mismatched account means the instruction creates or drains a token account the wallet may not control
+- Accounts that are unresolved address table lookups cannot be checked, and are skipped
+*/
+pub fn associated_token_account_warnings(
+    instruction_index: usize,
+    parsed_instruction: &SolanaParsedInstructionData,
+) -> Vec<TransactionWarning> {
+    if parsed_instruction.idl_source != IdlSource::Native(PROGRAM) {
+        return vec![];
+    }
+    // each check is the associated token account, and the wallet and mint it should be derived from
+    let checks: &[(&str, &str, &str)] = match parsed_instruction.instruction_name.as_str() {
+        "Create" | "CreateIdempotent" => &[("associated_token_account", "wallet", "mint")],
+        "RecoverNested" => &[
+            ("owner_associated_token_account", "wallet", "owner_mint"),
+            (
+                "nested_associated_token_account",
+                "owner_associated_token_account",
+                "nested_mint",
+            ),
+            (
+                "destination_associated_token_account",
+                "wallet",
+                "nested_mint",
+            ),
+        ],
+        _ => return vec![],
+    };
+    let pubkey = |name: &str| {
+        parsed_instruction
+            .named_accounts
+            .get(name)
+            .and_then(|account| Pubkey::from_str(account).ok())
+    };
+    let Some(token_program) = pubkey("token_program") else {
+        return vec![];
+    };
+    checks
+        .iter()
+        .filter_map(|(account_name, wallet, mint)| {
+            let account = pubkey(account_name)?;
+            let expected =
+                associated_token_address(&pubkey(wallet)?, &pubkey(mint)?, &token_program);
+            (account != expected).then(|| TransactionWarning::AssociatedTokenAccountMismatch {
+                instruction_index,
+                account_name: account_name.to_string(),
+                account: account.to_string(),
+                expected: expected.to_string(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,184 @@
+use super::{data_parse_error, native_instruction, InstructionDataReader};
+use crate::solana::structs::{
+    InstructionParseError, NativeProgram, SolanaFeeSummary, SolanaParsedInstructionData,
+    TransactionWarning,
+};
+use serde_json::json;
+use std::collections::HashMap;
+
+const PROGRAM: NativeProgram = NativeProgram::ComputeBudget;
+
+// Every signature of a transaction costs a fixed base fee
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+// The compute unit price is set in millionths of a lamport
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+// Without a SetComputeUnitLimit instruction, the runtime allows this many compute units for every other instruction
+const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+// No transaction can use more compute units than this, whatever limit it requests
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+// Compute Budget program instructions are borsh encoded: a one byte tag followed by the value
+// Reference: https://docs.rs/solana-compute-budget-interface/latest/solana_compute_budget_interface/enum.ComputeBudgetInstruction.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ComputeBudgetInstruction {
+    RequestHeapFrame { bytes: u32 },
+    SetComputeUnitLimit { units: u32 },
+    SetComputeUnitPrice { micro_lamports: u64 },
+    SetLoadedAccountsDataSizeLimit { bytes: u32 },
+}
+
+impl ComputeBudgetInstruction {
+    fn tag(&self) -> u8 {
+        match self {
+            ComputeBudgetInstruction::RequestHeapFrame { .. } => 1,
+            ComputeBudgetInstruction::SetComputeUnitLimit { .. } => 2,
+            ComputeBudgetInstruction::SetComputeUnitPrice { .. } => 3,
+            ComputeBudgetInstruction::SetLoadedAccountsDataSizeLimit { .. } => 4,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            ComputeBudgetInstruction::RequestHeapFrame { .. } => "RequestHeapFrame",
+            ComputeBudgetInstruction::SetComputeUnitLimit { .. } => "SetComputeUnitLimit",
+            ComputeBudgetInstruction::SetComputeUnitPrice { .. } => "SetComputeUnitPrice",
+            ComputeBudgetInstruction::SetLoadedAccountsDataSizeLimit { .. } => {
+                "SetLoadedAccountsDataSizeLimit"
+            }
+        }
+    }
+}
+
+// Tag 0 is a deprecated instruction that the runtime no longer accepts, so it is reported as unknown
+fn decode_compute_budget_instruction(
+    instruction_data: &[u8],
+) -> Result<ComputeBudgetInstruction, InstructionParseError> {
+    let (&tag, data) = instruction_data.split_first().ok_or_else(|| {
+        data_parse_error(
+            PROGRAM,
+            "error while parsing compute budget instruction data header".to_string(),
+        )
+    })?;
+    let instruction_name = match tag {
+        1 => "RequestHeapFrame",
+        2 => "SetComputeUnitLimit",
+        3 => "SetComputeUnitPrice",
+        4 => "SetLoadedAccountsDataSizeLimit",
+        _ => {
+            return Err(data_parse_error(
+                PROGRAM,
+                format!("unknown compute budget instruction tag {tag}"),
+            ))
+        }
+    };
+    let mut data = InstructionDataReader::new(PROGRAM, instruction_name, data);
+    Ok(match tag {
+        1 => ComputeBudgetInstruction::RequestHeapFrame {
+            bytes: data.u32("bytes")?,
+        },
+        2 => ComputeBudgetInstruction::SetComputeUnitLimit {
+            units: data.u32("units")?,
+        },
+        3 => ComputeBudgetInstruction::SetComputeUnitPrice {
+            micro_lamports: data.u64("micro_lamports")?,
+        },
+        _ => ComputeBudgetInstruction::SetLoadedAccountsDataSizeLimit {
+            bytes: data.u32("bytes")?,
+        },
+    })
+}
+
+/*
+Parse Compute Budget Instruction
+- Decodes the compute unit limit and price, heap frame and loaded accounts data size requests of a transaction
+- Compute Budget program instructions take no accounts
+*/
+pub fn parse_compute_budget_instruction(
+    instruction_data: &[u8],
+) -> Result<SolanaParsedInstructionData, InstructionParseError> {
+    let instruction = decode_compute_budget_instruction(instruction_data)?;
+    let args = match instruction {
+        ComputeBudgetInstruction::RequestHeapFrame { bytes }
+        | ComputeBudgetInstruction::SetLoadedAccountsDataSizeLimit { bytes } => {
+            json!({ "bytes": bytes })
+        }
+        ComputeBudgetInstruction::SetComputeUnitLimit { units } => json!({ "units": units }),
+        ComputeBudgetInstruction::SetComputeUnitPrice { micro_lamports } => {
+            json!({ "micro_lamports": micro_lamports })
+        }
+    };
+    Ok(native_instruction(
+        PROGRAM,
+        instruction.name(),
+        &[instruction.tag()],
+        HashMap::new(),
+        args,
+    ))
+}
+
+/*
+Transaction Fee Summary
+- The base fee is charged for every required signature, and the priority fee is the compute unit price times the compute unit limit, rounded up to the lamport
+- Without a SetComputeUnitLimit instruction the limit is the runtime default for the other instructions, and any limit is capped at the maximum the runtime allows
+- The runtime rejects a transaction with two Compute Budget instructions of the same kind, so every repeat is warned about
+- Compute Budget instructions that cannot be decoded are reported on the instruction and left out of the summary
+*/
+pub fn fee_summary(
+    signature_count: u8,
+    instructions: &[(String, &[u8])],
+) -> (SolanaFeeSummary, Vec<TransactionWarning>) {
+    let mut compute_unit_limit = None;
+    let mut compute_unit_price = None;
+    let mut seen_tags: Vec<u8> = vec![];
+    let mut other_instructions: u32 = 0;
+    let mut warnings = vec![];
+    for (instruction_index, (program_key, data)) in instructions.iter().enumerate() {
+        if program_key != PROGRAM.program_id() {
+            other_instructions += 1;
+            continue;
+        }
+        let Ok(instruction) = decode_compute_budget_instruction(data) else {
+            continue;
+        };
+        if seen_tags.contains(&instruction.tag()) {
+            warnings.push(TransactionWarning::DuplicateComputeBudgetInstruction {
+                instruction_index,
+                instruction_name: instruction.name().to_string(),
+            });
+            continue;
+        }
+        seen_tags.push(instruction.tag());
+        match instruction {
+            ComputeBudgetInstruction::SetComputeUnitLimit { units } => {
+                compute_unit_limit = Some(units)
+            }
+            ComputeBudgetInstruction::SetComputeUnitPrice { micro_lamports } => {
+                compute_unit_price = Some(micro_lamports)
+            }
+            _ => {}
+        }
+    }
+
+    let compute_unit_limit = compute_unit_limit
+        .unwrap_or_else(|| {
+            other_instructions.saturating_mul(DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT)
+        })
+        .min(MAX_COMPUTE_UNIT_LIMIT);
+    let compute_unit_price_micro_lamports = compute_unit_price.unwrap_or(0);
+    let priority_fee_lamports = (u128::from(compute_unit_price_micro_lamports)
+        * u128::from(compute_unit_limit))
+    .div_ceil(MICRO_LAMPORTS_PER_LAMPORT);
+    let priority_fee_lamports = u64::try_from(priority_fee_lamports).unwrap_or(u64::MAX);
+    let base_fee_lamports = u64::from(signature_count) * LAMPORTS_PER_SIGNATURE;
+    (
+        SolanaFeeSummary {
+            signature_count,
+            base_fee_lamports,
+            compute_unit_limit,
+            compute_unit_price_micro_lamports,
+            priority_fee_lamports,
+            total_max_fee_lamports: base_fee_lamports.saturating_add(priority_fee_lamports),
+        },
+        warnings,
+    )
+}
@@ -0,0 +1,457 @@
+use super::zk_elgamal_proof::{proof_instruction_name, reads_proof_from_record_account};
+use super::{
+    data_parse_error, named_accounts, native_instruction, optional_pubkey_value,
+    InstructionDataReader, SiblingInstructions,
+};
+use crate::solana::structs::{
+    AccountAddress, InstructionParseError, NativeProgram, SolanaParsedInstructionData,
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+const PROGRAM: NativeProgram = NativeProgram::Token2022;
+const CONFIDENTIAL_TRANSFER_EXTENSION_TAG: u8 = 27;
+// An authenticated encryption ciphertext of an account's available balance, decryptable by the account owner
+const LEN_DECRYPTABLE_BALANCE_BYTES: usize = 36;
+const LEN_ELGAMAL_CIPHERTEXT_BYTES: usize = 64;
+const LEN_ELGAMAL_PUBKEY_BYTES: usize = 32;
+
+// Reference for the instruction layouts: https://docs.rs/spl-token-2022/latest/src/spl_token_2022/extension/confidential_transfer/instruction.rs.html
+fn confidential_transfer_instruction_name(tag: u8) -> Option<&'static str> {
+    Some(match tag {
+        0 => "InitializeConfidentialTransferMint",
+        1 => "UpdateConfidentialTransferMint",
+        2 => "ConfigureConfidentialTransferAccount",
+        3 => "ApproveConfidentialTransferAccount",
+        4 => "EmptyConfidentialTransferAccount",
+        5 => "ConfidentialDeposit",
+        6 => "ConfidentialWithdraw",
+        7 => "ConfidentialTransfer",
+        8 => "ApplyPendingBalance",
+        9 => "EnableConfidentialCredits",
+        10 => "DisableConfidentialCredits",
+        11 => "EnableNonConfidentialCredits",
+        12 => "DisableNonConfidentialCredits",
+        13 => "ConfidentialTransferWithFee",
+        14 => "ConfigureConfidentialAccountWithRegistry",
+        _ => return None,
+    })
+}
+
+// A zero-knowledge proof that a confidential transfer instruction relies on, and the proof program instruction that verifies it
+// An offset of 0 means the proof was verified ahead of time into a context state account
+struct ProofReference {
+    proof: &'static str,
+    verify_instruction: &'static str,
+    offset: i8,
+}
+
+// Names the accounts of an instruction one at a time, for instructions whose accounts depend on where their proofs are verified
+struct AccountNamer<'a> {
+    instruction_name: &'a str,
+    accounts: &'a [AccountAddress],
+    named: HashMap<String, String>,
+    next: usize,
+}
+
+impl<'a> AccountNamer<'a> {
+    fn new(instruction_name: &'a str, accounts: &'a [AccountAddress]) -> Self {
+        AccountNamer {
+            instruction_name,
+            accounts,
+            named: HashMap::new(),
+            next: 0,
+        }
+    }
+
+    fn name(&mut self, name: &str) -> Result<&'a AccountAddress, InstructionParseError> {
+        let account = self.accounts.get(self.next).ok_or_else(|| {
+            InstructionParseError::NotEnoughAccounts {
+                instruction_name: self.instruction_name.to_string(),
+                expected: self.next + 1,
+                found: self.accounts.len(),
+            }
+        })?;
+        self.named.insert(name.to_string(), account.to_string());
+        self.next += 1;
+        Ok(account)
+    }
+
+    // Names the authority, then any multisig signers after it
+    fn authority_with_signers(
+        mut self,
+        name: &str,
+    ) -> Result<HashMap<String, String>, InstructionParseError> {
+        self.name(name)?;
+        for (i, signer) in self.accounts[self.next..].iter().enumerate() {
+            self.named
+                .insert(format!("signer_{}", i + 1), signer.to_string());
+        }
+        Ok(self.named)
+    }
+}
+
+/*
+Locate Proofs
+- Names the accounts that carry each proof, and describes where in the transaction each proof is verified
+- The instructions sysvar comes before the proof accounts when any proof is verified by an instruction in the same transaction, and is shared by all of them
+- A proof verified in the same transaction only takes an account of its own when the proof instruction reads the proof from a record account
+*/
+fn locate_proofs(
+    namer: &mut AccountNamer,
+    proofs: &[ProofReference],
+    siblings: SiblingInstructions,
+) -> Result<Value, InstructionParseError> {
+    if proofs.iter().any(|proof| proof.offset != 0) {
+        namer.name("instructions_sysvar")?;
+    }
+    let locations = proofs
+        .iter()
+        .map(|proof| {
+            if proof.offset == 0 {
+                let context_state = namer.name(&format!("{}_proof_context_state", proof.proof))?;
+                return Ok(json!({
+                    "proof": proof.proof,
+                    "verify_instruction": proof.verify_instruction,
+                    "context_state_account": context_state.to_string(),
+                }));
+            }
+            let proof_instruction_data = siblings
+                .at_offset(proof.offset)
+                .filter(|(_, program_key, _)| {
+                    *program_key == NativeProgram::ZkElGamalProof.program_id()
+                })
+                .map(|(_, _, data)| data);
+            let proof_instruction =
+                proof_instruction_data.and_then(|data| proof_instruction_name(*data.first()?));
+            let mut location = json!({
+                "proof": proof.proof,
+                "verify_instruction": proof.verify_instruction,
+                "instruction_offset": proof.offset,
+                "instruction_index": siblings.at_offset(proof.offset).map(|(index, _, _)| index),
+                "proof_instruction": proof_instruction,
+                "proof_instruction_matches": proof_instruction == Some(proof.verify_instruction),
+            });
+            if proof_instruction_data.is_some_and(reads_proof_from_record_account) {
+                let record = namer.name(&format!("{}_proof_record", proof.proof))?;
+                location["record_account"] = Value::String(record.to_string());
+            }
+            Ok(location)
+        })
+        .collect::<Result<Vec<Value>, InstructionParseError>>()?;
+    Ok(Value::Array(locations))
+}
+
+fn ciphertext_hex(
+    data: &mut InstructionDataReader,
+    len: usize,
+    field: &str,
+) -> Result<Value, InstructionParseError> {
+    Ok(Value::String(hex::encode(data.bytes(len, field)?)))
+}
+
+// An ElGamal pubkey packed as 32 bytes, where the all zero key means none
+fn optional_elgamal_pubkey(
+    data: &mut InstructionDataReader,
+    field: &str,
+) -> Result<Value, InstructionParseError> {
+    let key = data.bytes(LEN_ELGAMAL_PUBKEY_BYTES, field)?;
+    Ok(if key.iter().all(|b| *b == 0) {
+        Value::Null
+    } else {
+        Value::String(hex::encode(key))
+    })
+}
+
+/*
+Parse Confidential Transfer Instruction
+- Decodes the instructions of the Token 2022 confidential transfer extension, given the data that follows the extension tag
+- Ciphertexts and ElGamal pubkeys are reported as hex, since they can only be read with the owner's or auditor's keys
+- Instructions that rely on zero-knowledge proofs report where each proof is verified under "proofs"
+*/
+#[allow(clippy::too_many_lines)] // One match arm per confidential transfer instruction
+pub fn parse_confidential_transfer_instruction(
+    data: &[u8],
+    accounts: &[AccountAddress],
+    siblings: SiblingInstructions,
+) -> Result<SolanaParsedInstructionData, InstructionParseError> {
+    let (&tag, data) = data.split_first().ok_or_else(|| {
+        data_parse_error(
+            PROGRAM,
+            "error while parsing ConfidentialTransfer extension instruction -- instruction tag"
+                .to_string(),
+        )
+    })?;
+    let instruction_name = confidential_transfer_instruction_name(tag).ok_or_else(|| {
+        data_parse_error(
+            PROGRAM,
+            format!("unknown ConfidentialTransfer extension instruction tag {tag}"),
+        )
+    })?;
+
+    let mut data = InstructionDataReader::new(PROGRAM, instruction_name, data);
+    let mut namer = AccountNamer::new(instruction_name, accounts);
+    let proof = |proof, verify_instruction, offset| ProofReference {
+        proof,
+        verify_instruction,
+        offset,
+    };
+
+    let (named_accounts, args) = match tag {
+        0 => {
+            let authority = data.optional_nonzero_pubkey("authority")?;
+            let auto_approve_new_accounts = data.u8("auto_approve_new_accounts")? != 0;
+            let auditor_elgamal_pubkey =
+                optional_elgamal_pubkey(&mut data, "auditor_elgamal_pubkey")?;
+            (
+                named_accounts(instruction_name, accounts, &["mint"], &[])?,
+                json!({
+                    "authority": optional_pubkey_value(authority),
+                    "auto_approve_new_accounts": auto_approve_new_accounts,
+                    "auditor_elgamal_pubkey": auditor_elgamal_pubkey,
+                }),
+            )
+        }
+        1 => {
+            let auto_approve_new_accounts = data.u8("auto_approve_new_accounts")? != 0;
+            let auditor_elgamal_pubkey =
+                optional_elgamal_pubkey(&mut data, "auditor_elgamal_pubkey")?;
+            (
+                named_accounts(instruction_name, accounts, &["mint", "authority"], &[])?,
+                json!({
+                    "auto_approve_new_accounts": auto_approve_new_accounts,
+                    "auditor_elgamal_pubkey": auditor_elgamal_pubkey,
+                }),
+            )
+        }
+        2 => {
+            let decryptable_zero_balance = ciphertext_hex(
+                &mut data,
+                LEN_DECRYPTABLE_BALANCE_BYTES,
+                "decryptable_zero_balance",
+            )?;
+            let maximum_pending_balance_credit_counter =
+                data.u64("maximum_pending_balance_credit_counter")?;
+            let proof_instruction_offset = data.i8("proof_instruction_offset")?;
+            namer.name("account")?;
+            namer.name("mint")?;
+            let proofs = locate_proofs(
+                &mut namer,
+                &[proof(
+                    "pubkey_validity",
+                    "VerifyPubkeyValidity",
+                    proof_instruction_offset,
+                )],
+                siblings,
+            )?;
+            (
+                namer.authority_with_signers("owner")?,
+                json!({
+                    "decryptable_zero_balance": decryptable_zero_balance,
+                    "maximum_pending_balance_credit_counter": maximum_pending_balance_credit_counter,
+                    "proof_instruction_offset": proof_instruction_offset,
+                    "proofs": proofs,
+                }),
+            )
+        }
+        3 => (
+            named_accounts(
+                instruction_name,
+                accounts,
+                &["account", "mint", "authority"],
+                &[],
+            )?,
+            json!({}),
+        ),
+        4 => {
+            let proof_instruction_offset = data.i8("proof_instruction_offset")?;
+            namer.name("account")?;
+            let proofs = locate_proofs(
+                &mut namer,
+                &[proof(
+                    "zero_ciphertext",
+                    "VerifyZeroCiphertext",
+                    proof_instruction_offset,
+                )],
+                siblings,
+            )?;
+            (
+                namer.authority_with_signers("owner")?,
+                json!({
+                    "proof_instruction_offset": proof_instruction_offset,
+                    "proofs": proofs,
+                }),
+            )
+        }
+        5 => {
+            let amount = data.u64("amount")?;
+            let decimals = data.u8("decimals")?;
+            namer.name("account")?;
+            namer.name("mint")?;
+            (
+                namer.authority_with_signers("owner")?,
+                json!({ "amount": amount, "decimals": decimals }),
+            )
+        }
+        6 => {
+            let amount = data.u64("amount")?;
+            let decimals = data.u8("decimals")?;
+            let new_decryptable_available_balance = ciphertext_hex(
+                &mut data,
+                LEN_DECRYPTABLE_BALANCE_BYTES,
+                "new_decryptable_available_balance",
+            )?;
+            let equality_proof_instruction_offset = data.i8("equality_proof_instruction_offset")?;
+            let range_proof_instruction_offset = data.i8("range_proof_instruction_offset")?;
+            namer.name("account")?;
+            namer.name("mint")?;
+            let proofs = locate_proofs(
+                &mut namer,
+                &[
+                    proof(
+                        "equality",
+                        "VerifyCiphertextCommitmentEquality",
+                        equality_proof_instruction_offset,
+                    ),
+                    proof(
+                        "range",
+                        "VerifyBatchedRangeProofU64",
+                        range_proof_instruction_offset,
+                    ),
+                ],
+                siblings,
+            )?;
+            (
+                namer.authority_with_signers("owner")?,
+                json!({
+                    "amount": amount,
+                    "decimals": decimals,
+                    "new_decryptable_available_balance": new_decryptable_available_balance,
+                    "equality_proof_instruction_offset": equality_proof_instruction_offset,
+                    "range_proof_instruction_offset": range_proof_instruction_offset,
+                    "proofs": proofs,
+                }),
+            )
+        }
+        7 | 13 => {
+            let new_source_decryptable_available_balance = ciphertext_hex(
+                &mut data,
+                LEN_DECRYPTABLE_BALANCE_BYTES,
+                "new_source_decryptable_available_balance",
+            )?;
+            let transfer_amount_auditor_ciphertext_lo = ciphertext_hex(
+                &mut data,
+                LEN_ELGAMAL_CIPHERTEXT_BYTES,
+                "transfer_amount_auditor_ciphertext_lo",
+            )?;
+            let transfer_amount_auditor_ciphertext_hi = ciphertext_hex(
+                &mut data,
+                LEN_ELGAMAL_CIPHERTEXT_BYTES,
+                "transfer_amount_auditor_ciphertext_hi",
+            )?;
+            let mut args = json!({
+                "new_source_decryptable_available_balance": new_source_decryptable_available_balance,
+                "transfer_amount_auditor_ciphertext_lo": transfer_amount_auditor_ciphertext_lo,
+                "transfer_amount_auditor_ciphertext_hi": transfer_amount_auditor_ciphertext_hi,
+            });
+            // A transfer with fee also proves the fee was computed correctly and encrypted for the fee collector
+            let proof_fields: &[(&str, &str, &str)] = if tag == 7 {
+                &[
+                    (
+                        "equality",
+                        "equality_proof_instruction_offset",
+                        "VerifyCiphertextCommitmentEquality",
+                    ),
+                    (
+                        "ciphertext_validity",
+                        "ciphertext_validity_proof_instruction_offset",
+                        "VerifyBatchedGroupedCiphertext3HandlesValidity",
+                    ),
+                    (
+                        "range",
+                        "range_proof_instruction_offset",
+                        "VerifyBatchedRangeProofU128",
+                    ),
+                ]
+            } else {
+                &[
+                    (
+                        "equality",
+                        "equality_proof_instruction_offset",
+                        "VerifyCiphertextCommitmentEquality",
+                    ),
+                    (
+                        "transfer_amount_ciphertext_validity",
+                        "transfer_amount_ciphertext_validity_proof_instruction_offset",
+                        "VerifyBatchedGroupedCiphertext3HandlesValidity",
+                    ),
+                    (
+                        "fee_sigma",
+                        "fee_sigma_proof_instruction_offset",
+                        "VerifyPercentageWithCap",
+                    ),
+                    (
+                        "fee_ciphertext_validity",
+                        "fee_ciphertext_validity_proof_instruction_offset",
+                        "VerifyBatchedGroupedCiphertext2HandlesValidity",
+                    ),
+                    (
+                        "range",
+                        "range_proof_instruction_offset",
+                        "VerifyBatchedRangeProofU256",
+                    ),
+                ]
+            };
+            let mut proofs = vec![];
+            for (name, field, verify_instruction) in proof_fields {
+                let offset = data.i8(field)?;
+                args[*field] = json!(offset);
+                proofs.push(proof(name, verify_instruction, offset));
+            }
+            namer.name("source")?;
+            namer.name("mint")?;
+            namer.name("destination")?;
+            args["proofs"] = locate_proofs(&mut namer, &proofs, siblings)?;
+            (namer.authority_with_signers("authority")?, args)
+        }
+        8 => {
+            let expected_pending_balance_credit_counter =
+                data.u64("expected_pending_balance_credit_counter")?;
+            let new_decryptable_available_balance = ciphertext_hex(
+                &mut data,
+                LEN_DECRYPTABLE_BALANCE_BYTES,
+                "new_decryptable_available_balance",
+            )?;
+            namer.name("account")?;
+            (
+                namer.authority_with_signers("owner")?,
+                json!({
+                    "expected_pending_balance_credit_counter": expected_pending_balance_credit_counter,
+                    "new_decryptable_available_balance": new_decryptable_available_balance,
+                }),
+            )
+        }
+        9..=12 => {
+            namer.name("account")?;
+            (namer.authority_with_signers("owner")?, json!({}))
+        }
+        14 => (
+            named_accounts(
+                instruction_name,
+                accounts,
+                &["account", "mint", "elgamal_registry"],
+                &["payer", "system_program"],
+            )?,
+            json!({}),
+        ),
+        _ => unreachable!("every named confidential transfer instruction is decoded"),
+    };
+
+    Ok(native_instruction(
+        PROGRAM,
+        instruction_name,
+        &[CONFIDENTIAL_TRANSFER_EXTENSION_TAG, tag],
+        named_accounts,
+        args,
+    ))
+}
@@ -0,0 +1,82 @@
+use super::{data_parse_error, native_instruction};
+use crate::solana::structs::{
+    AccountAddress, InstructionParseError, NativeProgram, SolanaInstruction, SolanaMemo,
+    SolanaParsedInstructionData,
+};
+use serde_json::json;
+use std::collections::HashMap;
+
+/*
+Parse Memo Instruction
+- The whole instruction data is the memo, which both Memo program versions require to be valid UTF-8
+- Memo v2 requires every account passed to it to sign, so the accounts that sign the transaction are named signer_1, signer_2 and so on
+- Accounts that do not sign, such as lookup accounts or the unchecked accounts of a Memo v1 instruction, are named account_1, account_2 and so on
+*/
+pub fn parse_memo_instruction(
+    program: NativeProgram,
+    instruction_data: &[u8],
+    accounts: &[AccountAddress],
+) -> Result<SolanaParsedInstructionData, InstructionParseError> {
+    let memo = std::str::from_utf8(instruction_data)
+        .map_err(|e| data_parse_error(program, format!("memo is not valid UTF-8: {e}")))?;
+    let (signers, others): (Vec<&AccountAddress>, Vec<&AccountAddress>) = accounts
+        .iter()
+        .partition(|account| matches!(account, AccountAddress::Static(acct) if acct.signer));
+    let named_accounts: HashMap<String, String> = signers
+        .iter()
+        .enumerate()
+        .map(|(i, signer)| (format!("signer_{}", i + 1), signer.to_string()))
+        .chain(
+            others
+                .iter()
+                .enumerate()
+                .map(|(i, account)| (format!("account_{}", i + 1), account.to_string())),
+        )
+        .collect();
+    // Memo instructions have no discriminator
+    Ok(native_instruction(
+        program,
+        "Memo",
+        &[],
+        named_accounts,
+        json!({ "memo": memo }),
+    ))
+}
+
+/*
+Transaction Memos
+- Lists every Memo v1 and v2 instruction in instruction order, whether or not it decoded
+- Memos that are not valid UTF-8 are rejected by both programs, so they are listed without text; the raw bytes are always in memo_hex
+- Only accounts that sign the transaction are listed as signers, which leaves out lookup accounts since those can never sign
+*/
+pub fn memos(instructions: &[SolanaInstruction]) -> Vec<SolanaMemo> {
+    instructions
+        .iter()
+        .enumerate()
+        .filter(|(_, instruction)| {
+            matches!(
+                NativeProgram::from_program_id(&instruction.program_key),
+                Some(NativeProgram::Memo | NativeProgram::MemoV1)
+            )
+        })
+        .map(|(instruction_index, instruction)| {
+            let memo = hex::decode(&instruction.instruction_data_hex)
+                .ok()
+                .and_then(|data| String::from_utf8(data).ok());
+            // account metas follow the instruction's account order
+            let signers = instruction
+                .account_metas
+                .iter()
+                .filter(|account| account.signer)
+                .filter_map(|account| account.account_key.clone())
+                .collect();
+            SolanaMemo {
+                instruction_index,
+                program_id: instruction.program_key.clone(),
+                memo,
+                memo_hex: instruction.instruction_data_hex.clone(),
+                signers,
+            }
+        })
+        .collect()
+}
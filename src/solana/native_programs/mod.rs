@@ -0,0 +1,285 @@
+use crate::solana::structs::{
+    AccountAddress, IdlSource, InstructionParseError, NativeProgram, SolanaParsedInstructionData,
+    SplInterface,
+};
+use serde_json::{Map, Value};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+
+pub mod address_lookup_table;
+pub mod associated_token_account;
+pub mod compute_budget;
+pub mod confidential_transfer;
+pub mod memo;
+pub mod spl_interfaces;
+pub mod stake;
+pub mod system;
+pub mod token;
+pub mod token_2022;
+pub mod vote;
+pub mod zk_elgamal_proof;
+
+/// The program and data of every instruction in a transaction, along with the index of the one being decoded
+/// Confidential transfer instructions refer to the proof instructions they rely on by an offset from themselves
+#[derive(Debug, Clone, Copy)]
+pub struct SiblingInstructions<'a> {
+    pub instructions: &'a [(String, &'a [u8])],
+    pub current: usize,
+}
+
+impl<'a> SiblingInstructions<'a> {
+    // Returns the index, program key and data of the instruction at the given offset from the current one
+    fn at_offset(&self, offset: i8) -> Option<(usize, &'a str, &'a [u8])> {
+        let index = self.current.checked_add_signed(offset as isize)?;
+        let (program_key, data) = self.instructions.get(index)?;
+        Some((index, program_key.as_str(), *data))
+    }
+}
+
+// Name the accounts of a natively decoded instruction in the order the program expects them
+// Optional accounts are only named when present, and accounts past the named ones are ignored just like the runtime does
+fn named_accounts(
+    instruction_name: &str,
+    accounts: &[AccountAddress],
+    required: &[&str],
+    optional: &[&str],
+) -> Result<HashMap<String, String>, InstructionParseError> {
+    if accounts.len() < required.len() {
+        return Err(InstructionParseError::NotEnoughAccounts {
+            instruction_name: instruction_name.to_string(),
+            expected: required.len(),
+            found: accounts.len(),
+        });
+    }
+    Ok(required
+        .iter()
+        .chain(optional)
+        .zip(accounts)
+        .map(|(name, account)| (name.to_string(), account.to_string()))
+        .collect())
+}
+
+// Like named_accounts, but every account past the required ones is a multisig signer, named signer_1, signer_2 and so on
+fn named_accounts_with_signers(
+    instruction_name: &str,
+    accounts: &[AccountAddress],
+    required: &[&str],
+) -> Result<HashMap<String, String>, InstructionParseError> {
+    let mut named = named_accounts(instruction_name, accounts, required, &[])?;
+    for (i, signer) in accounts[required.len()..].iter().enumerate() {
+        named.insert(format!("signer_{}", i + 1), signer.to_string());
+    }
+    Ok(named)
+}
+
+// Build the parsed instruction of a natively decoded instruction, in the same shape as an IDL decoded one
+// Native programs have no IDL, so the IDL hash is left empty
+fn native_instruction(
+    program: NativeProgram,
+    instruction_name: &str,
+    discriminator: &[u8],
+    named_accounts: HashMap<String, String>,
+    args: Value,
+) -> SolanaParsedInstructionData {
+    decoded_instruction(
+        IdlSource::Native(program),
+        instruction_name,
+        discriminator,
+        named_accounts,
+        args,
+    )
+}
+
+// Like native_instruction, for an instruction of an SPL interface that may be implemented by any program
+fn interface_instruction(
+    interface: SplInterface,
+    instruction_name: &str,
+    discriminator: &[u8],
+    named_accounts: HashMap<String, String>,
+    args: Value,
+) -> SolanaParsedInstructionData {
+    decoded_instruction(
+        IdlSource::Interface(interface),
+        instruction_name,
+        discriminator,
+        named_accounts,
+        args,
+    )
+}
+
+fn decoded_instruction(
+    idl_source: IdlSource,
+    instruction_name: &str,
+    discriminator: &[u8],
+    named_accounts: HashMap<String, String>,
+    args: Value,
+) -> SolanaParsedInstructionData {
+    let program_call_args = match args {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    };
+    SolanaParsedInstructionData {
+        instruction_name: instruction_name.to_string(),
+        discriminator: hex::encode(discriminator),
+        named_accounts,
+        program_call_args,
+        idl_source,
+        idl_hash: String::new(),
+    }
+}
+
+fn data_parse_error(program: NativeProgram, error: String) -> InstructionParseError {
+    InstructionParseError::DataParseError {
+        program_name: program.program_name().to_string(),
+        error,
+    }
+}
+
+// Reads the little endian fields of native instruction data in order
+struct InstructionDataReader<'a> {
+    // the program or interface named in parse errors
+    program_name: &'static str,
+    instruction_name: &'a str,
+    data: &'a [u8],
+}
+
+impl<'a> InstructionDataReader<'a> {
+    fn new(program: NativeProgram, instruction_name: &'a str, data: &'a [u8]) -> Self {
+        InstructionDataReader {
+            program_name: program.program_name(),
+            instruction_name,
+            data,
+        }
+    }
+
+    fn for_interface(interface: SplInterface, instruction_name: &'a str, data: &'a [u8]) -> Self {
+        InstructionDataReader {
+            program_name: interface.interface_name(),
+            instruction_name,
+            data,
+        }
+    }
+
+    fn error(&self, detail: String) -> InstructionParseError {
+        InstructionParseError::DataParseError {
+            program_name: self.program_name.to_string(),
+            error: format!(
+                "error while parsing {} instruction -- {detail}",
+                self.instruction_name
+            ),
+        }
+    }
+
+    fn bytes(&mut self, len: usize, field: &str) -> Result<&'a [u8], InstructionParseError> {
+        if self.data.len() < len {
+            return Err(self.error(field.to_string()));
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self, field: &str) -> Result<[u8; N], InstructionParseError> {
+        Ok(self
+            .bytes(N, field)?
+            .try_into()
+            .expect("slice has the requested length"))
+    }
+
+    fn u8(&mut self, field: &str) -> Result<u8, InstructionParseError> {
+        Ok(self.array::<1>(field)?[0])
+    }
+
+    fn u16(&mut self, field: &str) -> Result<u16, InstructionParseError> {
+        Ok(u16::from_le_bytes(self.array(field)?))
+    }
+
+    fn u32(&mut self, field: &str) -> Result<u32, InstructionParseError> {
+        Ok(u32::from_le_bytes(self.array(field)?))
+    }
+
+    fn i8(&mut self, field: &str) -> Result<i8, InstructionParseError> {
+        Ok(i8::from_le_bytes(self.array(field)?))
+    }
+
+    fn i16(&mut self, field: &str) -> Result<i16, InstructionParseError> {
+        Ok(i16::from_le_bytes(self.array(field)?))
+    }
+
+    fn u64(&mut self, field: &str) -> Result<u64, InstructionParseError> {
+        Ok(u64::from_le_bytes(self.array(field)?))
+    }
+
+    fn pubkey(&mut self, field: &str) -> Result<Pubkey, InstructionParseError> {
+        Ok(Pubkey::new_from_array(self.array(field)?))
+    }
+
+    fn bool(&mut self, field: &str) -> Result<bool, InstructionParseError> {
+        match self.u8(field)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(self.error(format!("invalid bool {value} for {field}"))),
+        }
+    }
+
+    // The one byte tag in front of an optional value, which is 1 when the value follows
+    fn option_tag(&mut self, field: &str) -> Result<bool, InstructionParseError> {
+        match self.u8(field)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(self.error(format!("invalid option tag {tag} for {field}"))),
+        }
+    }
+
+    fn optional_pubkey(&mut self, field: &str) -> Result<Option<Pubkey>, InstructionParseError> {
+        if self.option_tag(field)? {
+            Ok(Some(self.pubkey(field)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn optional_u64(&mut self, field: &str) -> Result<Option<u64>, InstructionParseError> {
+        if self.option_tag(field)? {
+            Ok(Some(self.u64(field)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    // A utf8 string packed as a u32 length followed by its bytes
+    fn string(&mut self, field: &str) -> Result<String, InstructionParseError> {
+        let len = self.u32(field)? as usize;
+        let bytes = self.bytes(len, field)?;
+        String::from_utf8(bytes.to_vec())
+            .map_err(|e| self.error(format!("invalid utf8 in {field}: {e}")))
+    }
+
+    // An optional pubkey packed as 32 bytes, where the all zero key means none
+    fn optional_nonzero_pubkey(
+        &mut self,
+        field: &str,
+    ) -> Result<Option<Pubkey>, InstructionParseError> {
+        let key = self.pubkey(field)?;
+        Ok((key != Pubkey::default()).then_some(key))
+    }
+
+    // Every u16 left in the data, such as a list of extension types
+    fn u16s_to_end(&mut self, field: &str) -> Result<Vec<u16>, InstructionParseError> {
+        let mut values = Vec::with_capacity(self.data.len() / 2);
+        while !self.data.is_empty() {
+            values.push(self.u16(field)?);
+        }
+        Ok(values)
+    }
+
+    // Whatever data has not been read yet
+    fn rest(&mut self) -> &'a [u8] {
+        std::mem::take(&mut self.data)
+    }
+}
+
+// Renders an optional pubkey as a base58 string, or null when absent
+fn optional_pubkey_value(key: Option<Pubkey>) -> Value {
+    key.map_or(Value::Null, |k| Value::String(k.to_string()))
+}
@@ -0,0 +1,182 @@
+use super::{interface_instruction, named_accounts, optional_pubkey_value, InstructionDataReader};
+use crate::solana::structs::{
+    AccountAddress, InstructionParseError, SolanaParsedInstructionData, SplInterface,
+};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+const LEN_INTERFACE_DISCRIMINATOR_BYTES: usize = 8;
+
+// The hash input of every decoded interface instruction, with the interface and the instruction name
+// Note that the hash inputs are not always derived from the instruction name, and the transfer hook interface uses dashes
+// References: spl_token_metadata_interface::instruction, spl_token_group_interface::instruction and spl_transfer_hook_interface::instruction
+const INTERFACE_INSTRUCTIONS: [(&str, SplInterface, &str); 8] = [
+    (
+        "spl_token_metadata_interface:initialize_account",
+        SplInterface::TokenMetadata,
+        "Initialize",
+    ),
+    (
+        "spl_token_metadata_interface:updating_field",
+        SplInterface::TokenMetadata,
+        "UpdateField",
+    ),
+    (
+        "spl_token_metadata_interface:remove_key_ix",
+        SplInterface::TokenMetadata,
+        "RemoveKey",
+    ),
+    (
+        "spl_token_metadata_interface:update_the_authority",
+        SplInterface::TokenMetadata,
+        "UpdateAuthority",
+    ),
+    (
+        "spl_token_metadata_interface:emitter",
+        SplInterface::TokenMetadata,
+        "Emit",
+    ),
+    (
+        "spl_token_group_interface:initialize_token_group",
+        SplInterface::TokenGroup,
+        "InitializeGroup",
+    ),
+    (
+        "spl_token_group_interface:initialize_member",
+        SplInterface::TokenGroup,
+        "InitializeMember",
+    ),
+    (
+        "spl-transfer-hook-interface:execute",
+        SplInterface::TransferHook,
+        "Execute",
+    ),
+];
+
+/// Computes the discriminator of an SPL interface instruction: the first 8 bytes of the sha256 hash of its namespaced hash input
+pub fn compute_interface_discriminator(
+    hash_input: &str,
+) -> [u8; LEN_INTERFACE_DISCRIMINATOR_BYTES] {
+    let hash = Sha256::digest(hash_input.as_bytes());
+    let mut discriminator = [0; LEN_INTERFACE_DISCRIMINATOR_BYTES];
+    discriminator.copy_from_slice(&hash[..LEN_INTERFACE_DISCRIMINATOR_BYTES]);
+    discriminator
+}
+
+/*
+Parse SPL Interface Instruction
+- Decodes token metadata, token group and transfer hook interface instructions sent to any program
+- Returns None when the instruction data does not start with the discriminator of a decoded interface instruction
+*/
+#[allow(clippy::too_many_lines)] // One match arm per decoded interface instruction discriminator
+pub fn parse_interface_instruction(
+    instruction_data: &[u8],
+    accounts: &[AccountAddress],
+) -> Result<Option<SolanaParsedInstructionData>, InstructionParseError> {
+    let Some((discriminator, data)) =
+        instruction_data.split_first_chunk::<LEN_INTERFACE_DISCRIMINATOR_BYTES>()
+    else {
+        return Ok(None);
+    };
+    let Some(&(_, interface, instruction_name)) = INTERFACE_INSTRUCTIONS
+        .iter()
+        .find(|(hash_input, _, _)| compute_interface_discriminator(hash_input) == *discriminator)
+    else {
+        return Ok(None);
+    };
+
+    let mut data = InstructionDataReader::for_interface(interface, instruction_name, data);
+    let fixed = |names: &[&str]| named_accounts(instruction_name, accounts, names, &[]);
+
+    let (named_accounts, args) = match instruction_name {
+        "Initialize" => {
+            let name = data.string("name")?;
+            let symbol = data.string("symbol")?;
+            let uri = data.string("uri")?;
+            (
+                fixed(&["metadata", "update_authority", "mint", "mint_authority"])?,
+                json!({ "name": name, "symbol": symbol, "uri": uri }),
+            )
+        }
+        // The field is one of the base metadata fields, or a key of the additional metadata
+        "UpdateField" => {
+            let field = match data.u8("field")? {
+                0 => json!("Name"),
+                1 => json!("Symbol"),
+                2 => json!("Uri"),
+                3 => json!({ "Key": data.string("field")? }),
+                tag => {
+                    return Err(data.error(format!("unknown field {tag}")));
+                }
+            };
+            let value = data.string("value")?;
+            (
+                fixed(&["metadata", "update_authority"])?,
+                json!({ "field": field, "value": value }),
+            )
+        }
+        "RemoveKey" => {
+            let idempotent = data.bool("idempotent")?;
+            let key = data.string("key")?;
+            (
+                fixed(&["metadata", "update_authority"])?,
+                json!({ "idempotent": idempotent, "key": key }),
+            )
+        }
+        "UpdateAuthority" => (
+            fixed(&["metadata", "current_update_authority"])?,
+            json!({ "new_authority": optional_pubkey_value(data.optional_nonzero_pubkey("new_authority")?) }),
+        ),
+        "Emit" => {
+            let start = data.optional_u64("start")?;
+            let end = data.optional_u64("end")?;
+            (fixed(&["metadata"])?, json!({ "start": start, "end": end }))
+        }
+        "InitializeGroup" => {
+            let update_authority = data.optional_nonzero_pubkey("update_authority")?;
+            let max_size = data.u64("max_size")?;
+            (
+                fixed(&["group", "mint", "mint_authority"])?,
+                json!({
+                    "update_authority": optional_pubkey_value(update_authority),
+                    "max_size": max_size,
+                }),
+            )
+        }
+        "InitializeMember" => (
+            fixed(&[
+                "member",
+                "member_mint",
+                "member_mint_authority",
+                "group",
+                "group_update_authority",
+            ])?,
+            json!({}),
+        ),
+        // Execute is invoked by Token 2022 during a transfer, followed by the extra accounts the hook program asked for
+        "Execute" => {
+            let amount = data.u64("amount")?;
+            let required = [
+                "source",
+                "mint",
+                "destination",
+                "authority",
+                "extra_account_metas",
+            ];
+            let mut named = fixed(&required)?;
+            for (i, account) in accounts[required.len()..].iter().enumerate() {
+                named.insert(format!("extra_account_{}", i + 1), account.to_string());
+            }
+            (named, json!({ "amount": amount }))
+        }
+        _ => unreachable!("every listed interface instruction is decoded"),
+    };
+
+    Ok(Some(interface_instruction(
+        interface,
+        instruction_name,
+        discriminator,
+        named_accounts,
+        args,
+    )))
+}
@@ -0,0 +1,326 @@
+use super::{data_parse_error, named_accounts, native_instruction};
+use crate::solana::structs::{
+    AccountAddress, IdlSource, InstructionParseError, NativeProgram, SolanaInstruction,
+    SolanaParsedInstructionData, SolanaStakeSummary, StakeAuthorityChange, StakeDelegation,
+    StakeWithdrawal, TransactionWarning,
+};
+use serde_json::json;
+use solana_sdk::stake::{
+    instruction::{AuthorizeCheckedWithSeedArgs, AuthorizeWithSeedArgs, StakeInstruction},
+    state::StakeAuthorize,
+};
+
+const PROGRAM: NativeProgram = NativeProgram::Stake;
+
+// Stake program instructions are bincode encoded, starting with a little endian u32 tag
+const LEN_STAKE_INSTRUCTION_TAG_BYTES: usize = 4;
+
+/// Decode the data of a Stake program instruction
+pub fn decode_stake_instruction(
+    instruction_data: &[u8],
+) -> Result<StakeInstruction, InstructionParseError> {
+    bincode::deserialize(instruction_data)
+        .map_err(|e| data_parse_error(PROGRAM, format!("could not parse stake instruction: {e}")))
+}
+
+fn authority_type(stake_authorize: &StakeAuthorize) -> &'static str {
+    match stake_authorize {
+        StakeAuthorize::Staker => "Staker",
+        StakeAuthorize::Withdrawer => "Withdrawer",
+    }
+}
+
+/*
+Parse Stake Instruction
+- Names the accounts and arguments of every Stake program instruction
+- Account orders follow the documentation of each variant of the Solana SDK's StakeInstruction
+- The lockup authority is only passed to authorize and withdraw while a lockup is in force, so it is named when present
+*/
+#[allow(deprecated)] // Redelegate is deprecated but still decoded
+#[allow(clippy::too_many_lines)] // One match arm per StakeInstruction variant
+pub fn parse_stake_instruction(
+    instruction: &StakeInstruction,
+    instruction_data: &[u8],
+    accounts: &[AccountAddress],
+) -> Result<SolanaParsedInstructionData, InstructionParseError> {
+    let (instruction_name, required, optional, args): (&str, &[&str], &[&str], _) =
+        match instruction {
+            StakeInstruction::Initialize(authorized, lockup) => (
+                "Initialize",
+                &["stake_account", "rent_sysvar"],
+                &[],
+                json!({
+                    "staker": authorized.staker.to_string(),
+                    "withdrawer": authorized.withdrawer.to_string(),
+                    "lockup": {
+                        "unix_timestamp": lockup.unix_timestamp,
+                        "epoch": lockup.epoch,
+                        "custodian": lockup.custodian.to_string(),
+                    },
+                }),
+            ),
+            StakeInstruction::Authorize(new_authority, stake_authorize) => (
+                "Authorize",
+                &["stake_account", "clock_sysvar", "authority"],
+                &["lockup_authority"],
+                json!({
+                    "new_authority": new_authority.to_string(),
+                    "authority_type": authority_type(stake_authorize),
+                }),
+            ),
+            StakeInstruction::DelegateStake => (
+                "DelegateStake",
+                &[
+                    "stake_account",
+                    "vote_account",
+                    "clock_sysvar",
+                    "stake_history_sysvar",
+                    "stake_config",
+                    "stake_authority",
+                ],
+                &[],
+                json!({}),
+            ),
+            StakeInstruction::Split(lamports) => (
+                "Split",
+                &["stake_account", "split_stake_account", "stake_authority"],
+                &[],
+                json!({ "lamports": lamports }),
+            ),
+            StakeInstruction::Withdraw(lamports) => (
+                "Withdraw",
+                &[
+                    "stake_account",
+                    "recipient_account",
+                    "clock_sysvar",
+                    "stake_history_sysvar",
+                    "withdraw_authority",
+                ],
+                &["lockup_authority"],
+                json!({ "lamports": lamports }),
+            ),
+            StakeInstruction::Deactivate => (
+                "Deactivate",
+                &["stake_account", "clock_sysvar", "stake_authority"],
+                &[],
+                json!({}),
+            ),
+            // each lockup field is only updated when present
+            StakeInstruction::SetLockup(lockup) => (
+                "SetLockup",
+                &["stake_account", "lockup_or_withdraw_authority"],
+                &[],
+                json!({
+                    "unix_timestamp": lockup.unix_timestamp,
+                    "epoch": lockup.epoch,
+                    "custodian": lockup.custodian.map(|c| c.to_string()),
+                }),
+            ),
+            StakeInstruction::Merge => (
+                "Merge",
+                &[
+                    "destination_stake_account",
+                    "source_stake_account",
+                    "clock_sysvar",
+                    "stake_history_sysvar",
+                    "stake_authority",
+                ],
+                &[],
+                json!({}),
+            ),
+            // the current authority is the address derived from the base account, the seed and the owner
+            StakeInstruction::AuthorizeWithSeed(AuthorizeWithSeedArgs {
+                new_authorized_pubkey,
+                stake_authorize,
+                authority_seed,
+                authority_owner,
+            }) => (
+                "AuthorizeWithSeed",
+                &["stake_account", "authority_base", "clock_sysvar"],
+                &["lockup_authority"],
+                json!({
+                    "new_authority": new_authorized_pubkey.to_string(),
+                    "authority_type": authority_type(stake_authorize),
+                    "authority_seed": authority_seed,
+                    "authority_owner": authority_owner.to_string(),
+                }),
+            ),
+            StakeInstruction::InitializeChecked => (
+                "InitializeChecked",
+                &[
+                    "stake_account",
+                    "rent_sysvar",
+                    "stake_authority",
+                    "withdraw_authority",
+                ],
+                &[],
+                json!({}),
+            ),
+            // the checked variants take the new authority as a signing account instead of an argument
+            StakeInstruction::AuthorizeChecked(stake_authorize) => (
+                "AuthorizeChecked",
+                &[
+                    "stake_account",
+                    "clock_sysvar",
+                    "authority",
+                    "new_authority",
+                ],
+                &["lockup_authority"],
+                json!({ "authority_type": authority_type(stake_authorize) }),
+            ),
+            StakeInstruction::AuthorizeCheckedWithSeed(AuthorizeCheckedWithSeedArgs {
+                stake_authorize,
+                authority_seed,
+                authority_owner,
+            }) => (
+                "AuthorizeCheckedWithSeed",
+                &[
+                    "stake_account",
+                    "authority_base",
+                    "clock_sysvar",
+                    "new_authority",
+                ],
+                &["lockup_authority"],
+                json!({
+                    "authority_type": authority_type(stake_authorize),
+                    "authority_seed": authority_seed,
+                    "authority_owner": authority_owner.to_string(),
+                }),
+            ),
+            StakeInstruction::SetLockupChecked(lockup) => (
+                "SetLockupChecked",
+                &["stake_account", "lockup_or_withdraw_authority"],
+                &["new_lockup_authority"],
+                json!({ "unix_timestamp": lockup.unix_timestamp, "epoch": lockup.epoch }),
+            ),
+            StakeInstruction::GetMinimumDelegation => ("GetMinimumDelegation", &[], &[], json!({})),
+            StakeInstruction::DeactivateDelinquent => (
+                "DeactivateDelinquent",
+                &[
+                    "stake_account",
+                    "delinquent_vote_account",
+                    "reference_vote_account",
+                ],
+                &[],
+                json!({}),
+            ),
+            StakeInstruction::Redelegate => (
+                "Redelegate",
+                &[
+                    "stake_account",
+                    "new_stake_account",
+                    "vote_account",
+                    "stake_config",
+                    "stake_authority",
+                ],
+                &[],
+                json!({}),
+            ),
+            StakeInstruction::MoveStake(lamports) => (
+                "MoveStake",
+                &[
+                    "source_stake_account",
+                    "destination_stake_account",
+                    "stake_authority",
+                ],
+                &[],
+                json!({ "lamports": lamports }),
+            ),
+            StakeInstruction::MoveLamports(lamports) => (
+                "MoveLamports",
+                &[
+                    "source_stake_account",
+                    "destination_stake_account",
+                    "stake_authority",
+                ],
+                &[],
+                json!({ "lamports": lamports }),
+            ),
+        };
+
+    let named_accounts = named_accounts(instruction_name, accounts, required, optional)?;
+    Ok(native_instruction(
+        PROGRAM,
+        instruction_name,
+        &instruction_data[..LEN_STAKE_INSTRUCTION_TAG_BYTES],
+        named_accounts,
+        args,
+    ))
+}
+
+/*
+Stake Summary
+- Collects the vote accounts stake is delegated to, the stake authorities that change and the lamports withdrawn from stake accounts
+- Changing an authority hands over control of the stake account, and a withdrawal moves its lamports out, so both are also warned about
+- Only instructions decoded natively as Stake program instructions are summarized
+*/
+pub fn stake_summary(
+    instructions: &[SolanaInstruction],
+) -> (SolanaStakeSummary, Vec<TransactionWarning>) {
+    let mut summary = SolanaStakeSummary::default();
+    let mut warnings = vec![];
+    for (instruction_index, instruction) in instructions.iter().enumerate() {
+        let Some(parsed_instruction) = instruction
+            .parsed_instruction
+            .as_ref()
+            .filter(|p| p.idl_source == IdlSource::Native(PROGRAM))
+        else {
+            continue;
+        };
+        let account = |name: &str| {
+            parsed_instruction
+                .named_accounts
+                .get(name)
+                .cloned()
+                .unwrap_or_default()
+        };
+        let arg = |name: &str| parsed_instruction.program_call_args.get(name);
+        match parsed_instruction.instruction_name.as_str() {
+            "DelegateStake" => summary.delegations.push(StakeDelegation {
+                instruction_index,
+                stake_account: account("stake_account"),
+                vote_account: account("vote_account"),
+                stake_authority: account("stake_authority"),
+            }),
+            "Authorize" | "AuthorizeChecked" | "AuthorizeWithSeed" | "AuthorizeCheckedWithSeed" => {
+                let new_authority = arg("new_authority")
+                    .and_then(|a| a.as_str())
+                    .map(ToString::to_string)
+                    .unwrap_or_else(|| account("new_authority"));
+                let change = StakeAuthorityChange {
+                    instruction_index,
+                    stake_account: account("stake_account"),
+                    authority_type: arg("authority_type")
+                        .and_then(|a| a.as_str())
+                        .unwrap_or_default()
+                        .to_string(),
+                    new_authority,
+                };
+                warnings.push(TransactionWarning::StakeAuthorize {
+                    instruction_index,
+                    stake_account: change.stake_account.clone(),
+                    authority_type: change.authority_type.clone(),
+                    new_authority: change.new_authority.clone(),
+                });
+                summary.authority_changes.push(change);
+            }
+            "Withdraw" => {
+                let withdrawal = StakeWithdrawal {
+                    instruction_index,
+                    stake_account: account("stake_account"),
+                    recipient: account("recipient_account"),
+                    lamports: arg("lamports").and_then(|a| a.as_u64()).unwrap_or_default(),
+                };
+                warnings.push(TransactionWarning::StakeWithdraw {
+                    instruction_index,
+                    stake_account: withdrawal.stake_account.clone(),
+                    recipient: withdrawal.recipient.clone(),
+                    lamports: withdrawal.lamports,
+                });
+                summary.withdrawals.push(withdrawal);
+            }
+            _ => {}
+        }
+    }
+    (summary, warnings)
+}
@@ -0,0 +1,164 @@
+use super::{data_parse_error, named_accounts, native_instruction};
+use crate::solana::structs::{
+    AccountAddress, InstructionParseError, NativeProgram, SolanaParsedInstructionData,
+};
+use serde_json::json;
+use solana_sdk::system_instruction::SystemInstruction;
+
+// System program instructions are bincode encoded, starting with a little endian u32 tag
+const LEN_SYSTEM_INSTRUCTION_TAG_BYTES: usize = 4;
+
+/// Decode the data of a System program instruction
+pub fn decode_system_instruction(
+    instruction_data: &[u8],
+) -> Result<SystemInstruction, InstructionParseError> {
+    bincode::deserialize(instruction_data).map_err(|e| {
+        data_parse_error(
+            NativeProgram::System,
+            format!("could not parse system instruction: {e}"),
+        )
+    })
+}
+
+/*
+Parse System Instruction
+- Names the accounts and arguments of every System program instruction
+- Account orders follow the documentation of each variant of the Solana SDK's SystemInstruction
+- Nonce instructions reference the RecentBlockhashes and Rent sysvars, which are named like any other account
+*/
+#[allow(clippy::too_many_lines)] // One match arm per SystemInstruction variant
+pub fn parse_system_instruction(
+    instruction: &SystemInstruction,
+    instruction_data: &[u8],
+    accounts: &[AccountAddress],
+) -> Result<SolanaParsedInstructionData, InstructionParseError> {
+    let (instruction_name, required, optional, args): (&str, &[&str], &[&str], _) =
+        match instruction {
+            SystemInstruction::CreateAccount {
+                lamports,
+                space,
+                owner,
+            } => (
+                "CreateAccount",
+                &["funding_account", "new_account"],
+                &[],
+                json!({ "lamports": lamports, "space": space, "owner": owner.to_string() }),
+            ),
+            SystemInstruction::Assign { owner } => (
+                "Assign",
+                &["assigned_account"],
+                &[],
+                json!({ "owner": owner.to_string() }),
+            ),
+            SystemInstruction::Transfer { lamports } => (
+                "Transfer",
+                &["funding_account", "recipient_account"],
+                &[],
+                json!({ "lamports": lamports }),
+            ),
+            // the base account is only included when it differs from the funding account
+            SystemInstruction::CreateAccountWithSeed {
+                base,
+                seed,
+                lamports,
+                space,
+                owner,
+            } => (
+                "CreateAccountWithSeed",
+                &["funding_account", "created_account"],
+                &["base_account"],
+                json!({
+                    "base": base.to_string(),
+                    "seed": seed,
+                    "lamports": lamports,
+                    "space": space,
+                    "owner": owner.to_string(),
+                }),
+            ),
+            SystemInstruction::AdvanceNonceAccount => (
+                "AdvanceNonceAccount",
+                &[
+                    "nonce_account",
+                    "recent_blockhashes_sysvar",
+                    "nonce_authority",
+                ],
+                &[],
+                json!({}),
+            ),
+            SystemInstruction::WithdrawNonceAccount(lamports) => (
+                "WithdrawNonceAccount",
+                &[
+                    "nonce_account",
+                    "recipient_account",
+                    "recent_blockhashes_sysvar",
+                    "rent_sysvar",
+                    "nonce_authority",
+                ],
+                &[],
+                json!({ "lamports": lamports }),
+            ),
+            SystemInstruction::InitializeNonceAccount(authority) => (
+                "InitializeNonceAccount",
+                &["nonce_account", "recent_blockhashes_sysvar", "rent_sysvar"],
+                &[],
+                json!({ "nonce_authority": authority.to_string() }),
+            ),
+            SystemInstruction::AuthorizeNonceAccount(new_authority) => (
+                "AuthorizeNonceAccount",
+                &["nonce_account", "nonce_authority"],
+                &[],
+                json!({ "new_nonce_authority": new_authority.to_string() }),
+            ),
+            SystemInstruction::Allocate { space } => {
+                ("Allocate", &["new_account"], &[], json!({ "space": space }))
+            }
+            SystemInstruction::AllocateWithSeed {
+                base,
+                seed,
+                space,
+                owner,
+            } => (
+                "AllocateWithSeed",
+                &["allocated_account", "base_account"],
+                &[],
+                json!({
+                    "base": base.to_string(),
+                    "seed": seed,
+                    "space": space,
+                    "owner": owner.to_string(),
+                }),
+            ),
+            SystemInstruction::AssignWithSeed { base, seed, owner } => (
+                "AssignWithSeed",
+                &["assigned_account", "base_account"],
+                &[],
+                json!({ "base": base.to_string(), "seed": seed, "owner": owner.to_string() }),
+            ),
+            SystemInstruction::TransferWithSeed {
+                lamports,
+                from_seed,
+                from_owner,
+            } => (
+                "TransferWithSeed",
+                &["funding_account", "base_account", "recipient_account"],
+                &[],
+                json!({
+                    "lamports": lamports,
+                    "from_seed": from_seed,
+                    "from_owner": from_owner.to_string(),
+                }),
+            ),
+            SystemInstruction::UpgradeNonceAccount => {
+                ("UpgradeNonceAccount", &["nonce_account"], &[], json!({}))
+            }
+        };
+
+    let named_accounts = named_accounts(instruction_name, accounts, required, optional)?;
+    Ok(native_instruction(
+        NativeProgram::System,
+        instruction_name,
+        &instruction_data[..LEN_SYSTEM_INSTRUCTION_TAG_BYTES],
+        named_accounts,
+        args,
+    ))
+}
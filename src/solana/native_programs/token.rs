@@ -0,0 +1,245 @@
+use super::token_2022::parse_token_2022_extension_instruction;
+use super::{
+    data_parse_error, named_accounts, named_accounts_with_signers, native_instruction,
+    optional_pubkey_value, InstructionDataReader, SiblingInstructions,
+};
+use crate::solana::structs::{
+    AccountAddress, InstructionParseError, NativeProgram, SolanaParsedInstructionData,
+};
+use serde_json::json;
+
+// Instructions with a tag above this one only exist in the Token 2022 program
+const LAST_TOKEN_INSTRUCTION_TAG: u8 = 24;
+
+// Reference for the instruction layouts: https://docs.rs/spl-token/latest/src/spl_token/instruction.rs.html
+fn token_instruction_name(tag: u8) -> Option<&'static str> {
+    Some(match tag {
+        0 => "InitializeMint",
+        1 => "InitializeAccount",
+        2 => "InitializeMultisig",
+        3 => "Transfer",
+        4 => "Approve",
+        5 => "Revoke",
+        6 => "SetAuthority",
+        7 => "MintTo",
+        8 => "Burn",
+        9 => "CloseAccount",
+        10 => "FreezeAccount",
+        11 => "ThawAccount",
+        12 => "TransferChecked",
+        13 => "ApproveChecked",
+        14 => "MintToChecked",
+        15 => "BurnChecked",
+        16 => "InitializeAccount2",
+        17 => "SyncNative",
+        18 => "InitializeAccount3",
+        19 => "InitializeMultisig2",
+        20 => "InitializeMint2",
+        21 => "GetAccountDataSize",
+        22 => "InitializeImmutableOwner",
+        23 => "AmountToUiAmount",
+        24 => "UiAmountToAmount",
+        _ => return None,
+    })
+}
+
+/// Returns the name of a SetAuthority authority type, including the types added by Token 2022
+pub fn authority_type_name(authority_type: u8) -> Option<&'static str> {
+    Some(match authority_type {
+        0 => "MintTokens",
+        1 => "FreezeAccount",
+        2 => "AccountOwner",
+        3 => "CloseAccount",
+        4 => "TransferFeeConfig",
+        5 => "WithheldWithdraw",
+        6 => "CloseMint",
+        7 => "InterestRate",
+        8 => "PermanentDelegate",
+        9 => "ConfidentialTransferMint",
+        10 => "TransferHookProgramId",
+        11 => "ConfidentialTransferFeeConfig",
+        12 => "MetadataPointer",
+        13 => "GroupPointer",
+        14 => "GroupMemberPointer",
+        15 => "ScaledUiAmount",
+        16 => "Pause",
+        _ => return None,
+    })
+}
+
+/*
+Parse Token Instruction
+- Names the accounts and arguments of every instruction shared by the Token program and the Token 2022 program
+- Instructions that accept a multisig authority name the trailing signer accounts signer_1, signer_2 and so on
+- Token 2022 extension instructions are decoded by parse_token_2022_extension_instruction
+*/
+#[allow(clippy::too_many_lines)] // One match arm per TokenInstruction variant
+pub fn parse_token_instruction(
+    program: NativeProgram,
+    instruction_data: &[u8],
+    accounts: &[AccountAddress],
+    siblings: SiblingInstructions,
+) -> Result<Option<SolanaParsedInstructionData>, InstructionParseError> {
+    let (&tag, data) = instruction_data.split_first().ok_or_else(|| {
+        data_parse_error(
+            program,
+            "error while parsing spl instruction data header".to_string(),
+        )
+    })?;
+    let Some(instruction_name) = token_instruction_name(tag) else {
+        if program == NativeProgram::Token2022 && tag > LAST_TOKEN_INSTRUCTION_TAG {
+            return parse_token_2022_extension_instruction(tag, data, accounts, siblings);
+        }
+        return Err(data_parse_error(
+            program,
+            format!("unknown token instruction tag {tag}"),
+        ));
+    };
+
+    let mut data = InstructionDataReader::new(program, instruction_name, data);
+    let fixed = |names: &[&str]| named_accounts(instruction_name, accounts, names, &[]);
+    let with_signers =
+        |names: &[&str]| named_accounts_with_signers(instruction_name, accounts, names);
+
+    let (named_accounts, args) = match tag {
+        0 | 20 => {
+            let decimals = data.u8("decimals")?;
+            let mint_authority = data.pubkey("mint_authority")?;
+            let freeze_authority = data.optional_pubkey("freeze_authority")?;
+            let names: &[&str] = if tag == 0 {
+                &["mint", "rent_sysvar"]
+            } else {
+                &["mint"]
+            };
+            (
+                fixed(names)?,
+                json!({
+                    "decimals": decimals,
+                    "mint_authority": mint_authority.to_string(),
+                    "freeze_authority": optional_pubkey_value(freeze_authority),
+                }),
+            )
+        }
+        1 => (
+            fixed(&["account", "mint", "owner", "rent_sysvar"])?,
+            json!({}),
+        ),
+        2 | 19 => {
+            let m = data.u8("m")?;
+            let names: &[&str] = if tag == 2 {
+                &["multisig", "rent_sysvar"]
+            } else {
+                &["multisig"]
+            };
+            (with_signers(names)?, json!({ "m": m }))
+        }
+        3 => (
+            with_signers(&["source", "destination", "authority"])?,
+            json!({ "amount": data.u64("amount")? }),
+        ),
+        4 => (
+            with_signers(&["source", "delegate", "owner"])?,
+            json!({ "amount": data.u64("amount")? }),
+        ),
+        5 => (with_signers(&["source", "owner"])?, json!({})),
+        6 => {
+            let authority_type = data.u8("authority_type")?;
+            let authority_type = authority_type_name(authority_type).ok_or_else(|| {
+                data_parse_error(
+                    program,
+                    format!("error while parsing SetAuthority instruction -- unknown authority type {authority_type}"),
+                )
+            })?;
+            let new_authority = data.optional_pubkey("new_authority")?;
+            (
+                with_signers(&["account", "current_authority"])?,
+                json!({
+                    "authority_type": authority_type,
+                    "new_authority": optional_pubkey_value(new_authority),
+                }),
+            )
+        }
+        7 => (
+            with_signers(&["mint", "destination", "mint_authority"])?,
+            json!({ "amount": data.u64("amount")? }),
+        ),
+        8 => (
+            with_signers(&["account", "mint", "owner"])?,
+            json!({ "amount": data.u64("amount")? }),
+        ),
+        9 => (
+            with_signers(&["account", "destination", "owner"])?,
+            json!({}),
+        ),
+        10 | 11 => (
+            with_signers(&["account", "mint", "freeze_authority"])?,
+            json!({}),
+        ),
+        12 => {
+            let amount = data.u64("amount")?;
+            let decimals = data.u8("decimals")?;
+            (
+                with_signers(&["source", "mint", "destination", "authority"])?,
+                json!({ "amount": amount, "decimals": decimals }),
+            )
+        }
+        13 => {
+            let amount = data.u64("amount")?;
+            let decimals = data.u8("decimals")?;
+            (
+                with_signers(&["source", "mint", "delegate", "owner"])?,
+                json!({ "amount": amount, "decimals": decimals }),
+            )
+        }
+        14 => {
+            let amount = data.u64("amount")?;
+            let decimals = data.u8("decimals")?;
+            (
+                with_signers(&["mint", "destination", "mint_authority"])?,
+                json!({ "amount": amount, "decimals": decimals }),
+            )
+        }
+        15 => {
+            let amount = data.u64("amount")?;
+            let decimals = data.u8("decimals")?;
+            (
+                with_signers(&["account", "mint", "owner"])?,
+                json!({ "amount": amount, "decimals": decimals }),
+            )
+        }
+        16 => (
+            fixed(&["account", "mint", "rent_sysvar"])?,
+            json!({ "owner": data.pubkey("owner")?.to_string() }),
+        ),
+        17 => (fixed(&["account"])?, json!({})),
+        18 => (
+            fixed(&["account", "mint"])?,
+            json!({ "owner": data.pubkey("owner")?.to_string() }),
+        ),
+        // Token 2022 appends the extension types the account should be sized for, as u16s
+        21 => (
+            fixed(&["mint"])?,
+            json!({ "extension_types": data.u16s_to_end("extension_types")? }),
+        ),
+        22 => (fixed(&["account"])?, json!({})),
+        23 => (fixed(&["mint"])?, json!({ "amount": data.u64("amount")? })),
+        24 => {
+            let ui_amount = std::str::from_utf8(data.rest()).map_err(|e| {
+                data_parse_error(
+                    program,
+                    format!("error while parsing UiAmountToAmount instruction -- ui_amount: {e}"),
+                )
+            })?;
+            (fixed(&["mint"])?, json!({ "ui_amount": ui_amount }))
+        }
+        _ => unreachable!("every named token instruction tag is decoded"),
+    };
+
+    Ok(Some(native_instruction(
+        program,
+        instruction_name,
+        &[tag],
+        named_accounts,
+        args,
+    )))
+}
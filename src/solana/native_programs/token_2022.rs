@@ -0,0 +1,298 @@
+use super::confidential_transfer::parse_confidential_transfer_instruction;
+use super::{
+    data_parse_error, named_accounts, named_accounts_with_signers, native_instruction,
+    optional_pubkey_value, InstructionDataReader, SiblingInstructions,
+};
+use crate::solana::structs::{
+    AccountAddress, InstructionParseError, NativeProgram, SolanaParsedInstructionData,
+};
+use serde_json::json;
+use std::collections::HashMap;
+
+const PROGRAM: NativeProgram = NativeProgram::Token2022;
+
+// Extensions whose instructions carry a second tag, which selects the instruction within the extension
+fn extension_name(tag: u8) -> Option<&'static str> {
+    Some(match tag {
+        26 => "TransferFee",
+        28 => "DefaultAccountState",
+        30 => "MemoTransfer",
+        33 => "InterestBearingMint",
+        34 => "CpiGuard",
+        36 => "TransferHook",
+        39 => "MetadataPointer",
+        40 => "GroupPointer",
+        41 => "GroupMemberPointer",
+        _ => return None,
+    })
+}
+
+// Reference for the instruction layouts: https://docs.rs/spl-token-2022/latest/src/spl_token_2022/instruction.rs.html
+fn extension_instruction_name(tag: u8, extension_tag: Option<u8>) -> Option<&'static str> {
+    Some(match (tag, extension_tag) {
+        (25, None) => "InitializeMintCloseAuthority",
+        (26, Some(0)) => "InitializeTransferFeeConfig",
+        (26, Some(1)) => "TransferCheckedWithFee",
+        (26, Some(2)) => "WithdrawWithheldTokensFromMint",
+        (26, Some(3)) => "WithdrawWithheldTokensFromAccounts",
+        (26, Some(4)) => "HarvestWithheldTokensToMint",
+        (26, Some(5)) => "SetTransferFee",
+        (28, Some(0)) => "InitializeDefaultAccountState",
+        (28, Some(1)) => "UpdateDefaultAccountState",
+        (29, None) => "Reallocate",
+        (30, Some(0)) => "EnableRequiredMemoTransfers",
+        (30, Some(1)) => "DisableRequiredMemoTransfers",
+        (31, None) => "CreateNativeMint",
+        (32, None) => "InitializeNonTransferableMint",
+        (33, Some(0)) => "InitializeInterestBearingMint",
+        (33, Some(1)) => "UpdateInterestRate",
+        (34, Some(0)) => "EnableCpiGuard",
+        (34, Some(1)) => "DisableCpiGuard",
+        (35, None) => "InitializePermanentDelegate",
+        (36, Some(0)) => "InitializeTransferHook",
+        (36, Some(1)) => "UpdateTransferHook",
+        (38, None) => "WithdrawExcessLamports",
+        (39, Some(0)) => "InitializeMetadataPointer",
+        (39, Some(1)) => "UpdateMetadataPointer",
+        (40, Some(0)) => "InitializeGroupPointer",
+        (40, Some(1)) => "UpdateGroupPointer",
+        (41, Some(0)) => "InitializeGroupMemberPointer",
+        (41, Some(1)) => "UpdateGroupMemberPointer",
+        _ => return None,
+    })
+}
+
+fn account_state_name(state: u8) -> Option<&'static str> {
+    Some(match state {
+        0 => "Uninitialized",
+        1 => "Initialized",
+        2 => "Frozen",
+        _ => return None,
+    })
+}
+
+// Names a run of accounts prefix_1, prefix_2 and so on
+fn numbered_accounts(prefix: &str, accounts: &[AccountAddress]) -> HashMap<String, String> {
+    accounts
+        .iter()
+        .enumerate()
+        .map(|(i, account)| (format!("{prefix}_{}", i + 1), account.to_string()))
+        .collect()
+}
+
+/*
+Parse Token 2022 Extension Instruction
+- Decodes the instructions the Token 2022 program adds on top of the base token instruction set
+- Instructions of an extension are selected by a second tag, which is part of the discriminator
+- Confidential transfer instructions are decoded by parse_confidential_transfer_instruction, which needs the sibling instructions to locate proofs
+- Returns None for the confidential transfer fee, confidential mint burn, scaled ui amount and pausable extensions
+*/
+#[allow(clippy::too_many_lines)] // One match arm per extension and extension instruction
+pub fn parse_token_2022_extension_instruction(
+    tag: u8,
+    data: &[u8],
+    accounts: &[AccountAddress],
+    siblings: SiblingInstructions,
+) -> Result<Option<SolanaParsedInstructionData>, InstructionParseError> {
+    if tag == 27 {
+        return parse_confidential_transfer_instruction(data, accounts, siblings).map(Some);
+    }
+    if matches!(tag, 37 | 42..=44) {
+        return Ok(None);
+    }
+    let (extension_tag, data) = match extension_name(tag) {
+        Some(extension) => {
+            let (&extension_tag, data) = data.split_first().ok_or_else(|| {
+                data_parse_error(
+                    PROGRAM,
+                    format!(
+                        "error while parsing {extension} extension instruction -- instruction tag"
+                    ),
+                )
+            })?;
+            (Some(extension_tag), data)
+        }
+        None => (None, data),
+    };
+    let Some(instruction_name) = extension_instruction_name(tag, extension_tag) else {
+        let error = match (extension_name(tag), extension_tag) {
+            (Some(extension), Some(extension_tag)) => {
+                format!("unknown {extension} extension instruction tag {extension_tag}")
+            }
+            _ => format!("unknown token 2022 instruction tag {tag}"),
+        };
+        return Err(data_parse_error(PROGRAM, error));
+    };
+
+    let mut data = InstructionDataReader::new(PROGRAM, instruction_name, data);
+    let fixed = |names: &[&str]| named_accounts(instruction_name, accounts, names, &[]);
+    let with_signers =
+        |names: &[&str]| named_accounts_with_signers(instruction_name, accounts, names);
+
+    let (named_accounts, args) = match (tag, extension_tag) {
+        (25, _) => (
+            fixed(&["mint"])?,
+            json!({ "close_authority": optional_pubkey_value(data.optional_pubkey("close_authority")?) }),
+        ),
+        (26, Some(0)) => {
+            let transfer_fee_config_authority =
+                data.optional_pubkey("transfer_fee_config_authority")?;
+            let withdraw_withheld_authority =
+                data.optional_pubkey("withdraw_withheld_authority")?;
+            let transfer_fee_basis_points = data.u16("transfer_fee_basis_points")?;
+            let maximum_fee = data.u64("maximum_fee")?;
+            (
+                fixed(&["mint"])?,
+                json!({
+                    "transfer_fee_config_authority": optional_pubkey_value(transfer_fee_config_authority),
+                    "withdraw_withheld_authority": optional_pubkey_value(withdraw_withheld_authority),
+                    "transfer_fee_basis_points": transfer_fee_basis_points,
+                    "maximum_fee": maximum_fee,
+                }),
+            )
+        }
+        (26, Some(1)) => {
+            let amount = data.u64("amount")?;
+            let decimals = data.u8("decimals")?;
+            let fee = data.u64("fee")?;
+            (
+                with_signers(&["source", "mint", "destination", "authority"])?,
+                json!({ "amount": amount, "decimals": decimals, "fee": fee }),
+            )
+        }
+        (26, Some(2)) => (
+            with_signers(&["mint", "destination", "withdraw_withheld_authority"])?,
+            json!({}),
+        ),
+        // The token accounts to withdraw from follow the multisig signers, so they are counted from the end
+        (26, Some(3)) => {
+            let num_token_accounts = data.u8("num_token_accounts")?;
+            let required = ["mint", "destination", "withdraw_withheld_authority"];
+            let expected = required.len() + num_token_accounts as usize;
+            if accounts.len() < expected {
+                return Err(InstructionParseError::NotEnoughAccounts {
+                    instruction_name: instruction_name.to_string(),
+                    expected,
+                    found: accounts.len(),
+                });
+            }
+            let (authority_accounts, sources) =
+                accounts.split_at(accounts.len() - num_token_accounts as usize);
+            let mut named =
+                named_accounts_with_signers(instruction_name, authority_accounts, &required)?;
+            named.extend(numbered_accounts("source", sources));
+            (named, json!({ "num_token_accounts": num_token_accounts }))
+        }
+        (26, Some(4)) => {
+            let mut named = fixed(&["mint"])?;
+            named.extend(numbered_accounts("source", &accounts[1..]));
+            (named, json!({}))
+        }
+        (26, Some(5)) => {
+            let transfer_fee_basis_points = data.u16("transfer_fee_basis_points")?;
+            let maximum_fee = data.u64("maximum_fee")?;
+            (
+                with_signers(&["mint", "transfer_fee_config_authority"])?,
+                json!({
+                    "transfer_fee_basis_points": transfer_fee_basis_points,
+                    "maximum_fee": maximum_fee,
+                }),
+            )
+        }
+        (28, Some(extension_tag)) => {
+            let state = data.u8("state")?;
+            let state = account_state_name(state).ok_or_else(|| {
+                data_parse_error(
+                    PROGRAM,
+                    format!("error while parsing {instruction_name} instruction -- unknown account state {state}"),
+                )
+            })?;
+            let named = if extension_tag == 0 {
+                fixed(&["mint"])?
+            } else {
+                with_signers(&["mint", "freeze_authority"])?
+            };
+            (named, json!({ "state": state }))
+        }
+        (29, _) => (
+            with_signers(&["account", "payer", "system_program", "owner"])?,
+            json!({ "extension_types": data.u16s_to_end("extension_types")? }),
+        ),
+        (30 | 34, _) => (with_signers(&["account", "owner"])?, json!({})),
+        (31, _) => (
+            fixed(&["payer", "native_mint", "system_program"])?,
+            json!({}),
+        ),
+        (32, _) => (fixed(&["mint"])?, json!({})),
+        (33, Some(0)) => {
+            let rate_authority = data.optional_nonzero_pubkey("rate_authority")?;
+            let rate = data.i16("rate")?;
+            (
+                fixed(&["mint"])?,
+                json!({ "rate_authority": optional_pubkey_value(rate_authority), "rate": rate }),
+            )
+        }
+        (33, Some(1)) => (
+            with_signers(&["mint", "rate_authority"])?,
+            json!({ "rate": data.i16("rate")? }),
+        ),
+        (35, _) => (
+            fixed(&["mint"])?,
+            json!({ "delegate": data.pubkey("delegate")?.to_string() }),
+        ),
+        (36, Some(0)) => {
+            let authority = data.optional_nonzero_pubkey("authority")?;
+            let program_id = data.optional_nonzero_pubkey("program_id")?;
+            (
+                fixed(&["mint"])?,
+                json!({
+                    "authority": optional_pubkey_value(authority),
+                    "program_id": optional_pubkey_value(program_id),
+                }),
+            )
+        }
+        (36, Some(1)) => (
+            with_signers(&["mint", "authority"])?,
+            json!({ "program_id": optional_pubkey_value(data.optional_nonzero_pubkey("program_id")?) }),
+        ),
+        (38, _) => (
+            with_signers(&["source", "destination", "authority"])?,
+            json!({}),
+        ),
+        // The pointer extensions share a layout, and differ only in what the pointer is to
+        (39..=41, Some(extension_tag)) => {
+            let pointer = match tag {
+                39 => "metadata_address",
+                40 => "group_address",
+                _ => "member_address",
+            };
+            if extension_tag == 0 {
+                let authority = data.optional_nonzero_pubkey("authority")?;
+                let address = data.optional_nonzero_pubkey(pointer)?;
+                (
+                    fixed(&["mint"])?,
+                    json!({
+                        "authority": optional_pubkey_value(authority),
+                        pointer: optional_pubkey_value(address),
+                    }),
+                )
+            } else {
+                let address = data.optional_nonzero_pubkey(pointer)?;
+                (
+                    with_signers(&["mint", "authority"])?,
+                    json!({ pointer: optional_pubkey_value(address) }),
+                )
+            }
+        }
+        _ => unreachable!("every named token 2022 extension instruction is decoded"),
+    };
+
+    let discriminator: Vec<u8> = std::iter::once(tag).chain(extension_tag).collect();
+    Ok(Some(native_instruction(
+        PROGRAM,
+        instruction_name,
+        &discriminator,
+        named_accounts,
+        args,
+    )))
+}
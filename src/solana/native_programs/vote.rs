@@ -0,0 +1,226 @@
+use super::{data_parse_error, named_accounts, native_instruction};
+use crate::solana::structs::{
+    AccountAddress, InstructionParseError, NativeProgram, SolanaParsedInstructionData,
+};
+use serde_json::{json, Value};
+use solana_sdk::{
+    hash::Hash,
+    vote::{
+        instruction::VoteInstruction,
+        state::{
+            TowerSync, Vote, VoteAuthorize, VoteAuthorizeCheckedWithSeedArgs,
+            VoteAuthorizeWithSeedArgs, VoteStateUpdate,
+        },
+    },
+};
+
+const PROGRAM: NativeProgram = NativeProgram::Vote;
+
+// Vote program instructions are bincode encoded, starting with a little endian u32 tag
+const LEN_VOTE_INSTRUCTION_TAG_BYTES: usize = 4;
+
+/// Decode the data of a Vote program instruction
+pub fn decode_vote_instruction(
+    instruction_data: &[u8],
+) -> Result<VoteInstruction, InstructionParseError> {
+    bincode::deserialize(instruction_data)
+        .map_err(|e| data_parse_error(PROGRAM, format!("could not parse vote instruction: {e}")))
+}
+
+fn authority_type(vote_authorize: &VoteAuthorize) -> &'static str {
+    match vote_authorize {
+        VoteAuthorize::Voter => "Voter",
+        VoteAuthorize::Withdrawer => "Withdrawer",
+    }
+}
+
+// Consensus votes carry the validator's whole tower, so only its shape is shown instead of every slot
+fn vote_summary(vote: &Vote, switch_proof_hash: Option<&Hash>) -> Value {
+    let mut summary = json!({
+        "votes": vote.slots.len(),
+        "last_voted_slot": vote.last_voted_slot(),
+        "hash": vote.hash.to_string(),
+        "timestamp": vote.timestamp,
+    });
+    if let Some(hash) = switch_proof_hash {
+        summary["switch_proof_hash"] = json!(hash.to_string());
+    }
+    summary
+}
+
+fn vote_state_update_summary(update: &VoteStateUpdate, switch_proof_hash: Option<&Hash>) -> Value {
+    let mut summary = json!({
+        "lockouts": update.lockouts.len(),
+        "last_voted_slot": update.last_voted_slot(),
+        "root": update.root,
+        "hash": update.hash.to_string(),
+        "timestamp": update.timestamp,
+    });
+    if let Some(hash) = switch_proof_hash {
+        summary["switch_proof_hash"] = json!(hash.to_string());
+    }
+    summary
+}
+
+fn tower_sync_summary(tower_sync: &TowerSync, switch_proof_hash: Option<&Hash>) -> Value {
+    let mut summary = json!({
+        "lockouts": tower_sync.lockouts.len(),
+        "last_voted_slot": tower_sync.last_voted_slot(),
+        "root": tower_sync.root,
+        "hash": tower_sync.hash.to_string(),
+        "timestamp": tower_sync.timestamp,
+        "block_id": tower_sync.block_id.to_string(),
+    });
+    if let Some(hash) = switch_proof_hash {
+        summary["switch_proof_hash"] = json!(hash.to_string());
+    }
+    summary
+}
+
+/*
+Parse Vote Instruction
+- Names the accounts and arguments of every Vote program instruction
+- Account orders follow the documentation of each variant of the Solana SDK's VoteInstruction
+- Consensus votes (Vote, UpdateVoteState, TowerSync and their switch and compact variants) are summarized by their last voted slot, root, bank hash and timestamp
+*/
+#[allow(clippy::too_many_lines)] // One match arm per VoteInstruction variant
+pub fn parse_vote_instruction(
+    instruction: &VoteInstruction,
+    instruction_data: &[u8],
+    accounts: &[AccountAddress],
+) -> Result<SolanaParsedInstructionData, InstructionParseError> {
+    const VOTE_ACCOUNTS: &[&str] = &[
+        "vote_account",
+        "slot_hashes_sysvar",
+        "clock_sysvar",
+        "vote_authority",
+    ];
+    const VOTE_STATE_ACCOUNTS: &[&str] = &["vote_account", "vote_authority"];
+    let (instruction_name, required, args): (&str, &[&str], _) = match instruction {
+        VoteInstruction::InitializeAccount(vote_init) => (
+            "InitializeAccount",
+            &[
+                "vote_account",
+                "rent_sysvar",
+                "clock_sysvar",
+                "validator_identity",
+            ],
+            json!({
+                "node_pubkey": vote_init.node_pubkey.to_string(),
+                "authorized_voter": vote_init.authorized_voter.to_string(),
+                "authorized_withdrawer": vote_init.authorized_withdrawer.to_string(),
+                "commission": vote_init.commission,
+            }),
+        ),
+        VoteInstruction::Authorize(new_authority, vote_authorize) => (
+            "Authorize",
+            &["vote_account", "clock_sysvar", "authority"],
+            json!({
+                "new_authority": new_authority.to_string(),
+                "authority_type": authority_type(vote_authorize),
+            }),
+        ),
+        VoteInstruction::Vote(vote) => ("Vote", VOTE_ACCOUNTS, vote_summary(vote, None)),
+        VoteInstruction::Withdraw(lamports) => (
+            "Withdraw",
+            &["vote_account", "recipient_account", "withdraw_authority"],
+            json!({ "lamports": lamports }),
+        ),
+        VoteInstruction::UpdateValidatorIdentity => (
+            "UpdateValidatorIdentity",
+            &[
+                "vote_account",
+                "new_validator_identity",
+                "withdraw_authority",
+            ],
+            json!({}),
+        ),
+        VoteInstruction::UpdateCommission(commission) => (
+            "UpdateCommission",
+            &["vote_account", "withdraw_authority"],
+            json!({ "commission": commission }),
+        ),
+        VoteInstruction::VoteSwitch(vote, hash) => {
+            ("VoteSwitch", VOTE_ACCOUNTS, vote_summary(vote, Some(hash)))
+        }
+        // the checked variants take the new authority as a signing account instead of an argument
+        VoteInstruction::AuthorizeChecked(vote_authorize) => (
+            "AuthorizeChecked",
+            &["vote_account", "clock_sysvar", "authority", "new_authority"],
+            json!({ "authority_type": authority_type(vote_authorize) }),
+        ),
+        VoteInstruction::UpdateVoteState(update) => (
+            "UpdateVoteState",
+            VOTE_STATE_ACCOUNTS,
+            vote_state_update_summary(update, None),
+        ),
+        VoteInstruction::UpdateVoteStateSwitch(update, hash) => (
+            "UpdateVoteStateSwitch",
+            VOTE_STATE_ACCOUNTS,
+            vote_state_update_summary(update, Some(hash)),
+        ),
+        // the current authority is the address derived from the base account, the seed and the owner
+        VoteInstruction::AuthorizeWithSeed(VoteAuthorizeWithSeedArgs {
+            authorization_type,
+            current_authority_derived_key_owner,
+            current_authority_derived_key_seed,
+            new_authority,
+        }) => (
+            "AuthorizeWithSeed",
+            &["vote_account", "clock_sysvar", "authority_base"],
+            json!({
+                "new_authority": new_authority.to_string(),
+                "authority_type": authority_type(authorization_type),
+                "authority_seed": current_authority_derived_key_seed,
+                "authority_owner": current_authority_derived_key_owner.to_string(),
+            }),
+        ),
+        VoteInstruction::AuthorizeCheckedWithSeed(VoteAuthorizeCheckedWithSeedArgs {
+            authorization_type,
+            current_authority_derived_key_owner,
+            current_authority_derived_key_seed,
+        }) => (
+            "AuthorizeCheckedWithSeed",
+            &[
+                "vote_account",
+                "clock_sysvar",
+                "authority_base",
+                "new_authority",
+            ],
+            json!({
+                "authority_type": authority_type(authorization_type),
+                "authority_seed": current_authority_derived_key_seed,
+                "authority_owner": current_authority_derived_key_owner.to_string(),
+            }),
+        ),
+        VoteInstruction::CompactUpdateVoteState(update) => (
+            "CompactUpdateVoteState",
+            VOTE_STATE_ACCOUNTS,
+            vote_state_update_summary(update, None),
+        ),
+        VoteInstruction::CompactUpdateVoteStateSwitch(update, hash) => (
+            "CompactUpdateVoteStateSwitch",
+            VOTE_STATE_ACCOUNTS,
+            vote_state_update_summary(update, Some(hash)),
+        ),
+        VoteInstruction::TowerSync(tower_sync) => (
+            "TowerSync",
+            VOTE_STATE_ACCOUNTS,
+            tower_sync_summary(tower_sync, None),
+        ),
+        VoteInstruction::TowerSyncSwitch(tower_sync, hash) => (
+            "TowerSyncSwitch",
+            VOTE_STATE_ACCOUNTS,
+            tower_sync_summary(tower_sync, Some(hash)),
+        ),
+    };
+
+    let named_accounts = named_accounts(instruction_name, accounts, required, &[])?;
+    Ok(native_instruction(
+        PROGRAM,
+        instruction_name,
+        &instruction_data[..LEN_VOTE_INSTRUCTION_TAG_BYTES],
+        named_accounts,
+        args,
+    ))
+}
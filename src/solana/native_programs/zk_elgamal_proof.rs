@@ -0,0 +1,105 @@
+use super::{data_parse_error, named_accounts, native_instruction, InstructionDataReader};
+use crate::solana::structs::{
+    AccountAddress, InstructionParseError, NativeProgram, SolanaParsedInstructionData,
+};
+use serde_json::json;
+
+const PROGRAM: NativeProgram = NativeProgram::ZkElGamalProof;
+
+// A verify instruction that reads its proof from a record account carries only its tag and the offset of the proof in the record
+const LEN_RECORD_ACCOUNT_PROOF_DATA: usize = 5;
+
+// Reference for the instruction layouts: https://docs.rs/solana-zk-sdk/latest/solana_zk_sdk/zk_elgamal_proof_program/instruction/enum.ProofInstruction.html
+pub(super) fn proof_instruction_name(tag: u8) -> Option<&'static str> {
+    Some(match tag {
+        0 => "CloseContextState",
+        1 => "VerifyZeroCiphertext",
+        2 => "VerifyCiphertextCiphertextEquality",
+        3 => "VerifyCiphertextCommitmentEquality",
+        4 => "VerifyPubkeyValidity",
+        5 => "VerifyPercentageWithCap",
+        6 => "VerifyBatchedRangeProofU64",
+        7 => "VerifyBatchedRangeProofU128",
+        8 => "VerifyBatchedRangeProofU256",
+        9 => "VerifyGroupedCiphertext2HandlesValidity",
+        10 => "VerifyBatchedGroupedCiphertext2HandlesValidity",
+        11 => "VerifyGroupedCiphertext3HandlesValidity",
+        12 => "VerifyBatchedGroupedCiphertext3HandlesValidity",
+        _ => return None,
+    })
+}
+
+// Whether the data of a verify instruction points at a proof stored in a record account, which is then its first account
+pub(super) fn reads_proof_from_record_account(data: &[u8]) -> bool {
+    data.len() == LEN_RECORD_ACCOUNT_PROOF_DATA
+}
+
+/*
+Parse ZK ElGamal Proof Instruction
+- Names the accounts of the proof program instructions and reports where each verify instruction reads its proof from
+- A verify instruction given a context state account and its authority stores the verified proof context there, for a later instruction to use
+- The proofs themselves are not verified
+*/
+pub fn parse_zk_elgamal_proof_instruction(
+    instruction_data: &[u8],
+    accounts: &[AccountAddress],
+) -> Result<SolanaParsedInstructionData, InstructionParseError> {
+    let (&tag, data) = instruction_data.split_first().ok_or_else(|| {
+        data_parse_error(
+            PROGRAM,
+            "error while parsing proof instruction data header".to_string(),
+        )
+    })?;
+    let instruction_name = proof_instruction_name(tag)
+        .ok_or_else(|| data_parse_error(PROGRAM, format!("unknown proof instruction tag {tag}")))?;
+
+    let (named_accounts, args) = if tag == 0 {
+        (
+            named_accounts(
+                instruction_name,
+                accounts,
+                &["context_state", "destination", "context_state_owner"],
+                &[],
+            )?,
+            json!({}),
+        )
+    } else if reads_proof_from_record_account(instruction_data) {
+        let mut data = InstructionDataReader::new(PROGRAM, instruction_name, data);
+        let offset = data.u32("record_account_offset")?;
+        (
+            named_accounts(
+                instruction_name,
+                accounts,
+                &["record_account"],
+                &["context_state", "context_state_authority"],
+            )?,
+            json!({
+                "proof_location": "RecordAccount",
+                "record_account_offset": offset,
+                "creates_context_state": accounts.len() > 1,
+            }),
+        )
+    } else {
+        (
+            named_accounts(
+                instruction_name,
+                accounts,
+                &[],
+                &["context_state", "context_state_authority"],
+            )?,
+            json!({
+                "proof_location": "InstructionData",
+                "proof_data_length": data.len(),
+                "creates_context_state": !accounts.is_empty(),
+            }),
+        )
+    };
+
+    Ok(native_instruction(
+        PROGRAM,
+        instruction_name,
+        &[tag],
+        named_accounts,
+        args,
+    ))
+}
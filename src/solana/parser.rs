@@ -1,10 +1,20 @@
 use super::structs::{
-    AccountAddress, CustomIdlConfig, IdlParseError, IdlRecord, SolTransfer, SolanaAccount,
-    SolanaAddressTableLookup, SolanaInstruction, SolanaMetadata, SolanaParseResponse,
-    SolanaParsedInstructionData, SolanaParsedTransaction, SolanaParsedTransactionPayload,
-    SolanaSingleAddressTableLookup, SplTransfer,
+    AccountAddress, AddressLookupTableContents, AddressLookupTableStatus, CustomIdlConfig,
+    DecodedAddressLookupTable, IdlParseError, IdlRecord, InstructionParseError,
+    InvalidInstructionsError, NativeProgram, PayloadKind, SignatureInsertionError, SignatureStatus,
+    SolTransfer, SolanaAccount, SolanaAccountMeta, SolanaAddressTableLookup, SolanaInstruction,
+    SolanaLayoutSection, SolanaMetadata, SolanaParseResponse, SolanaParsedInstructionData,
+    SolanaParsedTransaction, SolanaParsedTransactionPayload, SolanaSignatureInsertion,
+    SolanaSignatureVerification, SolanaSingleAddressTableLookup, SplTransfer, TransactionEncoding,
+    TransactionParseError, TransactionSerializeError, TransactionWarning,
 };
 use crate::solana::idl_parser;
+use crate::solana::lookup_tables::AddressLookupTableProvider;
+use crate::solana::native_programs::{
+    address_lookup_table, associated_token_account, compute_budget, memo, spl_interfaces, stake,
+    system, token, vote, zk_elgamal_proof, SiblingInstructions,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine};
 use hex;
 use solana_sdk::{
     hash::Hash,
@@ -14,15 +24,16 @@ use solana_sdk::{
         Message as LegacyMessage, MessageHeader, VersionedMessage,
     },
     pubkey::Pubkey,
+    sanitize::SanitizeError,
     system_instruction::SystemInstruction,
 };
-use std::{collections::HashMap, error::Error};
+use std::{collections::HashMap, str::FromStr};
 
 // Length of a solana signature in bytes (64 bytes long)
 pub const LEN_SOL_SIGNATURE_BYTES: usize = 64;
 // Length of a solana account key in bytes (32 bytes long)
 pub const LEN_SOL_ACCOUNT_KEY_BYTES: usize = 32;
-// This is the length of the header of a compact array -- a pattern used multiple times in solana transactions (length of header is 1 byte)
+// This is the minimum length of the header of a compact array -- a pattern used multiple times in solana transactions (the header is a compact-u16 that is 1 to 3 bytes long)
 pub const LEN_ARRAY_HEADER_BYTES: usize = 1;
 // Length of a solana message header in bytes (3 bytes long)
 pub const LEN_MESSAGE_HEADER_BYTES: usize = 3;
@@ -32,6 +43,23 @@ pub const SOL_SYSTEM_PROGRAM_KEY: &str = "11111111111111111111111111111111";
 pub const TOKEN_PROGRAM_KEY: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
 // This is a string representation of the account address for the Token 2022 Program which is a strict superset of the old Token Program, used to add extra functionality -- Used for transferring SPL tokens
 pub const TOKEN_2022_PROGRAM_KEY: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+// This is a string representation of the account address of the ZK ElGamal Proof Program -- Verifies the zero-knowledge proofs that Token 2022 confidential transfers rely on
+pub const ZK_ELGAMAL_PROOF_PROGRAM_KEY: &str = "ZkE1Gama1Proof11111111111111111111111111111";
+// This is a string representation of the account address of the Associated Token Account Program -- Creates the canonical token account of a wallet for a mint
+pub const ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_KEY: &str =
+    "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+// This is a string representation of the account address of the Compute Budget Program -- Sets the compute unit limit and the priority fee of a transaction
+pub const COMPUTE_BUDGET_PROGRAM_KEY: &str = "ComputeBudget111111111111111111111111111111";
+// This is a string representation of the account address of the Stake Program -- Delegates SOL to validators and manages the authorities of stake accounts
+pub const STAKE_PROGRAM_KEY: &str = "Stake11111111111111111111111111111111111111";
+// This is a string representation of the account address of the Vote Program -- Records validator votes and manages the authorities and commission of vote accounts
+pub const VOTE_PROGRAM_KEY: &str = "Vote111111111111111111111111111111111111111";
+// This is a string representation of the account address of the Address Lookup Table Program -- Creates and extends the lookup tables that versioned transactions load addresses from
+pub const ADDRESS_LOOKUP_TABLE_PROGRAM_KEY: &str = "AddressLookupTab1e1111111111111111111111111";
+// This is a string representation of the account address of the Memo Program -- Records a UTF-8 memo, such as the deposit reference an exchange requires, signed by the accounts passed to it
+pub const MEMO_PROGRAM_KEY: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+// This is a string representation of the account address of the original version of the Memo Program, which does not check its accounts
+pub const MEMO_V1_PROGRAM_KEY: &str = "Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo";
 // Versioned transactions have a prefix of 0x80
 const V0_TRANSACTION_INDICATOR: u8 = 0x80;
 
@@ -44,11 +72,12 @@ const V0_TRANSACTION_INDICATOR: u8 = 0x80;
 ///   - idl_json: JSON string of the IDL
 ///   - override_builtin: if true, use custom IDL even if a built-in exists for this program_id
 ///   - Pass `None` to use only built-in IDLs (default behavior)
+#[allow(dead_code)] // Public API - exported from lib.rs
 pub fn parse_transaction(
     unsigned_tx: String,
     full_transaction: bool,
     custom_idls: Option<HashMap<String, (String, bool)>>,
-) -> Result<SolanaParseResponse, Box<dyn Error>> {
+) -> Result<SolanaParseResponse, TransactionParseError> {
     // Convert old API to new API
     let custom_configs = custom_idls.map(CustomIdlConfig::from_legacy_map);
     parse_transaction_with_idls(unsigned_tx, full_transaction, custom_configs)
@@ -82,30 +111,137 @@ pub fn parse_transaction(
 ///
 /// let result = parse_transaction_with_idls(tx_hex, true, Some(custom_idls));
 /// ```
+#[allow(dead_code)] // Public API - exported from lib.rs
 pub fn parse_transaction_with_idls(
     unsigned_tx: String,
     full_transaction: bool,
     custom_idls: Option<HashMap<String, CustomIdlConfig>>,
-) -> Result<SolanaParseResponse, Box<dyn Error>> {
+) -> Result<SolanaParseResponse, TransactionParseError> {
+    parse_transaction_with_encoding(
+        unsigned_tx,
+        TransactionEncoding::Hex,
+        full_transaction,
+        custom_idls,
+    )
+}
+
+/// Parse a Solana transaction provided in any of the supported input encodings
+///
+/// # Arguments
+/// * `unsigned_tx` - The encoded transaction to parse
+/// * `encoding` - How `unsigned_tx` is encoded (hex, base58, base64, or `Auto` to detect it)
+/// * `full_transaction` - Whether the input is a full transaction or just a message
+/// * `custom_idls` - Optional map of program_id -> CustomIdlConfig (see `parse_transaction_with_idls`)
+pub fn parse_transaction_with_encoding(
+    unsigned_tx: String,
+    encoding: TransactionEncoding,
+    full_transaction: bool,
+    custom_idls: Option<HashMap<String, CustomIdlConfig>>,
+) -> Result<SolanaParseResponse, TransactionParseError> {
+    parse_transaction_with_lookup_tables(unsigned_tx, encoding, full_transaction, custom_idls, None)
+}
+
+/// Parse a Solana transaction, resolving address table lookups against a lookup table provider
+///
+/// The provider is asked for every lookup table the message references. Lookups into a table it
+/// returns are resolved to the address stored in the table, so SPL transfer fields and IDL named
+/// accounts show real pubkeys instead of `ADDRESS_TABLE_LOOKUP`. Lookups into tables the provider
+/// does not know are left unresolved, and so are lookups past the end of a returned table, which
+/// are listed in the lookup's `out_of_range_indexes`. An error is only returned if the provider
+/// fails.
+///
+/// A `HashMap<String, AddressLookupTableContents>` can be passed to supply tables inline, and
+/// `DirectoryAddressLookupTableProvider` reads them from `solana address-lookup-table get` dumps.
+///
+/// # Arguments
+/// * `unsigned_tx` - The encoded transaction to parse
+/// * `encoding` - How `unsigned_tx` is encoded (hex, base58, base64, or `Auto` to detect it)
+/// * `full_transaction` - Whether the input is a full transaction or just a message
+/// * `custom_idls` - Optional map of program_id -> CustomIdlConfig (see `parse_transaction_with_idls`)
+/// * `lookup_tables` - Optional provider of lookup table contents, pass `None` to leave lookups unresolved
+pub fn parse_transaction_with_lookup_tables(
+    unsigned_tx: String,
+    encoding: TransactionEncoding,
+    full_transaction: bool,
+    custom_idls: Option<HashMap<String, CustomIdlConfig>>,
+    lookup_tables: Option<&dyn AddressLookupTableProvider>,
+) -> Result<SolanaParseResponse, TransactionParseError> {
     if unsigned_tx.is_empty() {
-        return Err("Transaction is empty".into());
+        return Err(TransactionParseError::Empty);
     }
 
-    let tx = SolanaTransaction::new_with_idls(&unsigned_tx, full_transaction, custom_idls)
-        .map_err(|e| {
-            Box::<dyn std::error::Error>::from(format!("Unable to parse transaction: {e}"))
-        })?;
+    let unsigned_tx_bytes = decode_transaction_bytes(&unsigned_tx, encoding)?;
+    let payload_kind = if full_transaction {
+        PayloadKind::Transaction
+    } else {
+        PayloadKind::Message
+    };
+    parse_transaction_bytes_into_response(
+        &unsigned_tx_bytes,
+        payload_kind,
+        custom_idls,
+        lookup_tables,
+    )
+}
+
+/// Parse a Solana payload without knowing whether it is a message or a full transaction
+///
+/// Both layouts are tried. An interpretation is only accepted if it parses, passes the
+/// message sanitization check, and (for full transactions) carries exactly as many signatures
+/// as the message header requires. The chosen interpretation is reported in
+/// `SolanaParsedTransactionPayload::payload_kind`. If both interpretations are valid an
+/// ambiguity error is returned and the caller must choose explicitly.
+///
+/// # Arguments
+/// * `unsigned_tx` - The encoded transaction or message to parse
+/// * `encoding` - How `unsigned_tx` is encoded (hex, base58, base64, or `Auto` to detect it)
+/// * `custom_idls` - Optional map of program_id -> CustomIdlConfig (see `parse_transaction_with_idls`)
+/// * `lookup_tables` - Optional provider of lookup table contents (see `parse_transaction_with_lookup_tables`)
+pub fn parse_transaction_auto_detect(
+    unsigned_tx: String,
+    encoding: TransactionEncoding,
+    custom_idls: Option<HashMap<String, CustomIdlConfig>>,
+    lookup_tables: Option<&dyn AddressLookupTableProvider>,
+) -> Result<SolanaParseResponse, TransactionParseError> {
+    if unsigned_tx.is_empty() {
+        return Err(TransactionParseError::Empty);
+    }
+
+    let unsigned_tx_bytes = decode_transaction_bytes(&unsigned_tx, encoding)?;
+    let payload_kind = detect_payload_kind(&unsigned_tx_bytes)?;
+    parse_transaction_bytes_into_response(
+        &unsigned_tx_bytes,
+        payload_kind,
+        custom_idls,
+        lookup_tables,
+    )
+}
+
+fn parse_transaction_bytes_into_response(
+    unsigned_tx_bytes: &[u8],
+    payload_kind: PayloadKind,
+    custom_idls: Option<HashMap<String, CustomIdlConfig>>,
+    lookup_tables: Option<&dyn AddressLookupTableProvider>,
+) -> Result<SolanaParseResponse, TransactionParseError> {
+    let full_transaction = payload_kind == PayloadKind::Transaction;
+    let tx = SolanaTransaction::from_bytes(unsigned_tx_bytes, full_transaction, custom_idls)?;
 
     // use the sanitize message to check for malformed transactions
-    tx.message.sanitize().map_err(|e| {
-        Box::<dyn std::error::Error>::from(format!(
-            "Solana transaction message failed sanitization check: {e}"
-        ))
-    })?;
+    tx.message
+        .sanitize()
+        .map_err(TransactionParseError::SanitizeFailed)?;
+    let tx = match lookup_tables {
+        Some(provider) => tx.with_address_lookup_tables(provider)?,
+        None => tx,
+    };
 
+    let transaction_metadata = tx.transaction_metadata()?;
     let payload = SolanaParsedTransactionPayload {
-        transaction_metadata: Some(tx.transaction_metadata()?),
-        unsigned_payload: unsigned_tx,
+        transaction_metadata: Some(transaction_metadata),
+        unsigned_payload: hex::encode(unsigned_tx_bytes),
+        payload_kind,
+        signable_payload: hex::encode(tx.message_bytes()),
+        message_hash: tx.message_hash(),
     };
 
     Ok(SolanaParseResponse {
@@ -115,6 +251,161 @@ pub fn parse_transaction_with_idls(
     })
 }
 
+/// Insert a signature into a transaction at the slot of the signer that produced it
+///
+/// The signer's slot is its index among the required signers in the message's static account
+/// keys. The transaction is re-serialized with the signature in place and returned in the same
+/// encoding as the input (hex when the input encoding was `Auto`), together with the required
+/// signers whose slots are still empty.
+///
+/// # Arguments
+/// * `unsigned_tx` - The encoded transaction (or message) to add the signature to
+/// * `encoding` - How `unsigned_tx` is encoded
+/// * `full_transaction` - Whether the input is a full transaction or just a message. When only a message is provided, every other slot is filled with a placeholder
+/// * `signer` - Base58 pubkey of the signer
+/// * `signature` - The 64 byte Ed25519 signature produced by `signer` over the message
+#[allow(dead_code)] // Public API - exported from lib.rs
+pub fn insert_signature(
+    unsigned_tx: String,
+    encoding: TransactionEncoding,
+    full_transaction: bool,
+    signer: &str,
+    signature: &[u8],
+) -> Result<SolanaSignatureInsertion, SignatureInsertionError> {
+    let unsigned_tx_bytes = decode_transaction_bytes(&unsigned_tx, encoding)?;
+    let mut tx = SolanaTransaction::from_bytes(&unsigned_tx_bytes, full_transaction, None)?;
+    tx.message
+        .sanitize()
+        .map_err(TransactionParseError::SanitizeFailed)?;
+
+    let signer_index = tx.add_signature(signer, signature)?;
+    let output_encoding = match encoding {
+        TransactionEncoding::Auto => TransactionEncoding::Hex,
+        other => other,
+    };
+    Ok(SolanaSignatureInsertion {
+        signer_index,
+        status: tx.verify_signatures()[signer_index].status,
+        transaction: tx.encode_transaction(output_encoding)?,
+        empty_signer_slots: tx.empty_signer_slots()?,
+    })
+}
+
+/*
+Detect Payload Kind
+- Context: the same bytes can in principle be read either as a bare message or as a full transaction (compact array of signatures followed by the message)
+- This function tries both layouts and keeps each interpretation that:
+    - parses without leftover bytes
+    - passes the Solana SDK message sanitization check
+    - (full transactions only) has a signature count equal to num_required_signatures in the message header
+- Exactly one valid interpretation is required, otherwise an error is returned
+*/
+fn detect_payload_kind(unsigned_tx_bytes: &[u8]) -> Result<PayloadKind, TransactionParseError> {
+    let as_transaction =
+        parse_solana_transaction_bytes(unsigned_tx_bytes, true, None).and_then(|tx| {
+            tx.message
+                .sanitize()
+                .map_err(TransactionParseError::SanitizeFailed)?;
+            let required = usize::from(tx.message.header().num_required_signatures);
+            if tx.signatures.len() != required {
+                return Err(TransactionParseError::SignatureCountMismatch {
+                    found: tx.signatures.len(),
+                    required,
+                });
+            }
+            Ok(())
+        });
+    let as_message =
+        parse_solana_transaction_bytes(unsigned_tx_bytes, false, None).and_then(|tx| {
+            tx.message
+                .sanitize()
+                .map_err(TransactionParseError::SanitizeFailed)
+        });
+
+    match (as_transaction, as_message) {
+        (Ok(()), Ok(())) => Err(TransactionParseError::AmbiguousPayload),
+        (Ok(()), Err(_)) => Ok(PayloadKind::Transaction),
+        (Err(_), Ok(())) => Ok(PayloadKind::Message),
+        (Err(transaction_err), Err(message_err)) => {
+            Err(TransactionParseError::UnrecognizedPayload {
+                as_transaction: Box::new(transaction_err),
+                as_message: Box::new(message_err),
+            })
+        }
+    }
+}
+
+/*
+Decode Transaction Bytes
+- This function decodes the string input to the parser into raw transaction bytes according to the requested encoding
+- Whitespace anywhere in the input is ignored, and a leading 0x is tolerated for hex input
+- Auto detection order:
+    - Hex: the input has a 0x prefix, or consists only of an even number of hex digits
+    - Base58: the input consists only of characters in the base58 alphabet (which excludes 0, O, I and l)
+    - Base64: anything else
+*/
+fn decode_transaction_bytes(
+    unsigned_tx: &str,
+    encoding: TransactionEncoding,
+) -> Result<Vec<u8>, TransactionParseError> {
+    let compact: String = unsigned_tx.chars().filter(|c| !c.is_whitespace()).collect();
+    let (has_hex_prefix, without_prefix) =
+        match compact.strip_prefix("0x").or(compact.strip_prefix("0X")) {
+            Some(rest) => (true, rest),
+            None => (false, compact.as_str()),
+        };
+
+    let encoding = match encoding {
+        TransactionEncoding::Auto => {
+            if has_hex_prefix
+                || (without_prefix.len() % 2 == 0
+                    && without_prefix.chars().all(|c| c.is_ascii_hexdigit()))
+            {
+                TransactionEncoding::Hex
+            } else if compact.chars().all(is_base58_char) {
+                TransactionEncoding::Base58
+            } else {
+                TransactionEncoding::Base64
+            }
+        }
+        other => other,
+    };
+
+    match encoding {
+        TransactionEncoding::Hex => hex::decode(without_prefix)
+            .map_err(|_| TransactionParseError::InvalidEncoding(encoding)),
+        TransactionEncoding::Base58 => bs58::decode(&compact)
+            .into_vec()
+            .map_err(|_| TransactionParseError::InvalidEncoding(encoding)),
+        TransactionEncoding::Base64 => BASE64_STANDARD
+            .decode(&compact)
+            .map_err(|_| TransactionParseError::InvalidEncoding(encoding)),
+        TransactionEncoding::Auto => unreachable!("auto encoding is resolved above"),
+    }
+}
+
+fn is_base58_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() && !matches!(c, '0' | 'O' | 'I' | 'l')
+}
+
+/*
+Encode Transaction Bytes
+- This function is the inverse of decode_transaction_bytes and encodes serialized transaction bytes into a string in the requested encoding
+- Hex output is lowercase without a 0x prefix
+- Auto is not a valid output encoding since there is nothing to detect
+*/
+fn encode_transaction_bytes(
+    tx_bytes: &[u8],
+    encoding: TransactionEncoding,
+) -> Result<String, TransactionSerializeError> {
+    match encoding {
+        TransactionEncoding::Hex => Ok(hex::encode(tx_bytes)),
+        TransactionEncoding::Base58 => Ok(bs58::encode(tx_bytes).into_string()),
+        TransactionEncoding::Base64 => Ok(BASE64_STANDARD.encode(tx_bytes)),
+        TransactionEncoding::Auto => Err(TransactionSerializeError::AutoOutputEncoding),
+    }
+}
+
 /*
 Parse Solana Transaction
 - This function takes an unsigned solana transaction hex string and parses it either as a v0 transaction or as legacy transaction (v0 transactions include Address Lookup Tables which allow more addresses to be included in a transaction by only including references to the addresses instead of the whole string)
@@ -124,7 +415,7 @@ fn parse_solana_transaction(
     unsigned_tx: &str,
     full_transaction: bool,
     custom_idls: Option<HashMap<String, (String, bool)>>,
-) -> Result<SolanaTransaction, Box<dyn std::error::Error>> {
+) -> Result<SolanaTransaction, TransactionParseError> {
     // Convert old API to new API
     let custom_configs = custom_idls.map(CustomIdlConfig::from_legacy_map);
     parse_solana_transaction_with_idls(unsigned_tx, full_transaction, custom_configs)
@@ -134,55 +425,135 @@ fn parse_solana_transaction_with_idls(
     unsigned_tx: &str,
     full_transaction: bool,
     custom_idls: Option<HashMap<String, CustomIdlConfig>>,
-) -> Result<SolanaTransaction, Box<dyn std::error::Error>> {
-    let unsigned_tx_bytes: Vec<u8> = hex::decode(unsigned_tx)
-        .map_err(|_| "unsigned Solana transaction provided is invalid hex")?;
+) -> Result<SolanaTransaction, TransactionParseError> {
+    let unsigned_tx_bytes = decode_transaction_bytes(unsigned_tx, TransactionEncoding::Hex)?;
+    parse_solana_transaction_bytes(&unsigned_tx_bytes, full_transaction, custom_idls)
+}
 
+fn parse_solana_transaction_bytes(
+    unsigned_tx_bytes: &[u8],
+    full_transaction: bool,
+    custom_idls: Option<HashMap<String, CustomIdlConfig>>,
+) -> Result<SolanaTransaction, TransactionParseError> {
     if unsigned_tx_bytes.is_empty() {
-        return Err(Box::<dyn std::error::Error>::from(
-            "unsigned Solana transaction provided must be non-empty",
-        ));
+        return Err(TransactionParseError::Empty);
     }
 
-    let custom_idl_records = idl_parser::construct_idl_records_map(custom_idls)?;
+    let custom_idl_records = idl_parser::construct_idl_records_map(custom_idls)
+        .map_err(|e| TransactionParseError::InvalidCustomIdl(e.to_string()))?;
 
-    if full_transaction {
-        let (signatures, tx_body) = parse_signatures(&unsigned_tx_bytes)?;
-        let message = match tx_body[0] {
-            V0_TRANSACTION_INDICATOR => parse_solana_v0_transaction(&tx_body[LEN_ARRAY_HEADER_BYTES..tx_body.len()]).map_err(|e| format!("Error parsing full transaction. If this is just a message instead of a full transaction, parse using the --message flag. Parsing Error: {e:#?}"))?,
-            _ => parse_solana_legacy_transaction(tx_body).map_err(|e| format!("Error parsing full transaction. If this is just a message instead of a full transaction, parse using the --message flag. Parsing Error: {e:#?}"))?,
-        };
-        return Ok(SolanaTransaction {
-            message,
-            signatures,
-            custom_idl_records,
-        });
-    }
-    let message = match unsigned_tx_bytes[0] {
-        V0_TRANSACTION_INDICATOR => parse_solana_v0_transaction(&unsigned_tx_bytes[LEN_ARRAY_HEADER_BYTES..unsigned_tx_bytes.len()]).map_err(|e| format!("Error parsing message. If this is a serialized Solana transaction with signatures, parse using the --transaction flag. Parsing error: {e:#?}"))?,
-        _ => parse_solana_legacy_transaction(&unsigned_tx_bytes).map_err(|e| format!("Error parsing message. If this is a full solana transaction with signatures or signature placeholders, parse using the --transaction flag. Parsing Error: {e:#?}"))?,
+    let (signatures, message_bytes) = if full_transaction {
+        parse_signatures(unsigned_tx_bytes).map_err(|e| locate_error(e, unsigned_tx_bytes.len()))?
+    } else {
+        (vec![], unsigned_tx_bytes) // Signatures array is empty when we are parsing a message (using --message) as opposed to a full transaction
     };
+    let message = parse_solana_message(message_bytes)
+        .map_err(|e| locate_error(e, unsigned_tx_bytes.len()))?;
     Ok(SolanaTransaction {
         message,
-        signatures: vec![],
+        message_bytes: message_bytes.to_vec(),
+        signatures,
         custom_idl_records,
-    }) // Signatures array is empty when we are parsing a message (using --message) as opposed to a full transaction
+        address_lookup_tables: HashMap::new(),
+        address_lookup_table_statuses: HashMap::new(),
+    })
+}
+
+/*
+Parse Solana Message
+- This function reads the version prefix of a message and dispatches to the legacy or v0 parser
+- A message whose first byte has the most significant bit set is versioned, and the remaining 7 bits are its version. Only version 0 is supported
+*/
+fn parse_solana_message(message_bytes: &[u8]) -> Result<VersionedMessage, TransactionParseError> {
+    validate_length(message_bytes, LEN_ARRAY_HEADER_BYTES, "Message")?;
+    match message_bytes[0] {
+        V0_TRANSACTION_INDICATOR => {
+            parse_solana_v0_transaction(&message_bytes[LEN_ARRAY_HEADER_BYTES..message_bytes.len()])
+        }
+        prefix if prefix & V0_TRANSACTION_INDICATOR != 0 => Err(
+            TransactionParseError::UnsupportedVersion(prefix & !V0_TRANSACTION_INDICATOR),
+        ),
+        _ => parse_solana_legacy_transaction(message_bytes),
+    }
+}
+
+/*
+Locate Error
+- Context: the parse helpers below only ever see the remainder of the input, which always runs to the end of the input, so they report positions as the number of bytes left rather than as offsets
+- This function converts those positions into byte offsets from the start of the input once the full input length is known
+*/
+fn locate_error(err: TransactionParseError, input_len: usize) -> TransactionParseError {
+    match err {
+        TransactionParseError::Truncated {
+            section,
+            offset,
+            needed,
+            available,
+        } => TransactionParseError::Truncated {
+            section,
+            offset: input_len - offset,
+            needed,
+            available,
+        },
+        TransactionParseError::InvalidCompactU16 { section, offset } => {
+            TransactionParseError::InvalidCompactU16 {
+                section,
+                offset: input_len - offset,
+            }
+        }
+        TransactionParseError::ExtraneousBytes { offset, count } => {
+            TransactionParseError::ExtraneousBytes {
+                offset: input_len - offset,
+                count,
+            }
+        }
+        other => other,
+    }
+}
+
+/*
+Decode Address Lookup Table
+- This function turns caller supplied lookup table contents into the table key and the list of addresses stored in the table
+- Raw account data is decoded with DecodedAddressLookupTable, which also reports whether the table is frozen or deactivating
+*/
+fn decode_address_lookup_table(
+    table: &str,
+    contents: AddressLookupTableContents,
+) -> Result<(Pubkey, Vec<Pubkey>, Option<AddressLookupTableStatus>), TransactionParseError> {
+    let invalid = |error: String| TransactionParseError::InvalidAddressLookupTable {
+        table: table.to_string(),
+        error,
+    };
+    let table_key =
+        Pubkey::from_str(table).map_err(|_| invalid("invalid table pubkey".to_string()))?;
+    let (addresses, status) = match contents {
+        AddressLookupTableContents::Addresses(addresses) => (addresses, None),
+        AddressLookupTableContents::AccountData(data) => {
+            let decoded = DecodedAddressLookupTable::decode(&data)
+                .map_err(|e| invalid(format!("invalid lookup table account data: {e}")))?;
+            let status = decoded.status();
+            (decoded.addresses, Some(status))
+        }
+    };
+    let addresses = addresses
+        .iter()
+        .map(|a| Pubkey::from_str(a).map_err(|_| invalid(format!("invalid address {a}"))))
+        .collect::<Result<Vec<Pubkey>, TransactionParseError>>()?;
+    Ok((table_key, addresses, status))
 }
 
 /*
 Parse Solana Legacy Transaction
 - This function sequentially parses each separate section of a solana legacy transaction and constructs a Legacy message object as defined by the Solana SDK
 */
-fn parse_solana_legacy_transaction(tx_body: &[u8]) -> Result<VersionedMessage, Box<dyn Error>> {
+fn parse_solana_legacy_transaction(
+    tx_body: &[u8],
+) -> Result<VersionedMessage, TransactionParseError> {
     let (header, tx_body_remainder) = parse_header(tx_body)?;
     let (account_keys, tx_body_remainder) = parse_accounts(tx_body_remainder)?;
     let (recent_blockhash, tx_body_remainder) = parse_block_hash(tx_body_remainder)?;
     let (instructions, tx_body_remainder) = parse_instructions(tx_body_remainder)?;
-    if !tx_body_remainder.is_empty() {
-        return Err(
-            "Legacy Transaction formatted incorrectly contains extraneous bytes at the end".into(),
-        );
-    }
+    validate_no_extraneous_bytes(tx_body_remainder)?;
     let message = VersionedMessage::Legacy(LegacyMessage {
         header,
         account_keys,
@@ -196,19 +567,14 @@ fn parse_solana_legacy_transaction(tx_body: &[u8]) -> Result<VersionedMessage, B
 Parse Solana V0 Transaction
 - This function sequentially parses each separate section of a solana v0 transaction and constructs a v0 message object as defined by the Solana SDK
 */
-fn parse_solana_v0_transaction(tx_body: &[u8]) -> Result<VersionedMessage, Box<dyn Error>> {
+fn parse_solana_v0_transaction(tx_body: &[u8]) -> Result<VersionedMessage, TransactionParseError> {
     let (header, tx_body_remainder) = parse_header(tx_body)?;
     let (account_keys, tx_body_remainder) = parse_accounts(tx_body_remainder)?;
     let (recent_blockhash, tx_body_remainder) = parse_block_hash(tx_body_remainder)?;
     let (instructions, tx_body_remainder) = parse_instructions(tx_body_remainder)?;
     let (address_table_lookups, tx_body_remainder) =
         parse_address_table_lookups(tx_body_remainder)?;
-    if !tx_body_remainder.is_empty() {
-        return Err(
-            "Solana V0 Transaction formatted incorrectly contains extraneous bytes at the end"
-                .into(),
-        );
-    }
+    validate_no_extraneous_bytes(tx_body_remainder)?;
 
     let message = VersionedMessage::V0(VersionZeroMessage {
         header,
@@ -220,6 +586,171 @@ fn parse_solana_v0_transaction(tx_body: &[u8]) -> Result<VersionedMessage, Box<d
     Ok(message)
 }
 
+/// Produce a byte-span annotated layout of a serialized Solana transaction or message
+///
+/// The input is first fully parsed and sanitized, so the layout is only produced for bytes the
+/// parser accepts. Every section of the wire format is then listed in order with its byte offset,
+/// length and raw hex, so that reviewers can audit exactly which bytes a signer is approving.
+///
+/// # Arguments
+/// * `unsigned_tx` - The encoded transaction or message
+/// * `encoding` - How `unsigned_tx` is encoded
+/// * `full_transaction` - Whether the input is a full transaction or just a message
+pub fn parse_transaction_layout(
+    unsigned_tx: &str,
+    encoding: TransactionEncoding,
+    full_transaction: bool,
+) -> Result<Vec<SolanaLayoutSection>, TransactionParseError> {
+    let unsigned_tx_bytes = decode_transaction_bytes(unsigned_tx, encoding)?;
+    let tx = parse_solana_transaction_bytes(&unsigned_tx_bytes, full_transaction, None)?;
+    tx.message
+        .sanitize()
+        .map_err(TransactionParseError::SanitizeFailed)?;
+    transaction_layout(&unsigned_tx_bytes, full_transaction)
+        .map_err(|e| locate_error(e, unsigned_tx_bytes.len()))
+}
+
+// LayoutRecorder records the span of each section as the transaction bytes are walked, using the length of the remaining bytes before and after each section to derive offsets
+struct LayoutRecorder<'a> {
+    tx_bytes: &'a [u8],
+    sections: Vec<SolanaLayoutSection>,
+}
+
+impl<'a> LayoutRecorder<'a> {
+    fn record(&mut self, section: String, before: &[u8], after: &[u8]) {
+        let offset = self.tx_bytes.len() - before.len();
+        let length = before.len() - after.len();
+        self.sections.push(SolanaLayoutSection {
+            section,
+            offset,
+            length,
+            hex: hex::encode(&before[..length]),
+        });
+    }
+}
+
+/*
+Transaction Layout
+- This function walks the serialized transaction with the same helpers used for parsing, recording the span of every section it consumes
+- Compact array headers are recorded as their own sections because they are 1-3 bytes long (compact-u16)
+*/
+fn transaction_layout(
+    tx_bytes: &[u8],
+    full_transaction: bool,
+) -> Result<Vec<SolanaLayoutSection>, TransactionParseError> {
+    let mut recorder = LayoutRecorder {
+        tx_bytes,
+        sections: vec![],
+    };
+    let mut remainder = tx_bytes;
+
+    if full_transaction {
+        let (num_signatures, after) = read_compact_u16(remainder, "Signature Array Header")?;
+        recorder.record("Signatures Array Header".to_string(), remainder, after);
+        remainder = after;
+        for i in 0..num_signatures {
+            validate_length(remainder, LEN_SOL_SIGNATURE_BYTES, "Signatures")?;
+            let after = &remainder[LEN_SOL_SIGNATURE_BYTES..];
+            recorder.record(format!("Signature {i}"), remainder, after);
+            remainder = after;
+        }
+    }
+
+    validate_length(remainder, LEN_ARRAY_HEADER_BYTES, "Message")?;
+    let is_v0 = remainder[0] == V0_TRANSACTION_INDICATOR;
+    if is_v0 {
+        let after = &remainder[LEN_ARRAY_HEADER_BYTES..];
+        recorder.record("Message Version Prefix".to_string(), remainder, after);
+        remainder = after;
+    }
+
+    let (_, after) = parse_header(remainder)?;
+    recorder.record("Message Header".to_string(), remainder, after);
+    remainder = after;
+
+    let (num_accounts, after) = read_compact_u16(remainder, "Accounts Array Header")?;
+    recorder.record("Account Keys Array Header".to_string(), remainder, after);
+    remainder = after;
+    for i in 0..num_accounts {
+        validate_length(remainder, LEN_SOL_ACCOUNT_KEY_BYTES, "Accounts")?;
+        let after = &remainder[LEN_SOL_ACCOUNT_KEY_BYTES..];
+        recorder.record(format!("Account Key {i}"), remainder, after);
+        remainder = after;
+    }
+
+    let (_, after) = parse_block_hash(remainder)?;
+    recorder.record("Recent Blockhash".to_string(), remainder, after);
+    remainder = after;
+
+    let (num_instructions, after) = read_compact_u16(remainder, "Instructions Array Header")?;
+    recorder.record("Instructions Array Header".to_string(), remainder, after);
+    remainder = after;
+    for i in 0..num_instructions {
+        validate_length(
+            remainder,
+            LEN_ARRAY_HEADER_BYTES,
+            "Instruction Program Index",
+        )?;
+        let after = &remainder[LEN_ARRAY_HEADER_BYTES..];
+        recorder.record(format!("Instruction {i} Program Index"), remainder, after);
+        remainder = after;
+
+        let (_, after) = parse_compact_array_of_bytes(remainder, "Instruction Account Indexes")?;
+        recorder.record(format!("Instruction {i} Account Indexes"), remainder, after);
+        remainder = after;
+
+        let (_, after) = parse_compact_array_of_bytes(remainder, "Instruction Data")?;
+        recorder.record(format!("Instruction {i} Data"), remainder, after);
+        remainder = after;
+    }
+
+    if is_v0 {
+        let (num_lookups, after) =
+            read_compact_u16(remainder, "Instructions Address Table Lookup Header")?;
+        recorder.record(
+            "Address Table Lookups Array Header".to_string(),
+            remainder,
+            after,
+        );
+        remainder = after;
+        for i in 0..num_lookups {
+            validate_length(
+                remainder,
+                LEN_SOL_ACCOUNT_KEY_BYTES,
+                "Address Table Lookup Program Account Key",
+            )?;
+            let after = &remainder[LEN_SOL_ACCOUNT_KEY_BYTES..];
+            recorder.record(
+                format!("Address Table Lookup {i} Account Key"),
+                remainder,
+                after,
+            );
+            remainder = after;
+
+            let (_, after) =
+                parse_compact_array_of_bytes(remainder, "Address Table Lookup Writable Indexes")?;
+            recorder.record(
+                format!("Address Table Lookup {i} Writable Indexes"),
+                remainder,
+                after,
+            );
+            remainder = after;
+
+            let (_, after) =
+                parse_compact_array_of_bytes(remainder, "Address Table Lookup Read-Only Indexes")?;
+            recorder.record(
+                format!("Address Table Lookup {i} Read-Only Indexes"),
+                remainder,
+                after,
+            );
+            remainder = after;
+        }
+    }
+
+    validate_no_extraneous_bytes(remainder)?;
+    Ok(recorder.sections)
+}
+
 /*
 Validate Length
 - Context: Solana transactions must be parsed sequentially because it's formatting includes "Compact Arrays" who specify their length by their first byte, so the length of each section is not known beforehand
@@ -234,12 +765,28 @@ fn validate_length(
     bytes: &[u8],
     length: usize,
     section: &str,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<(), TransactionParseError> {
     if bytes.len() < length {
-        return Err(format!(
-            "Unsigned transaction provided is incorrectly formatted, error while parsing {section}"
-        )
-        .into());
+        return Err(TransactionParseError::Truncated {
+            section: section.to_string(),
+            offset: bytes.len(), // bytes left, converted to an offset by locate_error
+            needed: length,
+            available: bytes.len(),
+        });
+    }
+    Ok(())
+}
+
+/*
+Validate No Extraneous Bytes
+- This function checks that nothing is left over once the final section of a message has been parsed
+*/
+fn validate_no_extraneous_bytes(remainder: &[u8]) -> Result<(), TransactionParseError> {
+    if !remainder.is_empty() {
+        return Err(TransactionParseError::ExtraneousBytes {
+            offset: remainder.len(), // bytes left, converted to an offset by locate_error
+            count: remainder.len(),
+        });
     }
     Ok(())
 }
@@ -247,29 +794,30 @@ fn validate_length(
 /*
 Parse Signatures
 - Context: Solana transactions contain a compact array of signatures at the beginning of a transaction
-- This function parses these signatures.
+- This function parses these signatures. Like every other compact array in the wire format, the length prefix is a compact-u16
 - NOTE: This is only relevant for when we are parsing FULL TRANSACTIONS (using the flag --transasction) not when we are parsing only the message (using --message)
 */
 #[allow(clippy::type_complexity)]
 fn parse_signatures(
     unsigned_tx_bytes: &[u8],
-) -> Result<(Vec<Signature>, &[u8]), Box<dyn std::error::Error>> {
+) -> Result<(Vec<Signature>, &[u8]), TransactionParseError> {
     validate_length(
         unsigned_tx_bytes,
         LEN_ARRAY_HEADER_BYTES,
         "Signature Array Header",
     )?;
-    let num_signatures = unsigned_tx_bytes[0] as usize;
-    let parse_len = 1 + (num_signatures * LEN_SOL_SIGNATURE_BYTES);
-    validate_length(unsigned_tx_bytes, parse_len, "Signatures")?;
-    let signatures: Vec<Signature> = unsigned_tx_bytes[1..]
+    let (num_signatures, signatures_remainder) =
+        read_compact_u16(unsigned_tx_bytes, "Signature Array Header")?;
+    let parse_len = num_signatures * LEN_SOL_SIGNATURE_BYTES;
+    validate_length(signatures_remainder, parse_len, "Signatures")?;
+    let signatures: Vec<Signature> = signatures_remainder
         .chunks_exact(LEN_SOL_SIGNATURE_BYTES)
         .take(num_signatures)
         .map(<[u8]>::to_vec)
         .collect();
     Ok((
         signatures,
-        &unsigned_tx_bytes[parse_len..unsigned_tx_bytes.len()],
+        &signatures_remainder[parse_len..signatures_remainder.len()],
     ))
 }
 
@@ -278,9 +826,7 @@ Parse Header
 - This function is used to parse the three bytes at the beginning of a solana transaction message that comprise the header.
 - The bytes specify the number of signatures required, the number of read only signing accounts, and the number of read only non signer accounts in the transaction
 */
-fn parse_header(
-    tx_body_remainder: &[u8],
-) -> Result<(MessageHeader, &[u8]), Box<dyn std::error::Error>> {
+fn parse_header(tx_body_remainder: &[u8]) -> Result<(MessageHeader, &[u8]), TransactionParseError> {
     validate_length(
         tx_body_remainder,
         LEN_MESSAGE_HEADER_BYTES,
@@ -300,25 +846,25 @@ fn parse_header(
 /*
 Parse Accounts
 - This function parses the compact array of all static account keys (as opposed to address table lookups) included in this transaction
+- The length prefix is a compact-u16, so transactions with 128 or more static keys have a multi-byte header
 */
 #[allow(clippy::type_complexity)]
-fn parse_accounts(
-    tx_body_remainder: &[u8],
-) -> Result<(Vec<Pubkey>, &[u8]), Box<dyn std::error::Error>> {
+fn parse_accounts(tx_body_remainder: &[u8]) -> Result<(Vec<Pubkey>, &[u8]), TransactionParseError> {
     validate_length(
         tx_body_remainder,
         LEN_ARRAY_HEADER_BYTES,
         "Accounts Array Header",
     )?;
-    let accounts_num = tx_body_remainder[0] as usize;
-    let len_accounts_array = (LEN_SOL_ACCOUNT_KEY_BYTES * accounts_num) + LEN_ARRAY_HEADER_BYTES;
+    let (accounts_num, tx_body_remainder) =
+        read_compact_u16(tx_body_remainder, "Accounts Array Header")?;
+    let len_accounts_array = LEN_SOL_ACCOUNT_KEY_BYTES * accounts_num;
     validate_length(tx_body_remainder, len_accounts_array, "Accounts")?;
     let mut pubkeys: Vec<Pubkey> = Vec::with_capacity(accounts_num);
     for i in 0..accounts_num {
         let mut pubkey_sized_bytes = [0u8; LEN_SOL_ACCOUNT_KEY_BYTES];
         pubkey_sized_bytes.copy_from_slice(
-            &tx_body_remainder[((i * LEN_SOL_ACCOUNT_KEY_BYTES) + LEN_ARRAY_HEADER_BYTES)
-                ..=((i + 1) * LEN_SOL_ACCOUNT_KEY_BYTES)],
+            &tx_body_remainder
+                [(i * LEN_SOL_ACCOUNT_KEY_BYTES)..((i + 1) * LEN_SOL_ACCOUNT_KEY_BYTES)],
         );
         pubkeys.push(Pubkey::new_from_array(pubkey_sized_bytes));
     }
@@ -332,7 +878,7 @@ fn parse_accounts(
 Parse Block Hash
 - This function parses the recent block hash included in the transaction
 */
-fn parse_block_hash(tx_body_remainder: &[u8]) -> Result<(Hash, &[u8]), Box<dyn std::error::Error>> {
+fn parse_block_hash(tx_body_remainder: &[u8]) -> Result<(Hash, &[u8]), TransactionParseError> {
     validate_length(tx_body_remainder, LEN_SOL_ACCOUNT_KEY_BYTES, "Block Hash")?;
     let hash_bytes: &[u8] = &tx_body_remainder[0..LEN_SOL_ACCOUNT_KEY_BYTES];
     let block_hash = Hash::new(hash_bytes);
@@ -349,16 +895,15 @@ Parse Instructions
 #[allow(clippy::type_complexity)]
 fn parse_instructions(
     tx_body_remainder: &[u8],
-) -> Result<(Vec<CompiledInstruction>, &[u8]), Box<dyn std::error::Error>> {
+) -> Result<(Vec<CompiledInstruction>, &[u8]), TransactionParseError> {
     validate_length(
         tx_body_remainder,
         LEN_ARRAY_HEADER_BYTES,
         "Instructions Array Header",
     )?;
-    let insts_num = tx_body_remainder[0] as usize;
+    let (insts_num, mut parsed_tx_body_remainder) =
+        read_compact_u16(tx_body_remainder, "Instructions Array Header")?;
     let mut compiled_insts: Vec<CompiledInstruction> = Vec::with_capacity(insts_num);
-    let mut parsed_tx_body_remainder =
-        &tx_body_remainder[LEN_ARRAY_HEADER_BYTES..tx_body_remainder.len()];
     for _ in 0..insts_num {
         let (new_inst, remainder_bytes) = parse_single_instruction(parsed_tx_body_remainder)?;
         parsed_tx_body_remainder = remainder_bytes;
@@ -373,7 +918,7 @@ Parse Single Instruction
 */
 fn parse_single_instruction(
     tx_body_remainder: &[u8],
-) -> Result<(CompiledInstruction, &[u8]), Box<dyn std::error::Error>> {
+) -> Result<(CompiledInstruction, &[u8]), TransactionParseError> {
     validate_length(
         tx_body_remainder,
         LEN_ARRAY_HEADER_BYTES,
@@ -401,15 +946,17 @@ Parse Address Table Lookups
 #[allow(clippy::type_complexity)]
 fn parse_address_table_lookups(
     tx_body_remainder: &[u8],
-) -> Result<(Vec<MessageAddressTableLookup>, &[u8]), Box<dyn std::error::Error>> {
+) -> Result<(Vec<MessageAddressTableLookup>, &[u8]), TransactionParseError> {
     validate_length(
         tx_body_remainder,
         LEN_ARRAY_HEADER_BYTES,
         "Instructions Address Table Lookup Header",
     )?;
-    let lookups_num = tx_body_remainder[0] as usize;
+    let (lookups_num, mut parsed_remainder) = read_compact_u16(
+        tx_body_remainder,
+        "Instructions Address Table Lookup Header",
+    )?;
     let mut lookups: Vec<MessageAddressTableLookup> = Vec::with_capacity(lookups_num);
-    let mut parsed_remainder = &tx_body_remainder[LEN_ARRAY_HEADER_BYTES..tx_body_remainder.len()];
     for _ in 0..lookups_num {
         let (new_lookup, remainder_bytes) = parse_single_address_table_lookup(parsed_remainder)?;
         parsed_remainder = remainder_bytes;
@@ -424,7 +971,7 @@ Parse Single Address Table Lookup
 */
 fn parse_single_address_table_lookup(
     tx_body_remainder: &[u8],
-) -> Result<(MessageAddressTableLookup, &[u8]), Box<dyn std::error::Error>> {
+) -> Result<(MessageAddressTableLookup, &[u8]), TransactionParseError> {
     validate_length(
         tx_body_remainder,
         LEN_SOL_ACCOUNT_KEY_BYTES,
@@ -456,13 +1003,14 @@ Parse Compact Array of Bytes
 fn parse_compact_array_of_bytes<'a>(
     tx_body_remainder: &'a [u8],
     section: &str,
-) -> Result<(Vec<u8>, &'a [u8]), Box<dyn std::error::Error>> {
+) -> Result<(Vec<u8>, &'a [u8]), TransactionParseError> {
     validate_length(
         tx_body_remainder,
         LEN_ARRAY_HEADER_BYTES,
         &format!("{section} Array Header"),
     )?;
-    let (length, tx_body_remainder) = read_compact_u16(tx_body_remainder)?;
+    let (length, tx_body_remainder) =
+        read_compact_u16(tx_body_remainder, &format!("{section} Array Header"))?;
     let parse_len = length * LEN_ARRAY_HEADER_BYTES;
     validate_length(tx_body_remainder, parse_len, &format!("{section} Array"))?;
     let bytes: Vec<u8> = tx_body_remainder[0..parse_len].to_vec();
@@ -481,10 +1029,12 @@ Read Compact u16
 - In other words, while parsing a byte that is part of a compact-u16, if the first bit is 1, then there will be a next byte (unless it is the 3rd bit, then the first bit being a 1 is erroneous and malformatted)
 - FINALLY -- if the final format is zzyyyyyyyxxxxxxx it is represented in compactu16 as 1xxxxxxx1yyyyyyy000000zz
 - In a 3 byte representation, the first byte has the 7 least significant digits, and the 3rd byte has the two most significant digits of the final u16
+- Only the shortest encoding of a value is accepted, as in the runtime's short_vec decoder: a final byte of zero after a continuation byte (e.g. 0x8c 0x00 for 12) is an alias and is rejected
 */
-fn read_compact_u16(
-    tx_body_remainder: &[u8],
-) -> Result<(usize, &[u8]), Box<dyn std::error::Error>> {
+fn read_compact_u16<'a>(
+    tx_body_remainder: &'a [u8],
+    section: &str,
+) -> Result<(usize, &'a [u8]), TransactionParseError> {
     let mut value = 0u16;
     let mut shift = 0;
     let mut bytes_read = 0;
@@ -492,9 +1042,12 @@ fn read_compact_u16(
     loop {
         // Check if there are enough bytes
         if bytes_read >= tx_body_remainder.len() {
-            return Err(
-                "error parsing unsigned transaction: unable to parse compact array header, not enough bytes".into()
-            );
+            return Err(TransactionParseError::Truncated {
+                section: section.to_string(),
+                offset: tx_body_remainder.len(), // bytes left, converted to an offset by locate_error
+                needed: bytes_read + 1,
+                available: tx_body_remainder.len(),
+            });
         }
 
         let byte = tx_body_remainder[bytes_read];
@@ -502,9 +1055,18 @@ fn read_compact_u16(
 
         // if we're on the 3rd byte, and the any bits other than the two least significant bits are set, this is an error
         if shift == 14 && (byte & 0xfc) != 0 {
-            return Err(
-                "error parsing unsigned transaction: third byte in compact u16 has more than it's 2 least significant bits set".into()
-            );
+            return Err(TransactionParseError::InvalidCompactU16 {
+                section: section.to_string(),
+                offset: tx_body_remainder.len(), // bytes left, converted to an offset by locate_error
+            });
+        }
+
+        // a zero byte after a continuation byte adds no bits, so the value has a shorter encoding
+        if shift > 0 && byte == 0 {
+            return Err(TransactionParseError::InvalidCompactU16 {
+                section: section.to_string(),
+                offset: tx_body_remainder.len(), // bytes left, converted to an offset by locate_error
+            });
         }
 
         // remove the continuation bit from the new byte and shift it over by the correct amount to put it in the final bit representation of the u16 number
@@ -521,6 +1083,33 @@ fn read_compact_u16(
     }
 }
 
+/*
+Write Compact u16
+- This function is the inverse of read_compact_u16 and appends the canonical (shortest) compact-u16 encoding of a length to the output
+- Lengths in a serialized transaction never exceed u16::MAX, since a serialized transaction is at most 1232 bytes
+*/
+fn write_compact_u16(
+    output: &mut Vec<u8>,
+    section: &str,
+    length: usize,
+) -> Result<(), TransactionSerializeError> {
+    let mut remaining =
+        u16::try_from(length).map_err(|_| TransactionSerializeError::CompactArrayTooLong {
+            section: section.to_string(),
+            len: length,
+        })?;
+    loop {
+        let byte = (remaining & 0x7f) as u8;
+        remaining >>= 7;
+        if remaining == 0 {
+            output.push(byte);
+            return Ok(());
+        }
+        // set the continuation bit since there will be another byte
+        output.push(byte | 0x80);
+    }
+}
+
 // SplInstructionData represents parsed instruction data of an instruction to the solana token program or the token 2022 program
 enum SplInstructionData {
     Transfer { amount: u64 },
@@ -590,11 +1179,26 @@ fn unpack_u64(input: &[u8]) -> Result<(u64, &[u8]), Box<dyn std::error::Error>>
 // Each signature is a Vec<u8> of 64 bytes
 pub type Signature = Vec<u8>;
 
+// Verifies a single Ed25519 signature, rejecting malformed public keys and signatures as well as non-canonical encodings
+fn verify_ed25519_signature(pubkey: &[u8; 32], message: &[u8], signature: &[u8]) -> bool {
+    let Ok(signature) = ed25519_dalek::Signature::from_slice(signature) else {
+        return false;
+    };
+    ed25519_dalek::VerifyingKey::from_bytes(pubkey)
+        .is_ok_and(|verifying_key| verifying_key.verify_strict(message, &signature).is_ok())
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct SolanaTransaction {
     message: VersionedMessage,
+    // The exact serialized message bytes that were parsed -- these are the bytes that signers sign
+    message_bytes: Vec<u8>,
     signatures: Vec<Signature>,
     custom_idl_records: HashMap<String, IdlRecord>,
+    // Caller supplied lookup table contents used to resolve address table lookups
+    address_lookup_tables: HashMap<Pubkey, Vec<Pubkey>>,
+    // Status of the lookup tables that were supplied as raw account data
+    address_lookup_table_statuses: HashMap<Pubkey, AddressLookupTableStatus>,
 }
 impl SolanaTransaction {
     #[allow(dead_code)] // Kept for backwards compatibility, use new_with_idls
@@ -602,18 +1206,144 @@ impl SolanaTransaction {
         hex_tx: &str,
         full_transaction: bool,
         custom_idls: Option<HashMap<String, (String, bool)>>,
-    ) -> Result<Self, Box<dyn Error>> {
+    ) -> Result<Self, TransactionParseError> {
         parse_solana_transaction(hex_tx, full_transaction, custom_idls)
     }
 
+    #[allow(dead_code)] // Public API
     pub fn new_with_idls(
         hex_tx: &str,
         full_transaction: bool,
         custom_idls: Option<HashMap<String, CustomIdlConfig>>,
-    ) -> Result<Self, Box<dyn Error>> {
+    ) -> Result<Self, TransactionParseError> {
         parse_solana_transaction_with_idls(hex_tx, full_transaction, custom_idls)
     }
 
+    #[allow(dead_code)] // Public API
+    pub fn new_with_encoding(
+        encoded_tx: &str,
+        encoding: TransactionEncoding,
+        full_transaction: bool,
+        custom_idls: Option<HashMap<String, CustomIdlConfig>>,
+    ) -> Result<Self, TransactionParseError> {
+        let tx_bytes = decode_transaction_bytes(encoded_tx, encoding)?;
+        parse_solana_transaction_bytes(&tx_bytes, full_transaction, custom_idls)
+    }
+
+    pub fn from_bytes(
+        tx_bytes: &[u8],
+        full_transaction: bool,
+        custom_idls: Option<HashMap<String, CustomIdlConfig>>,
+    ) -> Result<Self, TransactionParseError> {
+        parse_solana_transaction_bytes(tx_bytes, full_transaction, custom_idls)
+    }
+
+    /// Load the address lookup tables referenced by the message from a provider, so that lookups
+    /// into them resolve to real addresses
+    ///
+    /// Lookups whose index points past the end of a returned table stay unresolved, and their
+    /// indexes are reported in the `out_of_range_indexes` of the top level address table lookup.
+    pub fn with_address_lookup_tables(
+        mut self,
+        provider: &dyn AddressLookupTableProvider,
+    ) -> Result<Self, TransactionParseError> {
+        let lookups = self.message.address_table_lookups().unwrap_or_default();
+        for lookup in lookups {
+            if self.address_lookup_tables.contains_key(&lookup.account_key) {
+                continue;
+            }
+            let table = lookup.account_key.to_string();
+            let contents = provider.lookup_table(&table).map_err(|e| {
+                TransactionParseError::InvalidAddressLookupTable {
+                    table: table.clone(),
+                    error: e.to_string(),
+                }
+            })?;
+            if let Some(contents) = contents {
+                let (table_key, addresses, status) = decode_address_lookup_table(&table, contents)?;
+                self.address_lookup_tables.insert(table_key, addresses);
+                if let Some(status) = status {
+                    self.address_lookup_table_statuses.insert(table_key, status);
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    // Returns the address stored at an index of a lookup table, if the caller supplied that table
+    fn lookup_table_address(&self, table_key: &Pubkey, index: u8) -> Option<String> {
+        self.address_lookup_tables
+            .get(table_key)
+            .and_then(|addresses| addresses.get(usize::from(index)))
+            .map(Pubkey::to_string)
+    }
+
+    /// Returns the exact message bytes that were parsed, without the signature array.
+    /// These are the bytes that signers sign.
+    pub fn message_bytes(&self) -> &[u8] {
+        &self.message_bytes
+    }
+
+    /// Returns the Solana message hash (blake3 of the message bytes, as used by the runtime) encoded as base58
+    pub fn message_hash(&self) -> String {
+        VersionedMessage::hash_raw_message(&self.message_bytes).to_string()
+    }
+
+    /// Serializes the parsed message back into canonical legacy or v0 message bytes
+    ///
+    /// This is a canonical re-encoding. For input that was already canonical it is identical to
+    /// the parsed message bytes; signers sign the bytes returned by `message_bytes()`.
+    #[allow(dead_code)] // Public API
+    pub fn serialize_message(&self) -> Vec<u8> {
+        self.message.serialize()
+    }
+
+    /// Serializes the parsed transaction into wire bytes: the compact array of signatures
+    /// followed by the message bytes exactly as they were parsed, since those are the bytes the
+    /// signatures cover.
+    ///
+    /// If only a message was parsed there are no signatures, so a zeroed placeholder signature
+    /// is emitted for every required signer.
+    #[allow(dead_code)] // Public API
+    pub fn serialize_transaction(&self) -> Result<Vec<u8>, TransactionSerializeError> {
+        let placeholders;
+        let signatures = if self.signatures.is_empty() {
+            placeholders = vec![
+                vec![0u8; LEN_SOL_SIGNATURE_BYTES];
+                usize::from(self.message.header().num_required_signatures)
+            ];
+            &placeholders
+        } else {
+            &self.signatures
+        };
+
+        let mut tx_bytes = vec![];
+        write_compact_u16(&mut tx_bytes, "signatures", signatures.len())?;
+        for signature in signatures {
+            tx_bytes.extend_from_slice(signature);
+        }
+        tx_bytes.extend_from_slice(&self.message_bytes);
+        Ok(tx_bytes)
+    }
+
+    /// Encodes the canonical message bytes as hex, base58 or base64
+    #[allow(dead_code)] // Public API
+    pub fn encode_message(
+        &self,
+        encoding: TransactionEncoding,
+    ) -> Result<String, TransactionSerializeError> {
+        encode_transaction_bytes(&self.serialize_message(), encoding)
+    }
+
+    /// Encodes the transaction bytes as hex, base58 or base64
+    #[allow(dead_code)] // Public API
+    pub fn encode_transaction(
+        &self,
+        encoding: TransactionEncoding,
+    ) -> Result<String, TransactionSerializeError> {
+        encode_transaction_bytes(&self.serialize_transaction()?, encoding)
+    }
+
     fn all_account_key_strings(&self) -> Vec<String> {
         self.message
             .static_account_keys()
@@ -668,14 +1398,9 @@ impl SolanaTransaction {
     lets say an instruction references address at index 16 (the 17th address in this array)
     - This would resolve to the 4th READ ONLY address lookup in ALT #1 (a=5, b=5, d=3, and then the 4th address in c would be at index 16)
     */
-    fn resolve_address_table_lookup(
-        &self,
-        index: usize,
-    ) -> Result<SolanaSingleAddressTableLookup, Box<dyn Error>> {
+    fn resolve_address_table_lookup(&self, index: usize) -> Option<SolanaSingleAddressTableLookup> {
         match &self.message {
-            VersionedMessage::Legacy(_) => {
-                Err("legacy transaction instruction account index out of bounds".into())
-            }
+            VersionedMessage::Legacy(_) => None,
             VersionedMessage::V0(message) => {
                 let lookup_index = index - message.account_keys.len();
                 let mut num_parsed_indexes = 0;
@@ -683,10 +1408,13 @@ impl SolanaTransaction {
                 // Go through writable indexes first
                 for l in message.address_table_lookups.clone() {
                     if lookup_index < (num_parsed_indexes + l.writable_indexes.len()) {
-                        return Ok(SolanaSingleAddressTableLookup {
+                        let table_index = l.writable_indexes[lookup_index - num_parsed_indexes];
+                        return Some(SolanaSingleAddressTableLookup {
                             address_table_key: l.account_key.to_string(),
-                            index: i32::from(l.writable_indexes[lookup_index - num_parsed_indexes]),
+                            index: i32::from(table_index),
                             writable: true,
+                            resolved_address: self
+                                .lookup_table_address(&l.account_key, table_index),
                         });
                     }
                     num_parsed_indexes += l.writable_indexes.len();
@@ -695,15 +1423,18 @@ impl SolanaTransaction {
                 // Go through readable indexes next
                 for l in message.address_table_lookups.clone() {
                     if lookup_index < (num_parsed_indexes + l.readonly_indexes.len()) {
-                        return Ok(SolanaSingleAddressTableLookup {
+                        let table_index = l.readonly_indexes[lookup_index - num_parsed_indexes];
+                        return Some(SolanaSingleAddressTableLookup {
                             address_table_key: l.account_key.to_string(),
-                            index: i32::from(l.readonly_indexes[lookup_index - num_parsed_indexes]),
+                            index: i32::from(table_index),
                             writable: false,
+                            resolved_address: self
+                                .lookup_table_address(&l.account_key, table_index),
                         });
                     }
                     num_parsed_indexes += l.readonly_indexes.len();
                 }
-                Err("versioned transaction instruction account index out of bounds".into())
+                None
             }
         }
     }
@@ -712,27 +1443,42 @@ impl SolanaTransaction {
     fn all_instructions_and_transfers(
         &self,
     ) -> Result<
-        (Vec<SolanaInstruction>, Vec<SolTransfer>, Vec<SplTransfer>),
-        Box<dyn std::error::Error>,
+        (
+            Vec<SolanaInstruction>,
+            Vec<SolTransfer>,
+            Vec<SplTransfer>,
+            Vec<TransactionWarning>,
+        ),
+        InvalidInstructionsError,
     > {
         // use the sanitize message to check for malformed transactions
-        self.message.sanitize().map_err(|e| {
-            Box::<dyn std::error::Error>::from(format!("Solana transaction message failed sanitization check while parsing instructions: {}", e))
-        })?;
+        self.message
+            .sanitize()
+            .map_err(InvalidInstructionsError::SanitizeFailed)?;
         let mut instructions: Vec<SolanaInstruction> = vec![];
         let mut transfers: Vec<SolTransfer> = vec![];
         let mut spl_transfers: Vec<SplTransfer> = vec![];
-        for i in self.message.instructions() {
+        let mut warnings: Vec<TransactionWarning> = vec![];
+        // confidential transfer instructions locate the proof instructions they rely on by their offset in the transaction
+        let sibling_instructions = self.instruction_programs_and_data();
+        for (index, i) in self.message.instructions().iter().enumerate() {
             // all_transaction_addresses contains all addresses (both static and table lookups) for the transaction
             let mut all_transaction_addresses: Vec<AccountAddress> = vec![];
 
             // The arrays below separately parse the different types of transaction accounts into two separate arrays -- either static or table lookups
             let mut static_accounts: Vec<SolanaAccount> = vec![];
             let mut atlu_addresses: Vec<SolanaSingleAddressTableLookup> = vec![];
+            let account_index_out_of_bounds =
+                |account_index| InvalidInstructionsError::AccountIndexOutOfBounds {
+                    instruction_index: index,
+                    account_index,
+                };
             for a in i.accounts.clone() {
                 // if the index is out of bounds of the static account keys array it is an address lookup table (only for versioned transactions)
                 if a as usize >= self.message.static_account_keys().len() {
-                    let atlu = self.resolve_address_table_lookup(a as usize)?;
+                    let atlu = self
+                        .resolve_address_table_lookup(a as usize)
+                        .ok_or_else(|| account_index_out_of_bounds(a))?;
                     // push the parsed address table lookup to both the lookups array AND the combined all transaction address array
                     atlu_addresses.push(atlu.clone());
                     all_transaction_addresses
@@ -743,7 +1489,7 @@ impl SolanaTransaction {
                     .message
                     .static_account_keys()
                     .get(a as usize)
-                    .ok_or("instruction account index out of bounds for account keys array")?
+                    .ok_or_else(|| account_index_out_of_bounds(a))?
                     .to_string();
                 let acct = SolanaAccount {
                     account_key,
@@ -756,53 +1502,33 @@ impl SolanaTransaction {
             }
             // Make sure that program id is a statically included address -- Including program ID's as Address Table Lookups is INVALID
             if i.program_id_index as usize >= self.message.static_account_keys().len() {
-                return Err(Box::<dyn std::error::Error>::from(
-                    "Solana Instruction program index must be within static account keys",
-                ));
+                return Err(InvalidInstructionsError::ProgramIdNotStatic {
+                    instruction_index: index,
+                    program_id_index: i.program_id_index,
+                });
             }
             let program_key = i.program_id(self.message.static_account_keys()).to_string();
 
-            match program_key.as_str() {
-                SOL_SYSTEM_PROGRAM_KEY => {
-                    let system_instruction: SystemInstruction = bincode::deserialize(&i.data)
-                        .map_err(|_| "could not parse system instruction")?;
-                    if let SystemInstruction::Transfer { lamports } = system_instruction {
-                        // NOTE: While Solana system program Transfer instructions require 2 addresses (from and to), the Solana runtime is permissive and allows for the instructions to include more than 2 addresses
-                        // In the case that more than 2 addresses are included, they are ignored by the Solana runtime, so the same is done here
-                        // A third address being included may happen due to SDK artifacts, or to store state for some unconventional flows
-                        // Notably, transactions generated by Jupiter for limit order v2 deposits include these -- https://dev.jup.ag/api-reference/trigger/v2/deposit-craft
-                        if all_transaction_addresses.len() < 2 {
-                            return Err(Box::<dyn std::error::Error>::from("System Program Transfer Instruction should have at least 2 arguments"))
-                        }
-                        let transfer = SolTransfer {
-                            amount: lamports.to_string(),
-                            from: all_transaction_addresses[0].to_string(), // the "from" address is the address at index 0 in the address parameter array in a Sol transfer
-                            to: all_transaction_addresses[1].to_string(), // the "to" address is the address at index 1 in the address parameter array in a Sol transfer
-                        };
-                        transfers.push(transfer);
-                    }
-                }
-                TOKEN_PROGRAM_KEY => {
-                    let token_program_instruction: SplInstructionData =
-                        SplInstructionData::parse_spl_transfer_data(&i.data)?;
-                    if let Some(spl_transfer) = self.parse_spl_instruction_data(
-                        token_program_instruction,
-                        all_transaction_addresses.clone(),
-                    )? {
-                        spl_transfers.push(spl_transfer);
-                    }
-                }
-                TOKEN_2022_PROGRAM_KEY => {
-                    let token_program_22_instruction: SplInstructionData =
-                        SplInstructionData::parse_spl_transfer_data(&i.data)?;
-                    if let Some(spl_transfer) = self.parse_spl_instruction_data(
-                        token_program_22_instruction,
-                        all_transaction_addresses.clone(),
-                    )? {
-                        spl_transfers.push(spl_transfer);
-                    }
-                }
-                _ => {}
+            // A native instruction that cannot be decoded is recorded on the instruction instead of failing the whole transaction
+            let (native_instruction, instruction_parse_error) = match self.parse_native_instruction(
+                &program_key,
+                &i.data,
+                &all_transaction_addresses,
+                SiblingInstructions {
+                    instructions: &sibling_instructions,
+                    current: index,
+                },
+                &mut transfers,
+                &mut spl_transfers,
+            ) {
+                Ok(parsed) => (parsed, None),
+                Err(e) => (None, Some(e)),
+            };
+            if let Some(parsed_instruction) = &native_instruction {
+                warnings.extend(associated_token_account::associated_token_account_warnings(
+                    index,
+                    parsed_instruction,
+                ));
             }
 
             let (parsed_inst_option, idl_parse_error) = parse_idl(
@@ -810,7 +1536,7 @@ impl SolanaTransaction {
                 &all_transaction_addresses,
                 i,
                 &self.custom_idl_records,
-            )?;
+            );
 
             let instruction_data_hex: String = hex::encode(&i.data);
             let inst = SolanaInstruction {
@@ -818,22 +1544,183 @@ impl SolanaTransaction {
                 accounts: static_accounts,
                 instruction_data_hex,
                 address_table_lookups: atlu_addresses,
-                parsed_instruction: parsed_inst_option,
+                account_metas: all_transaction_addresses
+                    .iter()
+                    .map(SolanaAccountMeta::from)
+                    .collect(),
+                // an IDL supplied for a native program takes precedence over native decoding
+                parsed_instruction: parsed_inst_option.or(native_instruction),
                 idl_parse_error,
+                instruction_parse_error,
             };
             instructions.push(inst);
         }
-        Ok((instructions, transfers, spl_transfers))
+        Ok((instructions, transfers, spl_transfers, warnings))
+    }
+
+    // The program key and data of every instruction in the message, in order
+    fn instruction_programs_and_data(&self) -> Vec<(String, &[u8])> {
+        self.message
+            .instructions()
+            .iter()
+            .map(|i| {
+                let program_key = self
+                    .message
+                    .static_account_keys()
+                    .get(i.program_id_index as usize)
+                    .map(|k| k.to_string())
+                    .unwrap_or_default();
+                (program_key, i.data.as_slice())
+            })
+            .collect()
+    }
+
+    // Decode instructions of native programs and SPL interfaces that have no IDL, and collect SOL and SPL transfers from System, Token and Token 2022 program instructions
+    #[allow(clippy::too_many_lines)] // One match arm per natively decoded program
+    fn parse_native_instruction(
+        &self,
+        program_key: &str,
+        instruction_data: &[u8],
+        all_transaction_addresses: &[AccountAddress],
+        sibling_instructions: SiblingInstructions,
+        transfers: &mut Vec<SolTransfer>,
+        spl_transfers: &mut Vec<SplTransfer>,
+    ) -> Result<Option<SolanaParsedInstructionData>, InstructionParseError> {
+        // SPL interfaces can be implemented by any program, and Token 2022 checks for them before its own instructions
+        // The System and Token programs implement none of them
+        if program_key != SOL_SYSTEM_PROGRAM_KEY && program_key != TOKEN_PROGRAM_KEY {
+            if let Some(parsed_instruction) = spl_interfaces::parse_interface_instruction(
+                instruction_data,
+                all_transaction_addresses,
+            )? {
+                return Ok(Some(parsed_instruction));
+            }
+        }
+        match program_key {
+            SOL_SYSTEM_PROGRAM_KEY => {
+                let system_instruction = system::decode_system_instruction(instruction_data)?;
+                let parsed_instruction = system::parse_system_instruction(
+                    &system_instruction,
+                    instruction_data,
+                    all_transaction_addresses,
+                )?;
+                if let SystemInstruction::Transfer { lamports } = system_instruction {
+                    // NOTE: While Solana system program Transfer instructions require 2 addresses (from and to), the Solana runtime is permissive and allows for the instructions to include more than 2 addresses
+                    // In the case that more than 2 addresses are included, they are ignored by the Solana runtime, so the same is done here
+                    // A third address being included may happen due to SDK artifacts, or to store state for some unconventional flows
+                    // Notably, transactions generated by Jupiter for limit order v2 deposits include these -- https://dev.jup.ag/api-reference/trigger/v2/deposit-craft
+                    // parse_system_instruction has already checked that both addresses are present
+                    transfers.push(SolTransfer {
+                        amount: lamports.to_string(),
+                        from: all_transaction_addresses[0].to_string(), // the "from" address is the address at index 0 in the address parameter array in a Sol transfer
+                        to: all_transaction_addresses[1].to_string(), // the "to" address is the address at index 1 in the address parameter array in a Sol transfer
+                    });
+                }
+                return Ok(Some(parsed_instruction));
+            }
+            TOKEN_PROGRAM_KEY | TOKEN_2022_PROGRAM_KEY => {
+                let program = if program_key == TOKEN_PROGRAM_KEY {
+                    NativeProgram::Token
+                } else {
+                    NativeProgram::Token2022
+                };
+                let token_program_instruction =
+                    SplInstructionData::parse_spl_transfer_data(instruction_data).map_err(|e| {
+                        InstructionParseError::DataParseError {
+                            program_name: program.program_name().to_string(),
+                            error: e.to_string(),
+                        }
+                    })?;
+                let parsed_instruction = token::parse_token_instruction(
+                    program,
+                    instruction_data,
+                    all_transaction_addresses,
+                    sibling_instructions,
+                )?;
+                // the transfer is only recorded once the whole instruction has decoded, so an undecodable instruction reports no transfer
+                if let Some(spl_transfer) = self.parse_spl_instruction_data(
+                    token_program_instruction,
+                    all_transaction_addresses,
+                )? {
+                    spl_transfers.push(spl_transfer);
+                }
+                return Ok(parsed_instruction);
+            }
+            ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_KEY => {
+                return associated_token_account::parse_associated_token_account_instruction(
+                    instruction_data,
+                    all_transaction_addresses,
+                )
+                .map(Some);
+            }
+            STAKE_PROGRAM_KEY => {
+                let stake_instruction = stake::decode_stake_instruction(instruction_data)?;
+                return stake::parse_stake_instruction(
+                    &stake_instruction,
+                    instruction_data,
+                    all_transaction_addresses,
+                )
+                .map(Some);
+            }
+            VOTE_PROGRAM_KEY => {
+                let vote_instruction = vote::decode_vote_instruction(instruction_data)?;
+                return vote::parse_vote_instruction(
+                    &vote_instruction,
+                    instruction_data,
+                    all_transaction_addresses,
+                )
+                .map(Some);
+            }
+            ADDRESS_LOOKUP_TABLE_PROGRAM_KEY => {
+                let lookup_table_instruction =
+                    address_lookup_table::decode_address_lookup_table_instruction(
+                        instruction_data,
+                    )?;
+                return address_lookup_table::parse_address_lookup_table_instruction(
+                    &lookup_table_instruction,
+                    instruction_data,
+                    all_transaction_addresses,
+                )
+                .map(Some);
+            }
+            MEMO_PROGRAM_KEY | MEMO_V1_PROGRAM_KEY => {
+                let program = if program_key == MEMO_PROGRAM_KEY {
+                    NativeProgram::Memo
+                } else {
+                    NativeProgram::MemoV1
+                };
+                return memo::parse_memo_instruction(
+                    program,
+                    instruction_data,
+                    all_transaction_addresses,
+                )
+                .map(Some);
+            }
+            COMPUTE_BUDGET_PROGRAM_KEY => {
+                return compute_budget::parse_compute_budget_instruction(instruction_data)
+                    .map(Some);
+            }
+            ZK_ELGAMAL_PROOF_PROGRAM_KEY => {
+                return zk_elgamal_proof::parse_zk_elgamal_proof_instruction(
+                    instruction_data,
+                    all_transaction_addresses,
+                )
+                .map(Some);
+            }
+            _ => {}
+        }
+        Ok(None)
     }
 
     // Parse Instruction to Solana Token Program OR Solana Token Program 2022 and return something if it is an SPL transfer
     fn parse_spl_instruction_data(
         &self,
         token_instruction: SplInstructionData,
-        all_transaction_addresses: Vec<AccountAddress>,
-    ) -> Result<Option<SplTransfer>, Box<dyn std::error::Error>> {
+        all_transaction_addresses: &[AccountAddress],
+    ) -> Result<Option<SplTransfer>, InstructionParseError> {
         if let SplInstructionData::Transfer { amount } = token_instruction {
-            let signers = self.get_spl_multisig_signers_if_exist(&all_transaction_addresses, 3)?;
+            let signers =
+                self.get_spl_multisig_signers_if_exist(all_transaction_addresses, 3, "Transfer")?;
             let spl_transfer = SplTransfer {
                 amount: amount.to_string(),
                 from: all_transaction_addresses[0].to_string(), // the "from" address is the address at index 0 in the address parameter array in an SPL Transfer Instruction
@@ -846,7 +1733,11 @@ impl SolanaTransaction {
             };
             return Ok(Some(spl_transfer));
         } else if let SplInstructionData::TransferChecked { amount, decimals } = token_instruction {
-            let signers = self.get_spl_multisig_signers_if_exist(&all_transaction_addresses, 4)?;
+            let signers = self.get_spl_multisig_signers_if_exist(
+                all_transaction_addresses,
+                4,
+                "TransferChecked",
+            )?;
             let spl_transfer = SplTransfer {
                 amount: amount.to_string(),
                 from: all_transaction_addresses[0].to_string(), // the "from" address is the address at index 0 in the address parameter array in an SPL TransferChecked Instruction
@@ -864,7 +1755,11 @@ impl SolanaTransaction {
             fee,
         } = token_instruction
         {
-            let signers = self.get_spl_multisig_signers_if_exist(&all_transaction_addresses, 4)?;
+            let signers = self.get_spl_multisig_signers_if_exist(
+                all_transaction_addresses,
+                4,
+                "TransferCheckedWithFee",
+            )?;
             let spl_transfer = SplTransfer {
                 amount: amount.to_string(),
                 from: all_transaction_addresses[0].to_string(), // the "from" address is the address at index 0 in the address parameter array in a SPL TransferCheckedWithFee Instruction
@@ -884,11 +1779,14 @@ impl SolanaTransaction {
         &self,
         all_transaction_addresses: &[AccountAddress],
         num_accts_before_signer: usize,
-    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        instruction_name: &str,
+    ) -> Result<Vec<String>, InstructionParseError> {
         if all_transaction_addresses.len() < num_accts_before_signer {
-            return Err(
-                "invalid number of accounts provided for spl token transfer instruction".into(),
-            );
+            return Err(InstructionParseError::NotEnoughAccounts {
+                instruction_name: instruction_name.to_string(),
+                expected: num_accts_before_signer,
+                found: all_transaction_addresses.len(),
+            });
         }
         Ok(all_transaction_addresses[num_accts_before_signer..]
             .iter()
@@ -917,24 +1815,154 @@ impl SolanaTransaction {
                         .iter()
                         .map(|a| i32::from(a.to_owned()))
                         .collect(),
+                    table_status: self
+                        .address_lookup_table_statuses
+                        .get(&a.account_key)
+                        .copied(),
+                    // the runtime rejects a transaction whose lookups point past the end of a table
+                    out_of_range_indexes: self
+                        .address_lookup_tables
+                        .get(&a.account_key)
+                        .map(|addresses| {
+                            a.writable_indexes
+                                .iter()
+                                .chain(&a.readonly_indexes)
+                                .filter(|&&index| usize::from(index) >= addresses.len())
+                                .map(|&index| i32::from(index))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
                 })
                 .collect(),
             None => vec![],
         }
     }
 
-    fn signatures(&self) -> Result<Vec<String>, Box<dyn Error>> {
-        Ok(self
-            .signatures
+    fn signatures(&self) -> Vec<String> {
+        self.signatures
             .iter()
             .map(|sig| sig.iter().map(|b| format!("{:02x}", b)).collect::<String>())
+            .collect()
+    }
+
+    /*
+    Add Signature
+    - Context: a full transaction has one signature slot per required signer, and slot i belongs to static account key i
+    - This function places the signature at the slot of the given signer, replacing whatever was there (typically a placeholder)
+    - If only a message was parsed, the signature array is first filled with placeholders for every required signer
+    - Pubkeys that are not required signers of this message are rejected
+    - Returns the slot index the signature was placed at
+    */
+    pub fn add_signature(
+        &mut self,
+        signer: &str,
+        signature: &[u8],
+    ) -> Result<usize, SignatureInsertionError> {
+        let signer_pubkey = Pubkey::from_str(signer)
+            .map_err(|_| SignatureInsertionError::InvalidSigner(signer.to_string()))?;
+        if signature.len() != LEN_SOL_SIGNATURE_BYTES {
+            return Err(SignatureInsertionError::InvalidSignatureLength(
+                signature.len(),
+            ));
+        }
+
+        let required_signers = self.required_signer_keys()?;
+        let num_required_signatures = required_signers.len();
+        let signer_index = required_signers
+            .iter()
+            .position(|k| *k == signer_pubkey)
+            .ok_or_else(|| SignatureInsertionError::NotRequiredSigner(signer.to_string()))?;
+
+        if self.signatures.is_empty() {
+            self.signatures = vec![vec![0u8; LEN_SOL_SIGNATURE_BYTES]; num_required_signatures];
+        }
+        let signatures = self.signatures.len();
+        let slot = self.signatures.get_mut(signer_index).ok_or_else(|| {
+            SignatureInsertionError::MissingSignatureSlot {
+                signer: signer.to_string(),
+                signatures,
+            }
+        })?;
+        *slot = signature.to_vec();
+        Ok(signer_index)
+    }
+
+    /// Returns the pubkeys of required signers whose signature slot is still empty (missing or all zeros)
+    pub fn empty_signer_slots(&self) -> Result<Vec<String>, TransactionParseError> {
+        Ok(self
+            .required_signer_keys()?
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| {
+                self.signatures
+                    .get(*i)
+                    .is_none_or(|signature| signature.iter().all(|b| *b == 0))
+            })
+            .map(|(_, k)| k.to_string())
             .collect())
     }
 
-    pub fn transaction_metadata(&self) -> Result<SolanaMetadata, Box<dyn Error>> {
-        let (instructions, transfers, spl_transfers) = self.all_instructions_and_transfers()?;
-        let signatures = self.signatures()?;
+    // The required signers are the first num_required_signatures static account keys
+    // Messages are not sanitized when they are parsed, so a header may claim more signers than there are keys
+    fn required_signer_keys(&self) -> Result<&[Pubkey], TransactionParseError> {
+        let num_required_signatures = usize::from(self.message.header().num_required_signatures);
+        self.message
+            .static_account_keys()
+            .get(..num_required_signatures)
+            .ok_or(TransactionParseError::SanitizeFailed(
+                SanitizeError::IndexOutOfBounds,
+            ))
+    }
+
+    /*
+    Verify Signatures
+    - Context: signature slot i of a full transaction belongs to static account key i, for the first num_required_signatures account keys
+    - Each signature is verified as an Ed25519 signature over the exact serialized message bytes that were parsed
+    - A slot that is all zeros is a placeholder for a signature that has not been added yet
+    - A slot without a corresponding required signer can never be valid, so it is reported as invalid
+    - NOTE: This is only relevant for FULL TRANSACTIONS, when parsing only a message there are no signatures to verify
+    */
+    pub fn verify_signatures(&self) -> Vec<SolanaSignatureVerification> {
+        let num_required_signatures = usize::from(self.message.header().num_required_signatures);
+        let account_keys = self.message.static_account_keys();
+        self.signatures
+            .iter()
+            .enumerate()
+            .map(|(i, signature)| {
+                let signer = account_keys.get(i).filter(|_| i < num_required_signatures);
+                let status = if signature.iter().all(|b| *b == 0) {
+                    SignatureStatus::Placeholder
+                } else if signer.is_some_and(|signer| {
+                    verify_ed25519_signature(&signer.to_bytes(), &self.message_bytes, signature)
+                }) {
+                    SignatureStatus::Valid
+                } else {
+                    SignatureStatus::Invalid
+                };
+                SolanaSignatureVerification {
+                    signer: signer.map(ToString::to_string),
+                    signature: hex::encode(signature),
+                    status,
+                }
+            })
+            .collect()
+    }
+
+    pub fn transaction_metadata(&self) -> Result<SolanaMetadata, TransactionParseError> {
+        let (instructions, transfers, spl_transfers, mut warnings) = self
+            .all_instructions_and_transfers()
+            .map_err(TransactionParseError::InvalidInstructions)?;
+        let (fee_summary, fee_warnings) = compute_budget::fee_summary(
+            self.message.header().num_required_signatures,
+            &self.instruction_programs_and_data(),
+        );
+        warnings.extend(fee_warnings);
+        let (stake_summary, stake_warnings) = stake::stake_summary(&instructions);
+        warnings.extend(stake_warnings);
+        let memos = memo::memos(&instructions);
+        let signatures = self.signatures();
         Ok(SolanaMetadata {
+            signature_verifications: self.verify_signatures(),
             signatures,
             account_keys: self.all_account_key_strings(),
             address_table_lookups: self.address_table_lookups(),
@@ -943,6 +1971,10 @@ impl SolanaTransaction {
             instructions,
             transfers,
             spl_transfers,
+            memos,
+            fee_summary,
+            stake_summary,
+            warnings,
         })
     }
 }
@@ -950,22 +1982,21 @@ impl SolanaTransaction {
 /// Parses instruction data using the IDL for the given program.
 /// IDLs are embedded at compile time - no file system access required.
 ///
-/// Returns `Ok((parsed, None))` on success, or `Ok((None, Some(error)))` when
+/// Returns `(parsed, None)` on success, or `(None, Some(error))` when
 /// IDL parsing fails in a structured way the caller can inspect.
 fn parse_idl(
     program_key: &str,
     all_transaction_addresses: &[AccountAddress],
     inst: &CompiledInstruction,
     custom_idls: &HashMap<String, IdlRecord>,
-) -> Result<(Option<SolanaParsedInstructionData>, Option<IdlParseError>), Box<dyn std::error::Error>>
-{
+) -> (Option<SolanaParsedInstructionData>, Option<IdlParseError>) {
     if let Some(idl_record) = custom_idls.get(program_key) {
         // Resolve which IDL to use (built-in or custom) - uses embedded IDLs
         let (idl, idl_json_str, idl_source) =
             match idl_parser::resolve_idl_for_record(idl_record, program_key) {
                 Ok(v) => v,
                 Err(e) => {
-                    return Ok((None, Some(IdlParseError::IdlResolutionError(e.to_string()))));
+                    return (None, Some(IdlParseError::IdlResolutionError(e.to_string())));
                 }
             };
 
@@ -978,10 +2009,10 @@ fn parse_idl(
         ) {
             Ok(v) => v,
             Err(e) => {
-                return Ok((
+                return (
                     None,
                     Some(IdlParseError::DiscriminatorNotFound(e.to_string())),
-                ));
+                );
             }
         };
 
@@ -989,13 +2020,13 @@ fn parse_idl(
         let parsed_args = match idl_parser::parse_data_into_args(&inst.data, &instruction, &idl) {
             Ok(v) => v,
             Err(e) => {
-                return Ok((
+                return (
                     None,
                     Some(IdlParseError::DataParseError {
                         instruction_name: instruction.name,
                         error: e.to_string(),
                     }),
-                ));
+                );
             }
         };
 
@@ -1004,18 +2035,18 @@ fn parse_idl(
             match idl_parser::create_accounts_map(all_transaction_addresses, &instruction) {
                 Ok(v) => v,
                 Err(e) => {
-                    return Ok((
+                    return (
                         None,
                         Some(IdlParseError::AccountsMapError {
                             instruction_name: instruction.name,
                             error: e.to_string(),
                         }),
-                    ));
+                    );
                 }
             };
 
         if let Some(discriminator_bytes) = instruction.discriminator {
-            return Ok((
+            return (
                 Some(SolanaParsedInstructionData {
                     program_call_args: parsed_args,
                     discriminator: hex::encode(&discriminator_bytes),
@@ -1025,17 +2056,24 @@ fn parse_idl(
                     idl_hash,
                 }),
                 None,
-            ));
+            );
         }
         // We shouldn't get here because we found the instruction by the discriminator above
-        return Err("attempted to parse IDL into instruction without discriminator".into());
+        return (
+            None,
+            Some(IdlParseError::DiscriminatorNotFound(format!(
+                "instruction {} has no discriminator",
+                instruction.name
+            ))),
+        );
     }
-    Ok((None, None))
+    (None, None)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::error::Error;
 
     fn hex_to_vec(inp: &str) -> Result<Vec<u8>, Box<dyn Error>> {
         let unsigned_tx_bytes: Vec<u8> = (0..inp.len())
@@ -1051,40 +2089,61 @@ mod tests {
         // Test compact header with a single byte
         let test_input_1 = "05FFFFFFFFFF";
         let test_bytes_1 = hex_to_vec(test_input_1).unwrap();
-        let (len_1, rem_1) = read_compact_u16(&test_bytes_1).unwrap();
+        let (len_1, rem_1) = read_compact_u16(&test_bytes_1, "Test Array Header").unwrap();
         assert_eq!(len_1, 5);
         assert_eq!(rem_1, vec![0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
 
         // Test compact header with two bytes
         let test_input_2 = "8001FFFFFFFFFF";
         let test_bytes_2 = hex_to_vec(test_input_2).unwrap();
-        let (len_2, rem_2) = read_compact_u16(&test_bytes_2).unwrap();
+        let (len_2, rem_2) = read_compact_u16(&test_bytes_2, "Test Array Header").unwrap();
         assert_eq!(len_2, 128);
         assert_eq!(rem_2, vec![0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
 
         // Test compact header with 3 bytes (VALID)
         let test_input_3 = "808003FFFFFFFFFF";
         let test_bytes_3 = hex_to_vec(test_input_3).unwrap();
-        let (len_3, rem_3) = read_compact_u16(&test_bytes_3).unwrap();
+        let (len_3, rem_3) = read_compact_u16(&test_bytes_3, "Test Array Header").unwrap();
         assert_eq!(len_3, 49152);
         assert_eq!(rem_3, vec![0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
 
         // Test compact header with 3 bytes (INVALID, last byte has more than the 2 least significant digits populated since 0x04 == 0000 0100)
         let test_input_4 = "808004FFFFFFFFFF";
         let test_bytes_4 = hex_to_vec(test_input_4).unwrap();
-        let decoding_err_1 = read_compact_u16(&test_bytes_4).unwrap_err();
+        let decoding_err_1 = read_compact_u16(&test_bytes_4, "Test Array Header").unwrap_err();
         assert_eq!(
-            decoding_err_1.to_string(),
-            "error parsing unsigned transaction: third byte in compact u16 has more than it's 2 least significant bits set",
+            locate_error(decoding_err_1, test_bytes_4.len()),
+            TransactionParseError::InvalidCompactU16 {
+                section: "Test Array Header".to_string(),
+                offset: 0,
+            }
         );
 
+        // Test alias encodings (INVALID, 0x8c 0x00 is 12 and 0x80 0x00 and 0x80 0x80 0x00 are 0, which all have a shorter encoding)
+        for alias in ["8c00FF", "8000FF", "808000FF"] {
+            let alias_bytes = hex_to_vec(alias).unwrap();
+            let alias_err = read_compact_u16(&alias_bytes, "Test Array Header").unwrap_err();
+            assert_eq!(
+                locate_error(alias_err, alias_bytes.len()),
+                TransactionParseError::InvalidCompactU16 {
+                    section: "Test Array Header".to_string(),
+                    offset: 0,
+                }
+            );
+        }
+
         // Test compact header with 2 bytes (INVALID, 2 continuation bytes but then nothing after)
         let test_input_5 = "8080";
         let test_bytes_5 = hex_to_vec(test_input_5).unwrap();
-        let decoding_err_2 = read_compact_u16(&test_bytes_5).unwrap_err();
+        let decoding_err_2 = read_compact_u16(&test_bytes_5, "Test Array Header").unwrap_err();
         assert_eq!(
-            decoding_err_2.to_string(),
-            "error parsing unsigned transaction: unable to parse compact array header, not enough bytes",
+            locate_error(decoding_err_2, test_bytes_5.len()),
+            TransactionParseError::Truncated {
+                section: "Test Array Header".to_string(),
+                offset: 0,
+                needed: 3,
+                available: 2,
+            }
         );
     }
 
@@ -1099,11 +2158,12 @@ mod tests {
             empty_err.to_string(),
             "unsigned Solana transaction provided must be non-empty"
         );
+        // a 0x prefix is tolerated for hex input, so this decodes to zero bytes
         let unsigned_tx_too_short2 = "0x";
         let empty_err = parse_solana_transaction(unsigned_tx_too_short2, false, None).unwrap_err();
         assert_eq!(
             empty_err.to_string(),
-            "unsigned Solana transaction provided is invalid hex"
+            "unsigned Solana transaction provided must be non-empty"
         );
     }
 
@@ -1,6 +1,14 @@
+use crate::solana::parser::{
+    ADDRESS_LOOKUP_TABLE_PROGRAM_KEY, ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_KEY,
+    COMPUTE_BUDGET_PROGRAM_KEY, MEMO_PROGRAM_KEY, MEMO_V1_PROGRAM_KEY, SOL_SYSTEM_PROGRAM_KEY,
+    STAKE_PROGRAM_KEY, TOKEN_2022_PROGRAM_KEY, TOKEN_PROGRAM_KEY, VOTE_PROGRAM_KEY,
+    ZK_ELGAMAL_PROOF_PROGRAM_KEY,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::{collections::HashMap, fmt};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::sanitize::SanitizeError;
+use std::{collections::HashMap, error::Error, fmt};
 
 /// ProgramType represents the built-in IDL types supported by the library
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -141,23 +149,294 @@ impl ProgramType {
     }
 }
 
-/// IdlSource indicates whether a built-in or custom IDL was used for parsing
+/// NativeProgram represents the programs whose instructions are decoded natively, without an IDL
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NativeProgram {
+    System,
+    Token,
+    Token2022,
+    ZkElGamalProof,
+    AssociatedTokenAccount,
+    ComputeBudget,
+    Stake,
+    Vote,
+    AddressLookupTable,
+    Memo,
+    MemoV1,
+}
+
+impl NativeProgram {
+    /// Returns the program ID associated with this NativeProgram
+    pub fn program_id(&self) -> &str {
+        match self {
+            NativeProgram::System => SOL_SYSTEM_PROGRAM_KEY,
+            NativeProgram::Token => TOKEN_PROGRAM_KEY,
+            NativeProgram::Token2022 => TOKEN_2022_PROGRAM_KEY,
+            NativeProgram::ZkElGamalProof => ZK_ELGAMAL_PROOF_PROGRAM_KEY,
+            NativeProgram::AssociatedTokenAccount => ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_KEY,
+            NativeProgram::ComputeBudget => COMPUTE_BUDGET_PROGRAM_KEY,
+            NativeProgram::Stake => STAKE_PROGRAM_KEY,
+            NativeProgram::Vote => VOTE_PROGRAM_KEY,
+            NativeProgram::AddressLookupTable => ADDRESS_LOOKUP_TABLE_PROGRAM_KEY,
+            NativeProgram::Memo => MEMO_PROGRAM_KEY,
+            NativeProgram::MemoV1 => MEMO_V1_PROGRAM_KEY,
+        }
+    }
+
+    /// Returns the program name for this NativeProgram
+    pub fn program_name(&self) -> &'static str {
+        match self {
+            NativeProgram::System => "System Program",
+            NativeProgram::Token => "Token Program",
+            NativeProgram::Token2022 => "Token 2022 Program",
+            NativeProgram::ZkElGamalProof => "ZK ElGamal Proof Program",
+            NativeProgram::AssociatedTokenAccount => "Associated Token Account Program",
+            NativeProgram::ComputeBudget => "Compute Budget Program",
+            NativeProgram::Stake => "Stake Program",
+            NativeProgram::Vote => "Vote Program",
+            NativeProgram::AddressLookupTable => "Address Lookup Table Program",
+            NativeProgram::Memo => "Memo Program",
+            NativeProgram::MemoV1 => "Memo v1 Program",
+        }
+    }
+
+    /// Looks up a NativeProgram by program_id
+    #[allow(dead_code)] // Public API
+    pub fn from_program_id(program_id: &str) -> Option<NativeProgram> {
+        match program_id {
+            SOL_SYSTEM_PROGRAM_KEY => Some(NativeProgram::System),
+            TOKEN_PROGRAM_KEY => Some(NativeProgram::Token),
+            TOKEN_2022_PROGRAM_KEY => Some(NativeProgram::Token2022),
+            ZK_ELGAMAL_PROOF_PROGRAM_KEY => Some(NativeProgram::ZkElGamalProof),
+            ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_KEY => Some(NativeProgram::AssociatedTokenAccount),
+            COMPUTE_BUDGET_PROGRAM_KEY => Some(NativeProgram::ComputeBudget),
+            STAKE_PROGRAM_KEY => Some(NativeProgram::Stake),
+            VOTE_PROGRAM_KEY => Some(NativeProgram::Vote),
+            ADDRESS_LOOKUP_TABLE_PROGRAM_KEY => Some(NativeProgram::AddressLookupTable),
+            MEMO_PROGRAM_KEY => Some(NativeProgram::Memo),
+            MEMO_V1_PROGRAM_KEY => Some(NativeProgram::MemoV1),
+            _ => None,
+        }
+    }
+}
+
+/// SplInterface represents the SPL instruction interfaces that any program can implement
+/// Interface instructions are identified by an 8 byte discriminator derived from a namespaced name, so they are decoded whatever program they are sent to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SplInterface {
+    TokenMetadata,
+    TokenGroup,
+    TransferHook,
+}
+
+impl SplInterface {
+    /// Returns the interface name for this SplInterface
+    pub fn interface_name(&self) -> &'static str {
+        match self {
+            SplInterface::TokenMetadata => "Token Metadata Interface",
+            SplInterface::TokenGroup => "Token Group Interface",
+            SplInterface::TransferHook => "Transfer Hook Interface",
+        }
+    }
+}
+
+/// IdlSource indicates whether a built-in or custom IDL was used for parsing,
+/// or whether the instruction was decoded natively
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum IdlSource {
     BuiltIn(ProgramType),
     Custom,
+    /// Decoded by the parser itself, for native programs that have no IDL
+    Native(NativeProgram),
+    /// Decoded by the parser itself, for instructions of an SPL interface
+    Interface(SplInterface),
+}
+
+/// TransactionEncoding describes how the serialized transaction passed to the parser is encoded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionEncoding {
+    /// Hex string, optionally prefixed with `0x`
+    #[default]
+    Hex,
+    /// Base58 string, as produced by `solana --sign-only` and older RPC encodings
+    Base58,
+    /// Standard base64 string, as produced by web3.js `serialize()` and RPC responses
+    Base64,
+    /// Detect the encoding from the input -- hex is tried first, then base58, then base64
+    Auto,
+}
+
+impl std::str::FromStr for TransactionEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "hex" => Ok(TransactionEncoding::Hex),
+            "base58" | "bs58" => Ok(TransactionEncoding::Base58),
+            "base64" => Ok(TransactionEncoding::Base64),
+            "auto" => Ok(TransactionEncoding::Auto),
+            _ => Err(format!("unknown transaction encoding: {s}")),
+        }
+    }
+}
+
+/// PayloadKind indicates how the parsed bytes were interpreted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PayloadKind {
+    /// Only the message -- the payload that gets signed by signers
+    Message,
+    /// A full transaction -- the compact array of signatures followed by the message
+    Transaction,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct SolanaMetadata {
     pub signatures: Vec<String>,
+    /// Verification result for each signature slot, in the same order as `signatures`
+    pub signature_verifications: Vec<SolanaSignatureVerification>,
     pub account_keys: Vec<String>,
     pub program_keys: Vec<String>,
     pub instructions: Vec<SolanaInstruction>,
     pub transfers: Vec<SolTransfer>,
     pub spl_transfers: Vec<SplTransfer>,
+    /// The text of every Memo program instruction, in instruction order
+    pub memos: Vec<SolanaMemo>,
     pub recent_blockhash: String,
     pub address_table_lookups: Vec<SolanaAddressTableLookup>,
+    /// The most the transaction can be charged, from its signatures and Compute Budget program instructions
+    pub fee_summary: SolanaFeeSummary,
+    /// What the Stake program instructions of the transaction do to stake accounts
+    pub stake_summary: SolanaStakeSummary,
+    /// Suspicious but valid details of the transaction, which callers should surface before signing
+    pub warnings: Vec<TransactionWarning>,
+}
+
+/// SolanaFeeSummary breaks down the maximum fee of a transaction, in lamports unless stated otherwise.
+/// The priority fee is charged on the requested compute unit limit, not on the compute units actually used.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolanaFeeSummary {
+    /// The number of signatures the message requires, each charged the base fee
+    pub signature_count: u8,
+    pub base_fee_lamports: u64,
+    /// The requested compute unit limit, or the runtime default when none is requested
+    pub compute_unit_limit: u32,
+    /// The requested compute unit price, zero when none is requested
+    pub compute_unit_price_micro_lamports: u64,
+    pub priority_fee_lamports: u64,
+    /// The base fee plus the priority fee
+    pub total_max_fee_lamports: u64,
+}
+
+/// SolanaStakeSummary lists the delegations, authority changes and withdrawals of stake accounts in a transaction
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SolanaStakeSummary {
+    pub delegations: Vec<StakeDelegation>,
+    pub authority_changes: Vec<StakeAuthorityChange>,
+    pub withdrawals: Vec<StakeWithdrawal>,
+}
+
+/// A DelegateStake instruction, which delegates the stake of a stake account to a validator's vote account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StakeDelegation {
+    pub instruction_index: usize,
+    pub stake_account: String,
+    pub vote_account: String,
+    pub stake_authority: String,
+}
+
+/// An Authorize instruction, or one of its checked and seeded variants, which hands a stake account authority to a new key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StakeAuthorityChange {
+    pub instruction_index: usize,
+    pub stake_account: String,
+    /// Staker or Withdrawer
+    pub authority_type: String,
+    pub new_authority: String,
+}
+
+/// A Withdraw instruction, which moves lamports out of a stake account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StakeWithdrawal {
+    pub instruction_index: usize,
+    pub stake_account: String,
+    pub recipient: String,
+    pub lamports: u64,
+}
+
+/// TransactionWarning describes something about a parsed transaction that is worth a closer look before signing.
+/// Unlike a parse error, the transaction is otherwise parsed as usual.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionWarning {
+    /// An Associated Token Account program instruction names an associated token account
+    /// that is not the address derived from its wallet, mint and token program.
+    AssociatedTokenAccountMismatch {
+        instruction_index: usize,
+        account_name: String,
+        account: String,
+        expected: String,
+    },
+    /// A Compute Budget program instruction repeats an earlier one of the same kind,
+    /// which makes the runtime reject the whole transaction.
+    DuplicateComputeBudgetInstruction {
+        instruction_index: usize,
+        instruction_name: String,
+    },
+    /// A Stake program instruction gives the staker or withdrawer authority of a stake account to a new key,
+    /// who then controls the stake account.
+    StakeAuthorize {
+        instruction_index: usize,
+        stake_account: String,
+        authority_type: String,
+        new_authority: String,
+    },
+    /// A Stake program instruction withdraws lamports from a stake account.
+    StakeWithdraw {
+        instruction_index: usize,
+        stake_account: String,
+        recipient: String,
+        lamports: u64,
+    },
+}
+
+impl std::fmt::Display for TransactionWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AssociatedTokenAccountMismatch {
+                instruction_index,
+                account_name,
+                account,
+                expected,
+            } => write!(
+                f,
+                "instruction {instruction_index} uses {account} as {account_name}, but the derived associated token account is {expected}"
+            ),
+            Self::DuplicateComputeBudgetInstruction {
+                instruction_index,
+                instruction_name,
+            } => write!(
+                f,
+                "instruction {instruction_index} repeats {instruction_name}, so the transaction will be rejected"
+            ),
+            Self::StakeAuthorize {
+                instruction_index,
+                stake_account,
+                authority_type,
+                new_authority,
+            } => write!(
+                f,
+                "instruction {instruction_index} makes {new_authority} the {authority_type} authority of stake account {stake_account}"
+            ),
+            Self::StakeWithdraw {
+                instruction_index,
+                stake_account,
+                recipient,
+                lamports,
+            } => write!(
+                f,
+                "instruction {instruction_index} withdraws {lamports} lamports from stake account {stake_account} to {recipient}"
+            ),
+        }
+    }
 }
 
 /// Describes why IDL parsing failed for an instruction.
@@ -200,16 +479,336 @@ impl std::fmt::Display for IdlParseError {
 
 impl std::error::Error for IdlParseError {}
 
+/// Structured error for an instruction to a natively decoded program or SPL interface that could not be decoded.
+/// The rest of the transaction is still parsed when this occurs.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InstructionParseError {
+    /// The instruction data does not match any instruction layout of the program.
+    DataParseError { program_name: String, error: String },
+    /// The instruction references fewer accounts than the decoded instruction requires.
+    NotEnoughAccounts {
+        instruction_name: String,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl std::fmt::Display for InstructionParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DataParseError {
+                program_name,
+                error,
+            } => write!(f, "data parse error for {program_name}: {error}"),
+            Self::NotEnoughAccounts {
+                instruction_name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{instruction_name} instruction requires at least {expected} accounts, found {found}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InstructionParseError {}
+
+/// Structured error for failures to decode or parse a transaction or message.
+/// Offsets are byte offsets into the decoded input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionParseError {
+    /// The input was empty.
+    Empty,
+    /// The input is not valid in the given encoding.
+    InvalidEncoding(TransactionEncoding),
+    /// A section of the wire format needs more bytes than are left in the input.
+    Truncated {
+        section: String,
+        offset: usize,
+        needed: usize,
+        available: usize,
+    },
+    /// A compact-u16 length header has bits set past the 16th bit, or is not the shortest encoding of its value.
+    InvalidCompactU16 { section: String, offset: usize },
+    /// Bytes are left over after the last section of the message.
+    ExtraneousBytes { offset: usize, count: usize },
+    /// The message has a version prefix for a version other than legacy or v0.
+    UnsupportedVersion(u8),
+    /// The message failed the Solana SDK sanitization check.
+    SanitizeFailed(SanitizeError),
+    /// A full transaction does not carry one signature per required signer.
+    SignatureCountMismatch { found: usize, required: usize },
+    /// The payload is valid both as a message and as a full transaction.
+    AmbiguousPayload,
+    /// The payload is valid neither as a message nor as a full transaction.
+    UnrecognizedPayload {
+        as_transaction: Box<TransactionParseError>,
+        as_message: Box<TransactionParseError>,
+    },
+    /// A custom IDL could not be loaded.
+    InvalidCustomIdl(String),
+    /// Caller-supplied address lookup table contents could not be decoded.
+    InvalidAddressLookupTable { table: String, error: String },
+    /// The instructions of a well-formed message could not be processed.
+    InvalidInstructions(InvalidInstructionsError),
+}
+
+impl std::fmt::Display for TransactionParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "unsigned Solana transaction provided must be non-empty"),
+            Self::InvalidEncoding(encoding) => {
+                let name = match encoding {
+                    TransactionEncoding::Hex => "hex",
+                    TransactionEncoding::Base58 => "base58",
+                    TransactionEncoding::Base64 => "base64",
+                    TransactionEncoding::Auto => "in any supported encoding",
+                };
+                write!(f, "unsigned Solana transaction provided is invalid {name}")
+            }
+            Self::Truncated {
+                section,
+                offset,
+                needed,
+                available,
+            } => write!(
+                f,
+                "Unsigned transaction provided is incorrectly formatted, error while parsing {section}: needed {needed} bytes at offset {offset} but only {available} remain"
+            ),
+            Self::InvalidCompactU16 { section, offset } => write!(
+                f,
+                "error parsing unsigned transaction: invalid compact-u16 in {section} at offset {offset}, it overflows a u16 or is not its shortest encoding"
+            ),
+            Self::ExtraneousBytes { offset, count } => write!(
+                f,
+                "Transaction formatted incorrectly contains {count} extraneous bytes at the end, starting at offset {offset}"
+            ),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported Solana message version: {version}")
+            }
+            Self::SanitizeFailed(e) => {
+                write!(f, "Solana transaction message failed sanitization check: {e}")
+            }
+            Self::SignatureCountMismatch { found, required } => write!(
+                f,
+                "transaction contains {found} signatures but its message requires {required}"
+            ),
+            Self::AmbiguousPayload => write!(
+                f,
+                "Solana payload is ambiguous: it is valid both as a message and as a full transaction. Parse using the --message or --transaction flag"
+            ),
+            Self::UnrecognizedPayload {
+                as_transaction,
+                as_message,
+            } => write!(
+                f,
+                "Solana payload is neither a valid message nor a valid full transaction. As a transaction: {as_transaction}. As a message: {as_message}"
+            ),
+            Self::InvalidCustomIdl(e) => write!(f, "invalid custom IDL: {e}"),
+            Self::InvalidAddressLookupTable { table, error } => {
+                write!(f, "invalid address lookup table {table}: {error}")
+            }
+            Self::InvalidInstructions(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for TransactionParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::SanitizeFailed(e) => Some(e),
+            Self::InvalidInstructions(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Structured error for instructions that reference accounts the message does not have.
+/// Sanitized messages never do, so this only occurs for messages that were not sanitized when parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidInstructionsError {
+    /// The message failed the Solana SDK sanitization check while its instructions were parsed.
+    SanitizeFailed(SanitizeError),
+    /// An instruction account index points past the static account keys and address table lookups.
+    AccountIndexOutOfBounds {
+        instruction_index: usize,
+        account_index: u8,
+    },
+    /// The program of an instruction is not one of the static account keys.
+    ProgramIdNotStatic {
+        instruction_index: usize,
+        program_id_index: u8,
+    },
+}
+
+impl std::fmt::Display for InvalidInstructionsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SanitizeFailed(e) => write!(
+                f,
+                "Solana transaction message failed sanitization check while parsing instructions: {e}"
+            ),
+            Self::AccountIndexOutOfBounds {
+                instruction_index,
+                account_index,
+            } => write!(
+                f,
+                "instruction {instruction_index} account index {account_index} is out of bounds of the account keys and address table lookups"
+            ),
+            Self::ProgramIdNotStatic {
+                instruction_index,
+                program_id_index,
+            } => write!(
+                f,
+                "Solana Instruction program index must be within static account keys, instruction {instruction_index} has program index {program_id_index}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InvalidInstructionsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::SanitizeFailed(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Structured error for failures to re-serialize or encode a parsed transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionSerializeError {
+    /// A compact array has more entries than its compact-u16 length header can hold.
+    CompactArrayTooLong { section: String, len: usize },
+    /// Output must be hex, base58 or base64, since `Auto` only applies to input.
+    AutoOutputEncoding,
+}
+
+impl std::fmt::Display for TransactionSerializeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CompactArrayTooLong { section, len } => write!(
+                f,
+                "{section} has {len} entries, which does not fit in a compact-u16 header"
+            ),
+            Self::AutoOutputEncoding => write!(
+                f,
+                "an explicit output encoding (hex, base58 or base64) is required"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TransactionSerializeError {}
+
+/// Structured error for failures to insert a signature into a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureInsertionError {
+    /// The transaction could not be parsed.
+    Parse(TransactionParseError),
+    /// The transaction could not be re-serialized with the signature in place.
+    Serialize(TransactionSerializeError),
+    /// The signer is not a valid base58 pubkey.
+    InvalidSigner(String),
+    /// The signature is not 64 bytes long.
+    InvalidSignatureLength(usize),
+    /// The signer is not one of the message's required signers.
+    NotRequiredSigner(String),
+    /// A full transaction has fewer signature slots than the signer's index.
+    MissingSignatureSlot { signer: String, signatures: usize },
+}
+
+impl std::fmt::Display for SignatureInsertionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse(e) => write!(f, "{e}"),
+            Self::Serialize(e) => write!(f, "{e}"),
+            Self::InvalidSigner(signer) => write!(f, "invalid signer pubkey: {signer}"),
+            Self::InvalidSignatureLength(len) => write!(
+                f,
+                "signature must be 64 bytes long, got {len} bytes"
+            ),
+            Self::NotRequiredSigner(signer) => {
+                write!(f, "{signer} is not a required signer of this transaction")
+            }
+            Self::MissingSignatureSlot { signer, signatures } => write!(
+                f,
+                "transaction has no signature slot for required signer {signer}, it only has {signatures} signatures"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SignatureInsertionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Parse(e) => Some(e),
+            Self::Serialize(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<TransactionParseError> for SignatureInsertionError {
+    fn from(e: TransactionParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
+impl From<TransactionSerializeError> for SignatureInsertionError {
+    fn from(e: TransactionSerializeError) -> Self {
+        Self::Serialize(e)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct SolanaInstruction {
     pub program_key: String,
     pub accounts: Vec<SolanaAccount>,
     pub instruction_data_hex: String,
     pub address_table_lookups: Vec<SolanaSingleAddressTableLookup>,
+    /// Every account of the instruction, static and lookup alike, in the order of the compiled instruction's account indexes
+    pub account_metas: Vec<SolanaAccountMeta>,
     pub parsed_instruction: Option<SolanaParsedInstructionData>,
     /// If IDL parsing failed, this contains the structured error.
     /// `None` means either parsing succeeded or no IDL was available for this program.
     pub idl_parse_error: Option<IdlParseError>,
+    /// If an instruction to a natively decoded program could not be decoded, this contains the structured error.
+    /// `None` means either decoding succeeded or the program is not decoded natively.
+    pub instruction_parse_error: Option<InstructionParseError>,
+}
+
+/// SignatureStatus is the result of verifying a single signature slot of a full transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignatureStatus {
+    /// The signature is a valid Ed25519 signature of the message by the slot's required signer
+    Valid,
+    /// The signature does not verify against the message and the slot's required signer
+    Invalid,
+    /// The slot is all zeros -- the signer has not signed yet
+    Placeholder,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolanaSignatureVerification {
+    /// The required signer for this slot, `None` if the slot has no corresponding required signer
+    pub signer: Option<String>,
+    pub signature: String,
+    pub status: SignatureStatus,
+}
+
+/// Result of inserting a signature into a transaction
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolanaSignatureInsertion {
+    /// Index of the signature slot the signature was placed at
+    pub signer_index: usize,
+    /// Verification result of the inserted signature against the message
+    pub status: SignatureStatus,
+    /// The re-serialized transaction including the inserted signature
+    pub transaction: String,
+    /// Pubkeys of the required signers whose slots are still empty
+    pub empty_signer_slots: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -224,6 +823,53 @@ pub struct SolanaSingleAddressTableLookup {
     pub address_table_key: String,
     pub index: i32,
     pub writable: bool,
+    /// The address stored at `index` of the table, when the table's contents were supplied by the caller
+    pub resolved_address: Option<String>,
+}
+
+/// Where an instruction account is loaded from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolanaAccountSource {
+    /// The account key is included in the message's static account keys
+    Static,
+    /// The account is loaded from `index` of the address lookup table `address_table_key`
+    AddressTableLookup {
+        address_table_key: String,
+        index: i32,
+    },
+}
+
+/// A single account of an instruction, at its position in the compiled instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolanaAccountMeta {
+    pub source: SolanaAccountSource,
+    /// The account's pubkey, `None` for a lookup into a table whose contents were not supplied
+    pub account_key: Option<String>,
+    pub signer: bool,
+    pub writable: bool,
+}
+
+impl From<&AccountAddress> for SolanaAccountMeta {
+    fn from(address: &AccountAddress) -> Self {
+        match address {
+            AccountAddress::Static(account) => SolanaAccountMeta {
+                source: SolanaAccountSource::Static,
+                account_key: Some(account.account_key.clone()),
+                signer: account.signer,
+                writable: account.writable,
+            },
+            // accounts loaded from lookup tables can never be signers
+            AccountAddress::AddressTableLookUp(lookup) => SolanaAccountMeta {
+                source: SolanaAccountSource::AddressTableLookup {
+                    address_table_key: lookup.address_table_key.clone(),
+                    index: lookup.index,
+                },
+                account_key: lookup.resolved_address.clone(),
+                signer: false,
+                writable: lookup.writable,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -233,6 +879,20 @@ pub struct SolTransfer {
     pub amount: String,
 }
 
+/// SolanaMemo is the text of a Memo v1 or v2 instruction, such as the deposit reference an exchange requires
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolanaMemo {
+    pub instruction_index: usize,
+    /// The Memo program the instruction was sent to, v1 or v2
+    pub program_id: String,
+    /// The memo text, `None` when the instruction data is not valid UTF-8
+    pub memo: Option<String>,
+    /// The raw instruction data, hex encoded
+    pub memo_hex: String,
+    /// The accounts passed to the instruction that sign the transaction, in instruction order
+    pub signers: Vec<String>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct SplTransfer {
     pub from: String,
@@ -253,7 +913,7 @@ pub struct SolanaParsedInstructionData {
     pub program_call_args: serde_json::Map<std::string::String, Value>,
     /// Indicates whether a built-in or custom IDL was used
     pub idl_source: IdlSource,
-    /// SHA256 hash of the compressed (whitespace removed) IDL JSON string
+    /// SHA256 hash of the compressed (whitespace removed) IDL JSON string, empty for natively decoded instructions
     pub idl_hash: String,
 }
 
@@ -262,12 +922,215 @@ pub struct SolanaAddressTableLookup {
     pub address_table_key: String,
     pub writable_indexes: Vec<i32>,
     pub readonly_indexes: Vec<i32>,
+    /// Status of the lookup table, known when its raw account data was supplied
+    pub table_status: Option<AddressLookupTableStatus>,
+    /// Indexes of this lookup that point past the end of the supplied table, which the runtime rejects
+    /// Empty when the table was not supplied
+    pub out_of_range_indexes: Vec<i32>,
+}
+
+// Length of the metadata header at the start of an address lookup table account
+pub const LEN_LOOKUP_TABLE_META_BYTES: usize = 56;
+// Account type discriminator of an initialized address lookup table
+const LOOKUP_TABLE_DISCRIMINATOR: u32 = 1;
+// Deactivation slot of a table that has not been deactivated
+const LOOKUP_TABLE_ACTIVE_SLOT: u64 = u64::MAX;
+
+/// Lifecycle status of an address lookup table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressLookupTableStatus {
+    /// The table has an authority and can still be extended
+    Active,
+    /// The table has no authority, so its addresses can never change
+    Frozen,
+    /// The table was deactivated at `deactivation_slot` and is about to become unusable
+    Deactivating { deactivation_slot: u64 },
+}
+
+/// An address lookup table account decoded from its raw account data
+///
+/// The account is a 56 byte metadata header followed by the 32 byte addresses stored in the table:
+/// - bytes 0..4: account type discriminator (1 for an initialized lookup table)
+/// - bytes 4..12: deactivation slot (`u64::MAX` while the table is active)
+/// - bytes 12..20: slot in which the table was last extended
+/// - byte 20: index of the first address added in the last extended slot
+/// - bytes 21..54: optional authority (one tag byte followed by the 32 byte pubkey)
+/// - bytes 54..56: padding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedAddressLookupTable {
+    pub deactivation_slot: u64,
+    pub last_extended_slot: u64,
+    pub last_extended_slot_start_index: u8,
+    pub authority: Option<String>,
+    pub addresses: Vec<String>,
+}
+
+#[allow(dead_code)] // Public API
+impl DecodedAddressLookupTable {
+    /// Decode the raw data of an address lookup table account
+    pub fn decode(data: &[u8]) -> Result<Self, AddressLookupTableDecodeError> {
+        if data.len() < LEN_LOOKUP_TABLE_META_BYTES {
+            return Err(AddressLookupTableDecodeError::Truncated(data.len()));
+        }
+        let (meta, address_bytes) = data.split_at(LEN_LOOKUP_TABLE_META_BYTES);
+        let read_u64 = |offset: usize| {
+            u64::from_le_bytes(meta[offset..offset + 8].try_into().expect("8 byte slice"))
+        };
+
+        let discriminator = u32::from_le_bytes(meta[0..4].try_into().expect("4 byte slice"));
+        if discriminator != LOOKUP_TABLE_DISCRIMINATOR {
+            return Err(AddressLookupTableDecodeError::InvalidDiscriminator(
+                discriminator,
+            ));
+        }
+        let authority = match meta[21] {
+            0 => None,
+            1 => Some(
+                Pubkey::try_from(&meta[22..54])
+                    .expect("32 byte slice")
+                    .to_string(),
+            ),
+            tag => return Err(AddressLookupTableDecodeError::InvalidAuthorityTag(tag)),
+        };
+        if address_bytes.len() % 32 != 0 {
+            return Err(AddressLookupTableDecodeError::MisalignedAddresses(
+                address_bytes.len(),
+            ));
+        }
+        let addresses = address_bytes
+            .chunks_exact(32)
+            .map(|key| Pubkey::try_from(key).expect("32 byte chunk").to_string())
+            .collect();
+
+        Ok(DecodedAddressLookupTable {
+            deactivation_slot: read_u64(4),
+            last_extended_slot: read_u64(12),
+            last_extended_slot_start_index: meta[20],
+            authority,
+            addresses,
+        })
+    }
+
+    /// A frozen table has no authority, so its addresses can never be changed
+    pub fn is_frozen(&self) -> bool {
+        self.authority.is_none()
+    }
+
+    /// A deactivating table can no longer be extended and will be unusable once the deactivation cooldown ends
+    pub fn is_deactivating(&self) -> bool {
+        self.deactivation_slot != LOOKUP_TABLE_ACTIVE_SLOT
+    }
+
+    pub fn status(&self) -> AddressLookupTableStatus {
+        // deactivation requires the authority, so a table can only be frozen while active
+        if self.is_deactivating() {
+            AddressLookupTableStatus::Deactivating {
+                deactivation_slot: self.deactivation_slot,
+            }
+        } else if self.is_frozen() {
+            AddressLookupTableStatus::Frozen
+        } else {
+            AddressLookupTableStatus::Active
+        }
+    }
+}
+
+/// Structured error for lookup table account data that could not be decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressLookupTableDecodeError {
+    /// The data is shorter than the metadata header, carrying its length.
+    Truncated(usize),
+    /// The account is not an initialized lookup table, carrying its discriminator.
+    InvalidDiscriminator(u32),
+    /// The authority option tag is neither 0 nor 1.
+    InvalidAuthorityTag(u8),
+    /// The address data is not a multiple of 32 bytes, carrying its length.
+    MisalignedAddresses(usize),
+}
+
+impl std::fmt::Display for AddressLookupTableDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Truncated(len) => write!(
+                f,
+                "lookup table account data is {len} bytes, shorter than the {LEN_LOOKUP_TABLE_META_BYTES} byte metadata header"
+            ),
+            Self::InvalidDiscriminator(discriminator) => write!(
+                f,
+                "account is not an initialized lookup table (discriminator {discriminator})"
+            ),
+            Self::InvalidAuthorityTag(tag) => {
+                write!(f, "invalid lookup table authority option tag {tag}")
+            }
+            Self::MisalignedAddresses(len) => write!(
+                f,
+                "lookup table address data is {len} bytes, which is not a multiple of 32"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AddressLookupTableDecodeError {}
+
+/// The contents of an on-chain address lookup table, supplied by the caller so that
+/// address table lookups can be resolved to real addresses without network access.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(dead_code)] // Public API - variants used by library consumers
+pub enum AddressLookupTableContents {
+    /// The table's addresses as base58 pubkeys, in table order
+    Addresses(Vec<String>),
+    /// The raw data of the lookup table account (metadata header followed by the addresses)
+    AccountData(Vec<u8>),
+}
+
+#[allow(dead_code)] // Public API
+impl AddressLookupTableContents {
+    /// Create table contents from a list of base58 addresses
+    pub fn from_addresses(addresses: Vec<String>) -> Self {
+        AddressLookupTableContents::Addresses(addresses)
+    }
+
+    /// Create table contents from the raw lookup table account data
+    pub fn from_account_data(data: Vec<u8>) -> Self {
+        AddressLookupTableContents::AccountData(data)
+    }
+}
+
+/// AddressLookupTableDump is the JSON printed by `solana address-lookup-table get <table_pubkey> --output json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressLookupTableDump {
+    pub lookup_table_address: String,
+    pub authority: Option<String>,
+    pub deactivation_slot: u64,
+    pub last_extended_slot: u64,
+    pub addresses: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct SolanaParsedTransactionPayload {
     pub transaction_metadata: Option<SolanaMetadata>,
+    /// Hex encoding of the parsed bytes, regardless of the encoding the input was provided in
     pub unsigned_payload: String,
+    /// Whether the payload was parsed as a message or as a full transaction
+    pub payload_kind: PayloadKind,
+    /// Hex encoding of the message bytes that signers sign, i.e. the payload without the signature array
+    pub signable_payload: String,
+    /// Solana message hash (blake3) of `signable_payload`, encoded as base58
+    pub message_hash: String,
+}
+
+/// A single section of the serialized transaction wire format, located by its byte span
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolanaLayoutSection {
+    /// Human readable name of the section (e.g. "Account Key 2" or "Instruction 0 Data")
+    pub section: String,
+    /// Offset of the first byte of the section within the parsed bytes
+    pub offset: usize,
+    /// Number of bytes in the section
+    pub length: usize,
+    /// Raw bytes of the section as hex
+    pub hex: String,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -296,7 +1159,10 @@ impl fmt::Display for AccountAddress {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             AccountAddress::Static(account) => write!(f, "{}", account.account_key),
-            AccountAddress::AddressTableLookUp(_) => write!(f, "ADDRESS_TABLE_LOOKUP"),
+            AccountAddress::AddressTableLookUp(lookup) => match &lookup.resolved_address {
+                Some(address) => write!(f, "{address}"),
+                None => write!(f, "ADDRESS_TABLE_LOOKUP"),
+            },
         }
     }
 }
@@ -3,10 +3,19 @@ use std::{collections::HashMap, vec};
 
 use super::*;
 use crate::solana::idl_parser;
-use crate::solana::parser::{SolanaTransaction, TOKEN_2022_PROGRAM_KEY, TOKEN_PROGRAM_KEY};
+use crate::solana::parser::{
+    parse_transaction, SolanaTransaction, ADDRESS_LOOKUP_TABLE_PROGRAM_KEY,
+    ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_KEY, COMPUTE_BUDGET_PROGRAM_KEY, MEMO_PROGRAM_KEY,
+    MEMO_V1_PROGRAM_KEY, STAKE_PROGRAM_KEY, TOKEN_2022_PROGRAM_KEY, TOKEN_PROGRAM_KEY,
+    VOTE_PROGRAM_KEY, ZK_ELGAMAL_PROOF_PROGRAM_KEY,
+};
 use crate::solana::structs::{
-    IdlSource, ProgramType, SolTransfer, SolanaAccount, SolanaAddressTableLookup,
-    SolanaInstruction, SolanaSingleAddressTableLookup,
+    AccountAddress, IdlSource, InstructionParseError, InvalidInstructionsError, NativeProgram,
+    ProgramType, SolTransfer, SolanaAccount, SolanaAccountMeta, SolanaAccountSource,
+    SolanaAddressTableLookup, SolanaFeeSummary, SolanaInstruction, SolanaMemo,
+    SolanaParsedInstructionData, SolanaSingleAddressTableLookup, SplInterface,
+    StakeAuthorityChange, StakeDelegation, StakeWithdrawal, TransactionParseError,
+    TransactionWarning,
 };
 
 // Test-only IDL directory for test fixtures
@@ -119,21 +128,72 @@ fn parses_invalid_transactions() {
     let unsigned_payload = "0100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010001032b162ad640a79029d57fbe5dad39d5741066c4c65b22bd248c8677174c28a4630d42099a5e0aaeaad1d4ede263662787cb3f6291a6ede340c4aa7ca26249dbe3000000000000000000000000000000000000000000000000000000000000000021d594adba2b7fbd34a0383ded05e2ba526e907270d8394b47886805b880e73201020200010c020000006f000000000000".to_string();
     let parsed_tx = SolanaTransaction::new(&unsigned_payload, true, None);
 
-    let inst_error_message = parsed_tx.unwrap_err().to_string(); // Convert to String
+    // The 12 byte instruction data starts right after its length at byte 203 but only 11 bytes remain
     assert_eq!(
-    inst_error_message,
-    "Error parsing full transaction. If this is just a message instead of a full transaction, parse using the --message flag. Parsing Error: \"Unsigned transaction provided is incorrectly formatted, error while parsing Instruction Data Array\""
-);
+        parsed_tx.unwrap_err(),
+        TransactionParseError::Truncated {
+            section: "Instruction Data Array".to_string(),
+            offset: 203,
+            needed: 12,
+            available: 11,
+        }
+    );
 
     // Invalid length for Accounts Array
     let unsigned_payload = "0100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010001192b162ad640a79029d57fbe5dad39d5741066c4c65b22bd248c8677174c28a4630d42099a5e0aaeaad1d4ede263662787cb3f6291a6ede340c4aa7ca26249dbe3000000000000000000000000000000000000000000000000000000000000000021d594adba2b7fbd34a0383ded05e2ba526e907270d8394b47886805b880e73201020200010c020000006f000000000000".to_string();
     let parsed_tx = SolanaTransaction::new(&unsigned_payload, true, None);
 
-    let inst_error_message = parsed_tx.unwrap_err().to_string(); // Unwrap the error
+    // The header claims 25 account keys (800 bytes) starting at byte 69
     assert_eq!(
-            inst_error_message,
-            "Error parsing full transaction. If this is just a message instead of a full transaction, parse using the --message flag. Parsing Error: \"Unsigned transaction provided is incorrectly formatted, error while parsing Accounts\""
-        );
+        parsed_tx.unwrap_err(),
+        TransactionParseError::Truncated {
+            section: "Accounts".to_string(),
+            offset: 69,
+            needed: 800,
+            available: 145,
+        }
+    );
+}
+
+#[test]
+fn reports_structured_parse_errors() {
+    let message = "010001032b162ad640a79029d57fbe5dad39d5741066c4c65b22bd248c8677174c28a4630d42099a5e0aaeaad1d4ede263662787cb3f6291a6ede340c4aa7ca26249dbe3000000000000000000000000000000000000000000000000000000000000000021d594adba2b7fbd34a0383ded05e2ba526e907270d8394b47886805b880e73201020200010c020000006f00000000000000";
+    let message_len = message.len() / 2;
+
+    // Trailing bytes after the last instruction
+    let err = SolanaTransaction::new(&format!("{message}abcd"), false, None).unwrap_err();
+    assert_eq!(
+        err,
+        TransactionParseError::ExtraneousBytes {
+            offset: message_len,
+            count: 2,
+        }
+    );
+
+    // A version prefix other than 0x80 (v0)
+    let err = SolanaTransaction::new(&format!("81{message}"), false, None).unwrap_err();
+    assert_eq!(err, TransactionParseError::UnsupportedVersion(1));
+
+    // Patch the instruction's program index from 2 to 7, past the end of the three account keys
+    let out_of_bounds = message.replace("01020200010c", "01070200010c");
+    let err = parse_transaction(out_of_bounds.clone(), false, None).unwrap_err();
+    assert!(matches!(err, TransactionParseError::SanitizeFailed(_)));
+
+    // The constructors do not sanitize, so the same problem surfaces when the instructions are parsed
+    let err = SolanaTransaction::new(&out_of_bounds, false, None)
+        .unwrap()
+        .transaction_metadata()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TransactionParseError::InvalidInstructions(InvalidInstructionsError::SanitizeFailed(
+            solana_sdk::sanitize::SanitizeError::IndexOutOfBounds
+        ))
+    );
+    assert!(std::error::Error::source(&err).is_some());
+
+    let err = parse_transaction(String::new(), false, None).unwrap_err();
+    assert_eq!(err, TransactionParseError::Empty);
 }
 
 #[test]
@@ -172,6 +232,85 @@ fn parses_v0_transaction_message_only() {
     verify_jupiter_message(transaction_metadata)
 }
 
+// Expected account metas of an instruction whose accounts are all static
+fn static_metas(accounts: &[SolanaAccount]) -> Vec<SolanaAccountMeta> {
+    accounts
+        .iter()
+        .map(|a| SolanaAccountMeta::from(&AccountAddress::Static(a.clone())))
+        .collect()
+}
+
+// Expected natively decoded System program Transfer
+fn system_transfer_instruction(
+    from: &SolanaAccount,
+    to: &SolanaAccount,
+    lamports: u64,
+) -> SolanaParsedInstructionData {
+    SolanaParsedInstructionData {
+        instruction_name: "Transfer".to_string(),
+        discriminator: "02000000".to_string(),
+        named_accounts: HashMap::from([
+            ("funding_account".to_string(), from.account_key.clone()),
+            ("recipient_account".to_string(), to.account_key.clone()),
+        ]),
+        program_call_args: Map::from_iter([("lamports".to_string(), Value::from(lamports))]),
+        idl_source: IdlSource::Native(NativeProgram::System),
+        idl_hash: String::new(),
+    }
+}
+
+// Expected natively decoded Token program instruction without arguments
+fn token_instruction(
+    instruction_name: &str,
+    discriminator: &str,
+    named_accounts: &[(&str, &SolanaAccount)],
+) -> SolanaParsedInstructionData {
+    SolanaParsedInstructionData {
+        instruction_name: instruction_name.to_string(),
+        discriminator: discriminator.to_string(),
+        named_accounts: named_accounts
+            .iter()
+            .map(|(name, account)| (name.to_string(), account.account_key.clone()))
+            .collect(),
+        program_call_args: Map::new(),
+        idl_source: IdlSource::Native(NativeProgram::Token),
+        idl_hash: String::new(),
+    }
+}
+
+fn associated_token_account_instruction(
+    instruction_name: &str,
+    discriminator: &str,
+    named_accounts: &[(&str, &str)],
+) -> SolanaParsedInstructionData {
+    SolanaParsedInstructionData {
+        instruction_name: instruction_name.to_string(),
+        discriminator: discriminator.to_string(),
+        named_accounts: named_accounts
+            .iter()
+            .map(|(name, account)| (name.to_string(), account.to_string()))
+            .collect(),
+        program_call_args: Map::new(),
+        idl_source: IdlSource::Native(NativeProgram::AssociatedTokenAccount),
+        idl_hash: String::new(),
+    }
+}
+
+fn compute_budget_instruction(
+    instruction_name: &str,
+    discriminator: &str,
+    args: Value,
+) -> SolanaParsedInstructionData {
+    SolanaParsedInstructionData {
+        instruction_name: instruction_name.to_string(),
+        discriminator: discriminator.to_string(),
+        named_accounts: HashMap::new(),
+        program_call_args: args.as_object().cloned().unwrap_or_default(),
+        idl_source: IdlSource::Native(NativeProgram::ComputeBudget),
+        idl_hash: String::new(),
+    }
+}
+
 #[allow(clippy::too_many_lines)]
 fn verify_jupiter_message(transaction_metadata: SolanaMetadata) {
     // All Expected accounts
@@ -262,9 +401,15 @@ fn verify_jupiter_message(transaction_metadata: SolanaMetadata) {
         program_key: compute_budget_acct_key.to_string(),
         accounts: vec![],
         address_table_lookups: vec![],
+        account_metas: vec![],
         instruction_data_hex: "02c05c1500".to_string(),
-        parsed_instruction: None,
+        parsed_instruction: Some(compute_budget_instruction(
+            "SetComputeUnitLimit",
+            "02",
+            serde_json::json!({ "units": 1_400_000 }),
+        )),
         idl_parse_error: None,
+        instruction_parse_error: None,
     };
     assert_eq!(exp_instruction_1, transaction_metadata.instructions[0]);
 
@@ -273,17 +418,35 @@ fn verify_jupiter_message(transaction_metadata: SolanaMetadata) {
         program_key: compute_budget_acct_key.to_string(),
         accounts: vec![],
         address_table_lookups: vec![],
+        account_metas: vec![],
         instruction_data_hex: "03caa2000000000000".to_string(),
-        parsed_instruction: None,
+        parsed_instruction: Some(compute_budget_instruction(
+            "SetComputeUnitPrice",
+            "03",
+            serde_json::json!({ "micro_lamports": 41674 }),
+        )),
         idl_parse_error: None,
+        instruction_parse_error: None,
     };
     assert_eq!(exp_instruction_2, transaction_metadata.instructions[1]);
 
     // Instruction 3 - CreateIdempotent
     let exp_instruction_3 = SolanaInstruction {
         program_key: assoc_token_acct_key.to_string(),
-        parsed_instruction: None,
+        parsed_instruction: Some(associated_token_account_instruction(
+            "CreateIdempotent",
+            "01",
+            &[
+                ("payer", signer_acct_key),
+                ("associated_token_account", receiving_acct_key),
+                ("wallet", signer_acct_key),
+                ("mint", "ADDRESS_TABLE_LOOKUP"),
+                ("system_program", SOL_SYSTEM_PROGRAM_KEY),
+                ("token_program", token_acct_key),
+            ],
+        )),
         idl_parse_error: None,
+        instruction_parse_error: None,
         accounts: vec![
             signer_acct.clone(),
             receiving_acct.clone(),
@@ -295,7 +458,27 @@ fn verify_jupiter_message(transaction_metadata: SolanaMetadata) {
             address_table_key: lookup_table_key.to_string(),
             index: 151,
             writable: false,
+            resolved_address: None,
         }],
+        // the mint is loaded from the lookup table, between the wallet and the system program
+        account_metas: [
+            static_metas(&[
+                signer_acct.clone(),
+                receiving_acct.clone(),
+                signer_acct.clone(),
+            ]),
+            vec![SolanaAccountMeta {
+                source: SolanaAccountSource::AddressTableLookup {
+                    address_table_key: lookup_table_key.to_string(),
+                    index: 151,
+                },
+                account_key: None,
+                signer: false,
+                writable: false,
+            }],
+            static_metas(&[system_program_acct.clone(), token_acct.clone()]),
+        ]
+        .concat(),
         instruction_data_hex: "01".to_string(),
     };
     assert_eq!(exp_instruction_3, transaction_metadata.instructions[2]);
@@ -305,9 +488,15 @@ fn verify_jupiter_message(transaction_metadata: SolanaMetadata) {
         program_key: SOL_SYSTEM_PROGRAM_KEY.to_string(),
         accounts: vec![signer_acct.clone(), receiving_acct.clone()],
         address_table_lookups: vec![],
+        account_metas: static_metas(&[signer_acct.clone(), receiving_acct.clone()]),
         instruction_data_hex: "0200000080f0fa0200000000".to_string(),
-        parsed_instruction: None,
+        parsed_instruction: Some(system_transfer_instruction(
+            &signer_acct,
+            &receiving_acct,
+            50_000_000,
+        )),
         idl_parse_error: None,
+        instruction_parse_error: None,
     };
     assert_eq!(exp_instruction_4, transaction_metadata.instructions[3]);
 
@@ -316,9 +505,15 @@ fn verify_jupiter_message(transaction_metadata: SolanaMetadata) {
         program_key: token_acct_key.to_string(),
         accounts: vec![receiving_acct.clone()],
         address_table_lookups: vec![],
+        account_metas: static_metas(std::slice::from_ref(&receiving_acct)),
         instruction_data_hex: "11".to_string(),
-        parsed_instruction: None,
+        parsed_instruction: Some(token_instruction(
+            "SyncNative",
+            "11",
+            &[("account", &receiving_acct)],
+        )),
         idl_parse_error: None,
+        instruction_parse_error: None,
     };
     assert_eq!(exp_instruction_5, transaction_metadata.instructions[4]);
 
@@ -334,11 +529,46 @@ fn verify_jupiter_message(transaction_metadata: SolanaMetadata) {
             token_acct.clone(),
         ],
         address_table_lookups: vec![],
+        account_metas: static_metas(&[
+            signer_acct.clone(),
+            usdc_mint_acct.clone(),
+            signer_acct.clone(),
+            usdc_acct.clone(),
+            system_program_acct.clone(),
+            token_acct.clone(),
+        ]),
         instruction_data_hex: "01".to_string(),
-        parsed_instruction: None,
+        parsed_instruction: Some(associated_token_account_instruction(
+            "CreateIdempotent",
+            "01",
+            &[
+                ("payer", signer_acct_key),
+                ("associated_token_account", usdc_mint_acct_key),
+                ("wallet", signer_acct_key),
+                ("mint", usdc_acct_key),
+                ("system_program", SOL_SYSTEM_PROGRAM_KEY),
+                ("token_program", token_acct_key),
+            ],
+        )),
         idl_parse_error: None,
+        instruction_parse_error: None,
     };
     assert_eq!(exp_instruction_6, transaction_metadata.instructions[5]);
+    // both associated token accounts are the ones derived from the wallet and mint, or cannot be checked offline
+    assert!(transaction_metadata.warnings.is_empty());
+
+    // 1,400,000 compute units at 41,674 micro-lamports each is a priority fee of 58,343.6 lamports, rounded up
+    assert_eq!(
+        SolanaFeeSummary {
+            signature_count: 1,
+            base_fee_lamports: 5_000,
+            compute_unit_limit: 1_400_000,
+            compute_unit_price_micro_lamports: 41_674,
+            priority_fee_lamports: 58_344,
+            total_max_fee_lamports: 63_344,
+        },
+        transaction_metadata.fee_summary
+    );
 
     // Instruction 7 Jupiter Aggregator V6 Route
     let mut lookups_7: Vec<SolanaSingleAddressTableLookup> = vec![];
@@ -349,6 +579,7 @@ fn verify_jupiter_message(transaction_metadata: SolanaMetadata) {
             address_table_key: lookup_table_key.to_string(),
             index: lookups_7_inds[i],
             writable: lookups_7_writable[i],
+            resolved_address: None,
         });
     }
     let exp_instruction_7 = &transaction_metadata.instructions[6];
@@ -376,6 +607,21 @@ fn verify_jupiter_message(transaction_metadata: SolanaMetadata) {
         ]
     );
     assert_eq!(exp_instruction_7.address_table_lookups, lookups_7);
+    // The ordered account metas interleave both arrays, keeping the relative order of each
+    assert_eq!(exp_instruction_7.account_metas.len(), 14 + lookups_7.len());
+    let (static_metas_7, lookup_metas_7): (Vec<_>, Vec<_>) = exp_instruction_7
+        .account_metas
+        .iter()
+        .cloned()
+        .partition(|m| m.source == SolanaAccountSource::Static);
+    assert_eq!(static_metas_7, static_metas(&exp_instruction_7.accounts));
+    assert_eq!(
+        lookup_metas_7,
+        lookups_7
+            .iter()
+            .map(|l| SolanaAccountMeta::from(&AccountAddress::AddressTableLookUp(l.clone())))
+            .collect::<Vec<_>>()
+    );
     assert_eq!(
         exp_instruction_7.instruction_data_hex,
         "e517cb977ae3ad2a01000000120064000180f0fa02000000005d34700000000000320000"
@@ -400,9 +646,23 @@ fn verify_jupiter_message(transaction_metadata: SolanaMetadata) {
             signer_acct.clone(),
         ],
         address_table_lookups: vec![],
+        account_metas: static_metas(&[
+            receiving_acct.clone(),
+            signer_acct.clone(),
+            signer_acct.clone(),
+        ]),
         instruction_data_hex: "09".to_string(),
-        parsed_instruction: None,
+        parsed_instruction: Some(token_instruction(
+            "CloseAccount",
+            "09",
+            &[
+                ("account", &receiving_acct),
+                ("destination", &signer_acct),
+                ("owner", &signer_acct),
+            ],
+        )),
         idl_parse_error: None,
+        instruction_parse_error: None,
     };
     assert_eq!(exp_instruction_8, transaction_metadata.instructions[7]);
 
@@ -419,6 +679,8 @@ fn verify_jupiter_message(transaction_metadata: SolanaMetadata) {
         address_table_key: lookup_table_key.to_string(),
         writable_indexes: vec![189, 194, 193, 186],
         readonly_indexes: vec![151, 188, 187, 191],
+        table_status: None,
+        out_of_range_indexes: vec![],
     }];
     assert_eq!(exp_lookups, transaction_metadata.address_table_lookups);
 }
@@ -560,15 +822,32 @@ fn parses_valid_v0_transaction_with_complex_address_table_lookups() {
 
     // Assert expected number of instructions
     assert_eq!(7, transaction_metadata.instructions.len());
+    assert_eq!(
+        SolanaFeeSummary {
+            signature_count: 1,
+            base_fee_lamports: 5_000,
+            compute_unit_limit: 286_560,
+            compute_unit_price_micro_lamports: 348_967,
+            priority_fee_lamports: 100_000,
+            total_max_fee_lamports: 105_000,
+        },
+        transaction_metadata.fee_summary
+    );
 
     // Instruction 1 -- SetComputeUnitLimit
     let exp_instruction_1 = SolanaInstruction {
         program_key: compute_budget_acct_key.to_string(),
         accounts: vec![],
         address_table_lookups: vec![],
+        account_metas: vec![],
         instruction_data_hex: "02605f0400".to_string(),
-        parsed_instruction: None,
+        parsed_instruction: Some(compute_budget_instruction(
+            "SetComputeUnitLimit",
+            "02",
+            serde_json::json!({ "units": 286560 }),
+        )),
         idl_parse_error: None,
+        instruction_parse_error: None,
     };
     assert_eq!(exp_instruction_1, transaction_metadata.instructions[0]);
 
@@ -577,9 +856,15 @@ fn parses_valid_v0_transaction_with_complex_address_table_lookups() {
         program_key: compute_budget_acct_key.to_string(),
         accounts: vec![],
         address_table_lookups: vec![],
+        account_metas: vec![],
         instruction_data_hex: "032753050000000000".to_string(),
-        parsed_instruction: None,
+        parsed_instruction: Some(compute_budget_instruction(
+            "SetComputeUnitPrice",
+            "03",
+            serde_json::json!({ "micro_lamports": 348967 }),
+        )),
         idl_parse_error: None,
+        instruction_parse_error: None,
     };
     assert_eq!(exp_instruction_2, transaction_metadata.instructions[1]);
 
@@ -595,9 +880,29 @@ fn parses_valid_v0_transaction_with_complex_address_table_lookups() {
             token_acct.clone(),
         ],
         address_table_lookups: vec![],
+        account_metas: static_metas(&[
+            signer_acct.clone(),
+            wsol_mint_acct.clone(),
+            signer_acct.clone(),
+            wsol_acct.clone(),
+            system_program_acct.clone(),
+            token_acct.clone(),
+        ]),
         instruction_data_hex: "01".to_string(),
-        parsed_instruction: None,
+        parsed_instruction: Some(associated_token_account_instruction(
+            "CreateIdempotent",
+            "01",
+            &[
+                ("payer", signer_acct_key),
+                ("associated_token_account", wsol_mint_acct_key),
+                ("wallet", signer_acct_key),
+                ("mint", wsol_acct_key),
+                ("system_program", SOL_SYSTEM_PROGRAM_KEY),
+                ("token_program", token_acct_key),
+            ],
+        )),
         idl_parse_error: None,
+        instruction_parse_error: None,
     };
     assert_eq!(exp_instruction_3, transaction_metadata.instructions[2]);
 
@@ -606,9 +911,15 @@ fn parses_valid_v0_transaction_with_complex_address_table_lookups() {
         program_key: SOL_SYSTEM_PROGRAM_KEY.to_string(),
         accounts: vec![signer_acct.clone(), wsol_mint_acct.clone()],
         address_table_lookups: vec![],
+        account_metas: static_metas(&[signer_acct.clone(), wsol_mint_acct.clone()]),
         instruction_data_hex: "020000008096980000000000".to_string(),
-        parsed_instruction: None,
+        parsed_instruction: Some(system_transfer_instruction(
+            &signer_acct,
+            &wsol_mint_acct,
+            10_000_000,
+        )),
         idl_parse_error: None,
+        instruction_parse_error: None,
     };
     assert_eq!(exp_instruction_4, transaction_metadata.instructions[3]);
 
@@ -617,9 +928,15 @@ fn parses_valid_v0_transaction_with_complex_address_table_lookups() {
         program_key: token_acct_key.to_string(),
         accounts: vec![wsol_mint_acct.clone()],
         address_table_lookups: vec![],
+        account_metas: static_metas(std::slice::from_ref(&wsol_mint_acct)),
         instruction_data_hex: "11".to_string(),
-        parsed_instruction: None,
+        parsed_instruction: Some(token_instruction(
+            "SyncNative",
+            "11",
+            &[("account", &wsol_mint_acct)],
+        )),
         idl_parse_error: None,
+        instruction_parse_error: None,
     };
     assert_eq!(exp_instruction_5, transaction_metadata.instructions[4]);
 
@@ -668,86 +985,103 @@ fn parses_valid_v0_transaction_with_complex_address_table_lookups() {
                 address_table_key: lookup_table_key_1.to_string(),
                 index: 0,
                 writable: false,
+                resolved_address: None,
             },
             SolanaSingleAddressTableLookup {
                 address_table_key: lookup_table_key_1.to_string(),
                 index: 9,
                 writable: true,
+                resolved_address: None,
             },
             SolanaSingleAddressTableLookup {
                 address_table_key: lookup_table_key_1.to_string(),
                 index: 2,
                 writable: true,
+                resolved_address: None,
             },
             SolanaSingleAddressTableLookup {
                 address_table_key: lookup_table_key_1.to_string(),
                 index: 4,
                 writable: true,
+                resolved_address: None,
             },
             SolanaSingleAddressTableLookup {
                 address_table_key: lookup_table_key_1.to_string(),
                 index: 3,
                 writable: true,
+                resolved_address: None,
             },
             SolanaSingleAddressTableLookup {
                 address_table_key: lookup_table_key_1.to_string(),
                 index: 7,
                 writable: true,
+                resolved_address: None,
             },
             SolanaSingleAddressTableLookup {
                 address_table_key: lookup_table_key_1.to_string(),
                 index: 17,
                 writable: true,
+                resolved_address: None,
             },
             SolanaSingleAddressTableLookup {
                 address_table_key: lookup_table_key_1.to_string(),
                 index: 5,
                 writable: true,
+                resolved_address: None,
             },
             SolanaSingleAddressTableLookup {
                 address_table_key: lookup_table_key_1.to_string(),
                 index: 1,
                 writable: true,
+                resolved_address: None,
             },
             SolanaSingleAddressTableLookup {
                 address_table_key: lookup_table_key_1.to_string(),
                 index: 65,
                 writable: true,
+                resolved_address: None,
             },
             SolanaSingleAddressTableLookup {
                 address_table_key: lookup_table_key_1.to_string(),
                 index: 64,
                 writable: true,
+                resolved_address: None,
             },
             SolanaSingleAddressTableLookup {
                 address_table_key: lookup_table_key_1.to_string(),
                 index: 20,
                 writable: false,
+                resolved_address: None,
             },
             SolanaSingleAddressTableLookup {
                 address_table_key: lookup_table_key_2.to_string(),
                 index: 253,
                 writable: false,
+                resolved_address: None,
             },
             SolanaSingleAddressTableLookup {
                 address_table_key: lookup_table_key_2.to_string(),
                 index: 252,
                 writable: false,
+                resolved_address: None,
             },
             SolanaSingleAddressTableLookup {
                 address_table_key: lookup_table_key_2.to_string(),
                 index: 251,
                 writable: true,
+                resolved_address: None,
             },
             SolanaSingleAddressTableLookup {
                 address_table_key: lookup_table_key_2.to_string(),
                 index: 254,
                 writable: true,
+                resolved_address: None,
             },
             SolanaSingleAddressTableLookup {
                 address_table_key: lookup_table_key_2.to_string(),
                 index: 255,
                 writable: true,
+                resolved_address: None,
             },
         ]
     );
@@ -770,9 +1104,23 @@ fn parses_valid_v0_transaction_with_complex_address_table_lookups() {
             signer_acct.clone(),
         ],
         address_table_lookups: vec![],
+        account_metas: static_metas(&[
+            wsol_mint_acct.clone(),
+            signer_acct.clone(),
+            signer_acct.clone(),
+        ]),
         instruction_data_hex: "09".to_string(),
-        parsed_instruction: None,
+        parsed_instruction: Some(token_instruction(
+            "CloseAccount",
+            "09",
+            &[
+                ("account", &wsol_mint_acct),
+                ("destination", &signer_acct),
+                ("owner", &signer_acct),
+            ],
+        )),
         idl_parse_error: None,
+        instruction_parse_error: None,
     };
     assert_eq!(exp_instruction_7, transaction_metadata.instructions[6]);
 
@@ -790,11 +1138,15 @@ fn parses_valid_v0_transaction_with_complex_address_table_lookups() {
             address_table_key: lookup_table_key_1.to_string(),
             writable_indexes: vec![65, 1, 4, 64, 7, 5, 2, 17, 3, 9],
             readonly_indexes: vec![20, 0],
+            table_status: None,
+            out_of_range_indexes: vec![],
         },
         SolanaAddressTableLookup {
             address_table_key: lookup_table_key_2.to_string(),
             writable_indexes: vec![251, 254, 255],
             readonly_indexes: vec![253, 252],
+            table_status: None,
+            out_of_range_indexes: vec![],
         },
     ];
     assert_eq!(exp_lookups, transaction_metadata.address_table_lookups);
@@ -816,6 +1168,150 @@ fn parses_transaction_with_multi_byte_compact_array_header() {
     assert_eq!("00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000".to_string(), parsed_tx_sigs[0]);
 }
 
+#[test]
+fn parses_transaction_with_multi_byte_account_and_instruction_headers() {
+    // The static account keys array and the instructions array are both compact arrays whose length prefix is a compact-u16
+    // This test builds a legacy message with 130 static account keys and 130 instructions so that both headers are 2 bytes long (0x82 0x01)
+    use solana_sdk::{
+        hash::Hash,
+        instruction::CompiledInstruction,
+        message::{Message, MessageHeader},
+        pubkey::Pubkey,
+        system_instruction::SystemInstruction,
+    };
+
+    let num_keys: u8 = 130;
+    let mut account_keys: Vec<Pubkey> = (1..num_keys)
+        .map(|i| Pubkey::new_from_array([i; 32]))
+        .collect();
+    account_keys.push(Pubkey::default()); // system program is the last key
+    let instructions: Vec<CompiledInstruction> = (1..=num_keys)
+        .map(|i| CompiledInstruction {
+            program_id_index: num_keys - 1,
+            accounts: vec![0, i % (num_keys - 1)],
+            data: bincode::serialize(&SystemInstruction::Transfer {
+                lamports: u64::from(i),
+            })
+            .unwrap(),
+        })
+        .collect();
+    let message = Message {
+        header: MessageHeader {
+            num_required_signatures: 1,
+            num_readonly_signed_accounts: 0,
+            num_readonly_unsigned_accounts: 1,
+        },
+        account_keys,
+        recent_blockhash: Hash::new_from_array([7; 32]),
+        instructions,
+    };
+    let message_bytes = bincode::serialize(&message).unwrap();
+    assert_eq!(&message_bytes[3..5], &[0x82, 0x01]);
+
+    // a full transaction with a single placeholder signature
+    let mut transaction_bytes = vec![1u8];
+    transaction_bytes.extend_from_slice(&[0u8; 64]);
+    transaction_bytes.extend_from_slice(&message_bytes);
+
+    for (payload, full_transaction) in [(message_bytes, false), (transaction_bytes, true)] {
+        let parsed_tx =
+            SolanaTransaction::new(&hex::encode(payload), full_transaction, None).unwrap();
+        let transaction_metadata = parsed_tx.transaction_metadata().unwrap();
+        assert_eq!(transaction_metadata.account_keys.len(), 130);
+        assert_eq!(transaction_metadata.instructions.len(), 130);
+        assert_eq!(transaction_metadata.transfers.len(), 130);
+        assert_eq!(transaction_metadata.transfers[129].amount, "130");
+        assert_eq!(
+            transaction_metadata.recent_blockhash,
+            Hash::new_from_array([7; 32]).to_string()
+        );
+    }
+}
+
+#[test]
+fn records_undecodable_native_instructions_without_failing_the_transaction() {
+    // A system instruction with garbage data, a token instruction with no data and a token transfer missing its owner account surround a valid SOL transfer
+    use solana_sdk::{
+        hash::Hash,
+        instruction::CompiledInstruction,
+        message::{Message, MessageHeader},
+        pubkey::Pubkey,
+        system_instruction::SystemInstruction,
+    };
+
+    let token_program = Pubkey::from_str_const(TOKEN_PROGRAM_KEY);
+    let message = Message {
+        header: MessageHeader {
+            num_required_signatures: 1,
+            num_readonly_signed_accounts: 0,
+            num_readonly_unsigned_accounts: 2,
+        },
+        account_keys: vec![
+            Pubkey::new_from_array([1; 32]),
+            Pubkey::new_from_array([2; 32]),
+            Pubkey::default(),
+            token_program,
+        ],
+        recent_blockhash: Hash::new_from_array([7; 32]),
+        instructions: vec![
+            CompiledInstruction {
+                program_id_index: 2,
+                accounts: vec![0, 1],
+                data: vec![0xff, 0xff],
+            },
+            CompiledInstruction {
+                program_id_index: 3,
+                accounts: vec![0, 1],
+                data: vec![],
+            },
+            CompiledInstruction {
+                program_id_index: 3,
+                accounts: vec![0, 1],
+                data: [vec![3], 5u64.to_le_bytes().to_vec()].concat(),
+            },
+            CompiledInstruction {
+                program_id_index: 2,
+                accounts: vec![0, 1],
+                data: bincode::serialize(&SystemInstruction::Transfer { lamports: 42 }).unwrap(),
+            },
+        ],
+    };
+    let message_hex = hex::encode(bincode::serialize(&message).unwrap());
+
+    let response = parse_transaction(message_hex, false, None).unwrap();
+    let metadata = response
+        .solana_parsed_transaction
+        .payload
+        .unwrap()
+        .transaction_metadata
+        .unwrap();
+
+    assert_eq!(metadata.instructions.len(), 4);
+    assert!(matches!(
+        &metadata.instructions[0].instruction_parse_error,
+        Some(InstructionParseError::DataParseError { program_name, .. }) if program_name == "System Program"
+    ));
+    assert_eq!(
+        metadata.instructions[1].instruction_parse_error,
+        Some(InstructionParseError::DataParseError {
+            program_name: "Token Program".to_string(),
+            error: "error while parsing spl instruction data header".to_string(),
+        })
+    );
+    assert_eq!(
+        metadata.instructions[2].instruction_parse_error,
+        Some(InstructionParseError::NotEnoughAccounts {
+            instruction_name: "Transfer".to_string(),
+            expected: 3,
+            found: 2,
+        })
+    );
+    assert_eq!(metadata.instructions[3].instruction_parse_error, None);
+    assert_eq!(metadata.transfers.len(), 1);
+    assert_eq!(metadata.transfers[0].amount, "42");
+    assert!(metadata.spl_transfers.is_empty());
+}
+
 #[test]
 fn parse_spl_token_transfer() {
     // The below transaction hex involves two instructions, one to the system program which is irrelevant for this test, and an SPL token transfer described below.
@@ -2282,6 +2778,32 @@ mod custom_idl_tests {
         assert_eq!(unknown, None);
     }
 
+    #[test]
+    fn test_native_program_from_program_id() {
+        let programs = [
+            NativeProgram::System,
+            NativeProgram::Token,
+            NativeProgram::Token2022,
+            NativeProgram::ZkElGamalProof,
+            NativeProgram::AssociatedTokenAccount,
+            NativeProgram::ComputeBudget,
+            NativeProgram::Stake,
+            NativeProgram::Vote,
+            NativeProgram::AddressLookupTable,
+            NativeProgram::Memo,
+            NativeProgram::MemoV1,
+        ];
+        for program in programs {
+            assert_eq!(
+                NativeProgram::from_program_id(program.program_id()),
+                Some(program)
+            );
+        }
+
+        let unknown = NativeProgram::from_program_id("Unknown111111111111111111111111111111");
+        assert_eq!(unknown, None);
+    }
+
     #[test]
     fn test_program_type_methods() {
         let jupiter = ProgramType::Jupiter;
@@ -2302,4 +2824,3292 @@ mod custom_idl_tests {
         let result = parse_transaction(unsigned_payload, false, None);
         assert!(result.is_ok());
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod encoding_tests {
+    use super::*;
+    use crate::solana::parser::parse_transaction_with_encoding;
+    use crate::solana::structs::TransactionEncoding;
+    use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine};
+
+    const LEGACY_TRANSFER_TX: &str = "0100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010001032b162ad640a79029d57fbe5dad39d5741066c4c65b22bd248c8677174c28a4630d42099a5e0aaeaad1d4ede263662787cb3f6291a6ede340c4aa7ca26249dbe3000000000000000000000000000000000000000000000000000000000000000021d594adba2b7fbd34a0383ded05e2ba526e907270d8394b47886805b880e73201020200010c020000006f00000000000000";
+
+    #[test]
+    fn parses_transaction_in_all_supported_encodings() {
+        let tx_bytes = hex::decode(LEGACY_TRANSFER_TX).unwrap();
+        let expected = parse_transaction_with_encoding(
+            LEGACY_TRANSFER_TX.to_string(),
+            TransactionEncoding::Hex,
+            true,
+            None,
+        )
+        .unwrap();
+
+        let base58_tx = bs58::encode(&tx_bytes).into_string();
+        let base64_tx = BASE64_STANDARD.encode(&tx_bytes);
+        let prefixed_hex_tx = format!(" 0x{}\n", LEGACY_TRANSFER_TX.to_uppercase());
+        let cases = vec![
+            (base58_tx.clone(), TransactionEncoding::Base58),
+            (base64_tx.clone(), TransactionEncoding::Base64),
+            (prefixed_hex_tx.clone(), TransactionEncoding::Hex),
+            (base58_tx, TransactionEncoding::Auto),
+            (base64_tx, TransactionEncoding::Auto),
+            (prefixed_hex_tx, TransactionEncoding::Auto),
+        ];
+        for (input, encoding) in cases {
+            let response = parse_transaction_with_encoding(input, encoding, true, None).unwrap();
+            assert_eq!(
+                response, expected,
+                "encoding {encoding:?} parsed differently"
+            );
+        }
+
+        // the unsigned payload is always reported as hex
+        let payload = expected.solana_parsed_transaction.payload.unwrap();
+        assert_eq!(payload.unsigned_payload, LEGACY_TRANSFER_TX);
+    }
+
+    #[test]
+    fn rejects_input_invalid_for_requested_encoding() {
+        let err = parse_transaction_with_encoding(
+            "not*base64".to_string(),
+            TransactionEncoding::Base64,
+            true,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            TransactionParseError::InvalidEncoding(TransactionEncoding::Base64)
+        );
+        assert_eq!(
+            err.to_string(),
+            "unsigned Solana transaction provided is invalid base64"
+        );
+
+        // 0 is not part of the base58 alphabet
+        let err = parse_transaction_with_encoding(
+            LEGACY_TRANSFER_TX.to_string(),
+            TransactionEncoding::Base58,
+            true,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            TransactionParseError::InvalidEncoding(TransactionEncoding::Base58)
+        );
+
+        assert_eq!(
+            "Base64".parse::<TransactionEncoding>().unwrap(),
+            TransactionEncoding::Base64
+        );
+        assert!("utf8".parse::<TransactionEncoding>().is_err());
+    }
+}
+
+#[cfg(test)]
+mod payload_kind_tests {
+    use super::*;
+    use crate::solana::parser::parse_transaction_auto_detect;
+    use crate::solana::structs::{PayloadKind, TransactionEncoding};
+
+    const LEGACY_TRANSFER_MESSAGE: &str = "010001032b162ad640a79029d57fbe5dad39d5741066c4c65b22bd248c8677174c28a4630d42099a5e0aaeaad1d4ede263662787cb3f6291a6ede340c4aa7ca26249dbe3000000000000000000000000000000000000000000000000000000000000000021d594adba2b7fbd34a0383ded05e2ba526e907270d8394b47886805b880e73201020200010c020000006f00000000000000";
+    const JUPITER_V0_MESSAGE: &str = "800100070ae05271368f77a2c5fefe77ce50e2b2f93ceb671eee8b172734c8d4df9d9eddc186a35856664b03306690c1c0fbd4b5821aea1c64ffb8c368a0422e47ae0d2895de288ba87b903021e6c8c2abf12c2484e98b040792b1fbb87091bc8e0dd76b6600000000000000000000000000000000000000000000000000000000000000000306466fe5211732ffecadba72c39be7bc8ce5bbc5f7126b2c439b3a400000000479d55bf231c06eee74c56ece681507fdb1b2dea3f48e5102b1cda256bc138f06ddf6e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a98c97258f4e2489f1bb3d1029148e0d830b5a1399daff1084048e7bd8dbe9f859b43ffa27f5d7f64a74c09b1f295879de4b09ab36dfc9dd514b321aa7b38ce5e8c6fa7af3bedbad3a3d65f36aabc97431b1bbe4c2d2f6e0e47ca60203452f5d616419cee70b839eb4eadd1411aa73eea6fd8700da5f0ea730136db1dd6fb2de660804000502c05c150004000903caa200000000000007060002000e03060101030200020c0200000080f0fa02000000000601020111070600010009030601010515060002010509050805100f0a0d01020b0c0011060524e517cb977ae3ad2a01000000120064000180f0fa02000000005d34700000000000320000060302000001090158b73fa66d1fb4a0562610136ebc84c7729542a8d792cb9bd2ad1bf75c30d5a404bdc2c1ba0497bcbbbf";
+
+    fn with_placeholder_signature(message_hex: &str) -> String {
+        format!("01{}{message_hex}", "00".repeat(64))
+    }
+
+    fn detected_kind(input: String) -> PayloadKind {
+        parse_transaction_auto_detect(input, TransactionEncoding::Hex, None, None)
+            .unwrap()
+            .solana_parsed_transaction
+            .payload
+            .unwrap()
+            .payload_kind
+    }
+
+    #[test]
+    fn detects_messages_and_full_transactions() {
+        for message in [LEGACY_TRANSFER_MESSAGE, JUPITER_V0_MESSAGE] {
+            assert_eq!(detected_kind(message.to_string()), PayloadKind::Message);
+            assert_eq!(
+                detected_kind(with_placeholder_signature(message)),
+                PayloadKind::Transaction
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_payload_that_is_neither_layout() {
+        let err =
+            parse_transaction_auto_detect("0102".to_string(), TransactionEncoding::Hex, None, None)
+                .unwrap_err();
+        // As a transaction the single signature needs 64 bytes, as a message the header needs 3
+        assert_eq!(
+            err,
+            TransactionParseError::UnrecognizedPayload {
+                as_transaction: Box::new(TransactionParseError::Truncated {
+                    section: "Signatures".to_string(),
+                    offset: 1,
+                    needed: 64,
+                    available: 1,
+                }),
+                as_message: Box::new(TransactionParseError::Truncated {
+                    section: "Message Header".to_string(),
+                    offset: 0,
+                    needed: 3,
+                    available: 2,
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_ambiguous_payload() {
+        // Inner message M: header [1, 0, 1], two account keys A and B, blockhash H and no instructions
+        // The full transaction [1] + signature + M is valid, and the signature bytes are chosen so that the same bytes also read as a valid message:
+        // header [1, 0, 1], two account keys, a blockhash, and one instruction (program index 1, no accounts) whose data swallows the rest of M
+        let mut key_a = [0xaa; 32];
+        key_a[31] = 1; // read as the instruction count of the message interpretation
+        let mut key_b = [0xbb; 32];
+        key_b[0] = 1; // program index
+        key_b[1] = 0; // no instruction accounts
+        key_b[2] = 62; // instruction data length (29 bytes of B, 32 bytes of H and the instruction count of M)
+        let mut inner_message = vec![1u8, 0, 1, 2];
+        inner_message.extend_from_slice(&key_a);
+        inner_message.extend_from_slice(&key_b);
+        inner_message.extend_from_slice(&[0xcc; 32]);
+        inner_message.push(0);
+
+        let mut signature = vec![0u8, 1, 2];
+        signature.extend_from_slice(&[0xdd; 61]);
+        let mut payload = vec![1u8];
+        payload.extend_from_slice(&signature);
+        payload.extend_from_slice(&inner_message);
+
+        let err = parse_transaction_auto_detect(
+            hex::encode(payload),
+            TransactionEncoding::Hex,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err, TransactionParseError::AmbiguousPayload);
+    }
+}
+
+#[cfg(test)]
+mod layout_tests {
+    use crate::solana::parser::parse_transaction_layout;
+    use crate::solana::structs::{SolanaLayoutSection, TransactionEncoding};
+
+    const JUPITER_V0_TRANSACTION: &str = "0100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000800100070ae05271368f77a2c5fefe77ce50e2b2f93ceb671eee8b172734c8d4df9d9eddc186a35856664b03306690c1c0fbd4b5821aea1c64ffb8c368a0422e47ae0d2895de288ba87b903021e6c8c2abf12c2484e98b040792b1fbb87091bc8e0dd76b6600000000000000000000000000000000000000000000000000000000000000000306466fe5211732ffecadba72c39be7bc8ce5bbc5f7126b2c439b3a400000000479d55bf231c06eee74c56ece681507fdb1b2dea3f48e5102b1cda256bc138f06ddf6e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a98c97258f4e2489f1bb3d1029148e0d830b5a1399daff1084048e7bd8dbe9f859b43ffa27f5d7f64a74c09b1f295879de4b09ab36dfc9dd514b321aa7b38ce5e8c6fa7af3bedbad3a3d65f36aabc97431b1bbe4c2d2f6e0e47ca60203452f5d616419cee70b839eb4eadd1411aa73eea6fd8700da5f0ea730136db1dd6fb2de660804000502c05c150004000903caa200000000000007060002000e03060101030200020c0200000080f0fa02000000000601020111070600010009030601010515060002010509050805100f0a0d01020b0c0011060524e517cb977ae3ad2a01000000120064000180f0fa02000000005d34700000000000320000060302000001090158b73fa66d1fb4a0562610136ebc84c7729542a8d792cb9bd2ad1bf75c30d5a404bdc2c1ba0497bcbbbf";
+
+    fn find<'a>(sections: &'a [SolanaLayoutSection], name: &str) -> &'a SolanaLayoutSection {
+        sections
+            .iter()
+            .find(|s| s.section == name)
+            .unwrap_or_else(|| panic!("missing layout section {name}"))
+    }
+
+    #[test]
+    fn layout_covers_every_byte_of_v0_transaction() {
+        let sections =
+            parse_transaction_layout(JUPITER_V0_TRANSACTION, TransactionEncoding::Hex, true)
+                .unwrap();
+
+        // sections are contiguous and concatenate back to the input
+        let mut expected_offset = 0;
+        for section in &sections {
+            assert_eq!(section.offset, expected_offset, "{}", section.section);
+            assert_eq!(section.hex.len(), section.length * 2);
+            expected_offset += section.length;
+        }
+        let rebuilt: String = sections.iter().map(|s| s.hex.as_str()).collect();
+        assert_eq!(rebuilt, JUPITER_V0_TRANSACTION);
+
+        let signature = find(&sections, "Signature 0");
+        assert_eq!((signature.offset, signature.length), (1, 64));
+        let version = find(&sections, "Message Version Prefix");
+        assert_eq!((version.offset, version.hex.as_str()), (65, "80"));
+        assert_eq!(find(&sections, "Account Keys Array Header").hex, "0a");
+        assert_eq!(find(&sections, "Instruction 0 Data").hex, "0502c05c1500");
+        assert_eq!(find(&sections, "Instruction 0 Account Indexes").hex, "00");
+        assert_eq!(
+            find(&sections, "Address Table Lookup 0 Writable Indexes").hex,
+            "04bdc2c1ba"
+        );
+        assert_eq!(
+            find(&sections, "Address Table Lookup 0 Read-Only Indexes").hex,
+            "0497bcbbbf"
+        );
+    }
+
+    #[test]
+    fn layout_rejects_unparseable_input() {
+        // a full transaction passed as a message does not parse, so no layout is produced
+        assert!(
+            parse_transaction_layout(JUPITER_V0_TRANSACTION, TransactionEncoding::Hex, false)
+                .is_err()
+        );
+    }
+}
+
+#[cfg(test)]
+mod signature_verification_tests {
+    use super::*;
+    use crate::solana::parser::insert_signature;
+    use crate::solana::structs::{SignatureInsertionError, SignatureStatus, TransactionEncoding};
+    use ed25519_dalek::{Signer, SigningKey};
+    use solana_sdk::{
+        hash::Hash,
+        instruction::CompiledInstruction,
+        message::{Message, MessageHeader},
+        pubkey::Pubkey,
+    };
+
+    // Builds a legacy message transferring 111 lamports from the first signer, with two required signers
+    fn two_signer_message(first: &SigningKey, second: &SigningKey) -> Vec<u8> {
+        let message = Message {
+            header: MessageHeader {
+                num_required_signatures: 2,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![
+                Pubkey::new_from_array(first.verifying_key().to_bytes()),
+                Pubkey::new_from_array(second.verifying_key().to_bytes()),
+                Pubkey::new_from_array([9; 32]),
+                Pubkey::default(),
+            ],
+            recent_blockhash: Hash::new_from_array([3; 32]),
+            instructions: vec![CompiledInstruction {
+                program_id_index: 3,
+                accounts: vec![0, 2],
+                data: hex::decode("020000006f00000000000000").unwrap(),
+            }],
+        };
+        bincode::serialize(&message).unwrap()
+    }
+
+    fn full_transaction(signatures: &[[u8; 64]], message: &[u8]) -> String {
+        let mut tx = vec![signatures.len() as u8];
+        for signature in signatures {
+            tx.extend_from_slice(signature);
+        }
+        tx.extend_from_slice(message);
+        hex::encode(tx)
+    }
+
+    fn statuses(tx_hex: &str) -> Vec<SignatureStatus> {
+        SolanaTransaction::new(tx_hex, true, None)
+            .unwrap()
+            .transaction_metadata()
+            .unwrap()
+            .signature_verifications
+            .iter()
+            .map(|v| v.status)
+            .collect()
+    }
+
+    #[test]
+    fn verifies_signed_and_placeholder_slots() {
+        let first = SigningKey::from_bytes(&[1; 32]);
+        let second = SigningKey::from_bytes(&[2; 32]);
+        let message = two_signer_message(&first, &second);
+        let first_signature = first.sign(&message).to_bytes();
+
+        // partially signed: the second signer has not signed yet
+        let tx_hex = full_transaction(&[first_signature, [0; 64]], &message);
+        let parsed_tx = SolanaTransaction::new(&tx_hex, true, None).unwrap();
+        let verifications = parsed_tx
+            .transaction_metadata()
+            .unwrap()
+            .signature_verifications;
+        assert_eq!(verifications[0].status, SignatureStatus::Valid);
+        assert_eq!(
+            verifications[0].signer,
+            Some(Pubkey::new_from_array(first.verifying_key().to_bytes()).to_string())
+        );
+        assert_eq!(verifications[0].signature, hex::encode(first_signature));
+        assert_eq!(verifications[1].status, SignatureStatus::Placeholder);
+
+        // fully signed
+        let second_signature = second.sign(&message).to_bytes();
+        let tx_hex = full_transaction(&[first_signature, second_signature], &message);
+        assert_eq!(
+            statuses(&tx_hex),
+            vec![SignatureStatus::Valid, SignatureStatus::Valid]
+        );
+
+        // signatures placed in the wrong slots do not verify
+        let tx_hex = full_transaction(&[second_signature, first_signature], &message);
+        assert_eq!(
+            statuses(&tx_hex),
+            vec![SignatureStatus::Invalid, SignatureStatus::Invalid]
+        );
+    }
+
+    #[test]
+    fn detects_tampered_message() {
+        let first = SigningKey::from_bytes(&[1; 32]);
+        let second = SigningKey::from_bytes(&[2; 32]);
+        let message = two_signer_message(&first, &second);
+        let first_signature = first.sign(&message).to_bytes();
+
+        // change the transfer amount from 111 to 112 lamports after signing
+        let mut tampered = message.clone();
+        let amount_offset = tampered.len() - 8;
+        tampered[amount_offset] = 0x70;
+        let tx_hex = full_transaction(&[first_signature, [0; 64]], &tampered);
+        assert_eq!(
+            statuses(&tx_hex),
+            vec![SignatureStatus::Invalid, SignatureStatus::Placeholder]
+        );
+    }
+
+    #[test]
+    fn message_only_input_has_no_signature_verifications() {
+        let first = SigningKey::from_bytes(&[1; 32]);
+        let second = SigningKey::from_bytes(&[2; 32]);
+        let message = two_signer_message(&first, &second);
+        let parsed_tx = SolanaTransaction::new(&hex::encode(message), false, None).unwrap();
+        assert!(parsed_tx
+            .transaction_metadata()
+            .unwrap()
+            .signature_verifications
+            .is_empty());
+    }
+
+    #[test]
+    fn inserts_signatures_at_signer_slots() {
+        let first = SigningKey::from_bytes(&[1; 32]);
+        let second = SigningKey::from_bytes(&[2; 32]);
+        let first_pubkey = Pubkey::new_from_array(first.verifying_key().to_bytes()).to_string();
+        let second_pubkey = Pubkey::new_from_array(second.verifying_key().to_bytes()).to_string();
+        let message = two_signer_message(&first, &second);
+        let first_signature = first.sign(&message).to_bytes();
+        let second_signature = second.sign(&message).to_bytes();
+
+        // start from the bare message and add the second signer's signature first
+        let insertion = insert_signature(
+            hex::encode(&message),
+            TransactionEncoding::Hex,
+            false,
+            &second_pubkey,
+            &second_signature,
+        )
+        .unwrap();
+        assert_eq!(insertion.signer_index, 1);
+        assert_eq!(insertion.status, SignatureStatus::Valid);
+        assert_eq!(insertion.empty_signer_slots, vec![first_pubkey.clone()]);
+        assert_eq!(
+            insertion.transaction,
+            full_transaction(&[[0; 64], second_signature], &message)
+        );
+
+        // then add the first signer's signature to the partially signed transaction
+        let insertion = insert_signature(
+            insertion.transaction,
+            TransactionEncoding::Hex,
+            true,
+            &first_pubkey,
+            &first_signature,
+        )
+        .unwrap();
+        assert_eq!(insertion.signer_index, 0);
+        assert!(insertion.empty_signer_slots.is_empty());
+        assert_eq!(
+            statuses(&insertion.transaction),
+            vec![SignatureStatus::Valid, SignatureStatus::Valid]
+        );
+    }
+
+    #[test]
+    fn rejects_signing_message_with_alias_compact_u16() {
+        let first = SigningKey::from_bytes(&[1; 32]);
+        let second = SigningKey::from_bytes(&[2; 32]);
+        let first_pubkey = Pubkey::new_from_array(first.verifying_key().to_bytes()).to_string();
+        // encode the instruction data length 0x0c as the two byte alias 0x8c 0x00
+        let canonical = two_signer_message(&first, &second);
+        let data_offset = canonical.len() - 12;
+        let mut message = canonical[..data_offset - 1].to_vec();
+        message.extend_from_slice(&[0x8c, 0x00]);
+        message.extend_from_slice(&canonical[data_offset..]);
+
+        // the runtime would never execute this message, so no signed transaction is emitted for it
+        let err = insert_signature(
+            hex::encode(&message),
+            TransactionEncoding::Hex,
+            false,
+            &first_pubkey,
+            &first.sign(&message).to_bytes(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            SignatureInsertionError::Parse(TransactionParseError::InvalidCompactU16 {
+                section: "Instruction Data Array Header".to_string(),
+                offset: 169,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_signatures_from_non_signers() {
+        let first = SigningKey::from_bytes(&[1; 32]);
+        let second = SigningKey::from_bytes(&[2; 32]);
+        let message = two_signer_message(&first, &second);
+        let tx_hex = full_transaction(&[[0; 64], [0; 64]], &message);
+
+        // account key 2 is part of the message but is not a required signer
+        let non_signer = Pubkey::new_from_array([9; 32]).to_string();
+        let err = insert_signature(
+            tx_hex.clone(),
+            TransactionEncoding::Hex,
+            true,
+            &non_signer,
+            &[1; 64],
+        )
+        .unwrap_err();
+        assert_eq!(err, SignatureInsertionError::NotRequiredSigner(non_signer));
+
+        let first_pubkey = Pubkey::new_from_array(first.verifying_key().to_bytes()).to_string();
+        let err = insert_signature(
+            tx_hex,
+            TransactionEncoding::Hex,
+            true,
+            &first_pubkey,
+            &[1; 63],
+        )
+        .unwrap_err();
+        assert_eq!(err, SignatureInsertionError::InvalidSignatureLength(63));
+        assert_eq!(
+            err.to_string(),
+            "signature must be 64 bytes long, got 63 bytes"
+        );
+    }
+
+    #[test]
+    fn rejects_header_with_more_required_signers_than_account_keys() {
+        let first = SigningKey::from_bytes(&[1; 32]);
+        let first_pubkey = Pubkey::new_from_array(first.verifying_key().to_bytes());
+        let message = Message {
+            header: MessageHeader {
+                num_required_signatures: 3,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 0,
+            },
+            account_keys: vec![first_pubkey, Pubkey::new_from_array([9; 32])],
+            recent_blockhash: Hash::new_from_array([3; 32]),
+            instructions: vec![],
+        };
+        let message = bincode::serialize(&message).unwrap();
+
+        // the constructors do not sanitize, so the signer slots must be bounds checked themselves
+        let mut tx = SolanaTransaction::from_bytes(&message, false, None).unwrap();
+        let sanitize_error = TransactionParseError::SanitizeFailed(
+            solana_sdk::sanitize::SanitizeError::IndexOutOfBounds,
+        );
+        assert_eq!(tx.empty_signer_slots(), Err(sanitize_error.clone()));
+        let err = tx
+            .add_signature(&first_pubkey.to_string(), &first.sign(&message).to_bytes())
+            .unwrap_err();
+        assert_eq!(err, SignatureInsertionError::Parse(sanitize_error.clone()));
+
+        let err = insert_signature(
+            hex::encode(&message),
+            TransactionEncoding::Hex,
+            false,
+            &first_pubkey.to_string(),
+            &[1; 64],
+        )
+        .unwrap_err();
+        assert_eq!(err, SignatureInsertionError::Parse(sanitize_error));
+    }
+}
+
+#[cfg(test)]
+mod serialization_tests {
+    use super::*;
+    use crate::solana::parser::parse_transaction;
+    use crate::solana::structs::{TransactionEncoding, TransactionSerializeError};
+
+    // Every hex fixture in this file that parses (as a full transaction or as a message) is part of the round trip corpus
+    fn corpus() -> Vec<(String, bool)> {
+        include_str!("tests.rs")
+            .split('"')
+            .skip(1)
+            .step_by(2)
+            .filter(|s| s.len() >= 200 && s.len() % 2 == 0)
+            .filter(|s| s.chars().all(|c| c.is_ascii_hexdigit()))
+            .filter_map(|s| {
+                [true, false].into_iter().find_map(|full_transaction| {
+                    let parsed_tx = SolanaTransaction::new(s, full_transaction, None).ok()?;
+                    parsed_tx.transaction_metadata().ok()?;
+                    Some((s.to_string(), full_transaction))
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trips_test_corpus() {
+        let corpus = corpus();
+        assert!(
+            corpus.len() >= 15,
+            "corpus only has {} entries",
+            corpus.len()
+        );
+
+        for (tx_hex, full_transaction) in corpus {
+            let parsed_tx = SolanaTransaction::new(&tx_hex, full_transaction, None).unwrap();
+            let encoded = if full_transaction {
+                parsed_tx.encode_transaction(TransactionEncoding::Hex)
+            } else {
+                parsed_tx.encode_message(TransactionEncoding::Hex)
+            }
+            .unwrap();
+
+            // the corpus is canonically serialized so encoding reproduces the input exactly
+            assert_eq!(encoded, tx_hex);
+            let reparsed = SolanaTransaction::new(&encoded, full_transaction, None).unwrap();
+            assert_eq!(reparsed, parsed_tx);
+
+            for encoding in [TransactionEncoding::Base58, TransactionEncoding::Base64] {
+                let encoded = parsed_tx.encode_transaction(encoding).unwrap();
+                let reparsed =
+                    SolanaTransaction::new_with_encoding(&encoded, encoding, true, None).unwrap();
+                assert_eq!(reparsed.serialize_message(), parsed_tx.serialize_message());
+            }
+        }
+    }
+
+    #[test]
+    fn message_serializes_to_transaction_with_placeholder_signatures() {
+        let message_hex = "010001032b162ad640a79029d57fbe5dad39d5741066c4c65b22bd248c8677174c28a4630d42099a5e0aaeaad1d4ede263662787cb3f6291a6ede340c4aa7ca26249dbe3000000000000000000000000000000000000000000000000000000000000000021d594adba2b7fbd34a0383ded05e2ba526e907270d8394b47886805b880e73201020200010c020000006f00000000000000";
+        let parsed_tx = SolanaTransaction::new(message_hex, false, None).unwrap();
+        let tx_hex = parsed_tx
+            .encode_transaction(TransactionEncoding::Hex)
+            .unwrap();
+        assert_eq!(tx_hex, format!("01{}{message_hex}", "00".repeat(64)));
+
+        assert_eq!(
+            parsed_tx.encode_message(TransactionEncoding::Auto),
+            Err(TransactionSerializeError::AutoOutputEncoding)
+        );
+    }
+
+    #[test]
+    fn non_canonical_compact_u16_is_rejected() {
+        // The instruction data length 0x0c is encoded as the two byte alias 0x8c 0x00, which the runtime rejects
+        let message_hex = "010001032b162ad640a79029d57fbe5dad39d5741066c4c65b22bd248c8677174c28a4630d42099a5e0aaeaad1d4ede263662787cb3f6291a6ede340c4aa7ca26249dbe3000000000000000000000000000000000000000000000000000000000000000021d594adba2b7fbd34a0383ded05e2ba526e907270d8394b47886805b880e73201020200018c00020000006f00000000000000";
+        assert_eq!(
+            SolanaTransaction::new(message_hex, false, None).unwrap_err(),
+            TransactionParseError::InvalidCompactU16 {
+                section: "Instruction Data Array Header".to_string(),
+                offset: 137,
+            }
+        );
+        assert_eq!(
+            SolanaTransaction::new(&format!("01{}{message_hex}", "00".repeat(64)), true, None)
+                .unwrap_err(),
+            TransactionParseError::InvalidCompactU16 {
+                section: "Instruction Data Array Header".to_string(),
+                offset: 202,
+            }
+        );
+    }
+
+    #[test]
+    fn full_transaction_exposes_signable_message_bytes() {
+        let message_hex = "010001032b162ad640a79029d57fbe5dad39d5741066c4c65b22bd248c8677174c28a4630d42099a5e0aaeaad1d4ede263662787cb3f6291a6ede340c4aa7ca26249dbe3000000000000000000000000000000000000000000000000000000000000000021d594adba2b7fbd34a0383ded05e2ba526e907270d8394b47886805b880e73201020200010c020000006f00000000000000";
+        let tx_hex = format!("01{}{message_hex}", "11".repeat(64));
+
+        let payload_for = |input: &str, full_transaction: bool| {
+            parse_transaction(input.to_string(), full_transaction, None)
+                .unwrap()
+                .solana_parsed_transaction
+                .payload
+                .unwrap()
+        };
+        let message_payload = payload_for(message_hex, false);
+        let tx_payload = payload_for(&tx_hex, true);
+
+        assert_eq!(message_payload.signable_payload, message_hex);
+        assert_eq!(tx_payload.signable_payload, message_hex);
+        assert_eq!(tx_payload.unsigned_payload, tx_hex);
+        assert_eq!(tx_payload.message_hash, message_payload.message_hash);
+        assert_eq!(
+            tx_payload.message_hash,
+            "GU1YH4i3QLcnPxMuKiT4vHkmkau2YQoDLrGuKvdsurpN"
+        );
+    }
+}
+
+#[cfg(test)]
+mod lookup_table_tests {
+    use super::*;
+    use crate::solana::lookup_tables::{
+        AddressLookupTableProvider, DirectoryAddressLookupTableProvider,
+    };
+    use crate::solana::parser::parse_transaction_with_lookup_tables;
+    use crate::solana::structs::{
+        AddressLookupTableContents, AddressLookupTableDecodeError, AddressLookupTableStatus,
+        DecodedAddressLookupTable, TransactionEncoding,
+    };
+    use solana_sdk::{
+        address_lookup_table::state::{AddressLookupTable, LookupTableMeta},
+        hash::Hash,
+        instruction::CompiledInstruction,
+        message::{
+            v0::{self, MessageAddressTableLookup},
+            MessageHeader, VersionedMessage,
+        },
+        pubkey::Pubkey,
+    };
+    use std::borrow::Cow;
+    use std::cell::RefCell;
+    use std::error::Error;
+
+    const JUPITER_LOOKUP_TABLE: &str = "6yJwigBRYdkrpfDEsCRj7H5rrzdnAYv8LHzYbb5jRFKy";
+    const JUPITER_V0_TRANSACTION: &str = "0100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000800100070ae05271368f77a2c5fefe77ce50e2b2f93ceb671eee8b172734c8d4df9d9eddc186a35856664b03306690c1c0fbd4b5821aea1c64ffb8c368a0422e47ae0d2895de288ba87b903021e6c8c2abf12c2484e98b040792b1fbb87091bc8e0dd76b6600000000000000000000000000000000000000000000000000000000000000000306466fe5211732ffecadba72c39be7bc8ce5bbc5f7126b2c439b3a400000000479d55bf231c06eee74c56ece681507fdb1b2dea3f48e5102b1cda256bc138f06ddf6e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a98c97258f4e2489f1bb3d1029148e0d830b5a1399daff1084048e7bd8dbe9f859b43ffa27f5d7f64a74c09b1f295879de4b09ab36dfc9dd514b321aa7b38ce5e8c6fa7af3bedbad3a3d65f36aabc97431b1bbe4c2d2f6e0e47ca60203452f5d616419cee70b839eb4eadd1411aa73eea6fd8700da5f0ea730136db1dd6fb2de660804000502c05c150004000903caa200000000000007060002000e03060101030200020c0200000080f0fa02000000000601020111070600010009030601010515060002010509050805100f0a0d01020b0c0011060524e517cb977ae3ad2a01000000120064000180f0fa02000000005d34700000000000320000060302000001090158b73fa66d1fb4a0562610136ebc84c7729542a8d792cb9bd2ad1bf75c30d5a404bdc2c1ba0497bcbbbf";
+
+    // A synthetic table whose address at index i is [i; 32]
+    fn table_addresses(len: u8) -> Vec<Pubkey> {
+        (0..len).map(|i| Pubkey::new_from_array([i; 32])).collect()
+    }
+
+    fn parse_with_table(table: &str, contents: AddressLookupTableContents) -> SolanaMetadata {
+        let lookup_tables = HashMap::from([(table.to_string(), contents)]);
+        parse_with_provider(&lookup_tables)
+    }
+
+    fn parse_with_provider(provider: &dyn AddressLookupTableProvider) -> SolanaMetadata {
+        parse_transaction_with_lookup_tables(
+            JUPITER_V0_TRANSACTION.to_string(),
+            TransactionEncoding::Hex,
+            true,
+            None,
+            Some(provider),
+        )
+        .unwrap()
+        .solana_parsed_transaction
+        .payload
+        .unwrap()
+        .transaction_metadata
+        .unwrap()
+    }
+
+    fn assert_lookups_resolved(metadata: &SolanaMetadata) {
+        // The associated token account instruction reads its mint from index 151 of the table
+        let ata_lookup = &metadata.instructions[2].address_table_lookups[0];
+        assert_eq!(ata_lookup.index, 151);
+        assert_eq!(
+            ata_lookup.resolved_address,
+            Some(Pubkey::new_from_array([151; 32]).to_string())
+        );
+
+        for lookup in &metadata.instructions[6].address_table_lookups {
+            let index = u8::try_from(lookup.index).unwrap();
+            assert_eq!(
+                lookup.resolved_address,
+                Some(Pubkey::new_from_array([index; 32]).to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn resolves_lookups_from_address_list() {
+        let addresses = table_addresses(195).iter().map(Pubkey::to_string).collect();
+        let metadata = parse_with_table(
+            JUPITER_LOOKUP_TABLE,
+            AddressLookupTableContents::from_addresses(addresses),
+        );
+        assert_lookups_resolved(&metadata);
+    }
+
+    #[test]
+    fn resolves_lookups_from_account_data() {
+        let table = AddressLookupTable {
+            meta: LookupTableMeta::default(),
+            addresses: Cow::Owned(table_addresses(195)),
+        };
+        let data = table.serialize_for_tests().unwrap();
+        let metadata = parse_with_table(
+            JUPITER_LOOKUP_TABLE,
+            AddressLookupTableContents::from_account_data(data),
+        );
+        assert_lookups_resolved(&metadata);
+    }
+
+    #[test]
+    fn leaves_lookups_into_other_tables_unresolved() {
+        let other_table = Pubkey::new_from_array([9; 32]).to_string();
+        let metadata = parse_with_table(
+            &other_table,
+            AddressLookupTableContents::from_addresses(vec![]),
+        );
+        assert!(metadata
+            .instructions
+            .iter()
+            .flat_map(|i| &i.address_table_lookups)
+            .all(|l| l.resolved_address.is_none()));
+    }
+
+    #[test]
+    fn resolves_spl_transfer_accounts_loaded_from_lookup_table() {
+        // A v0 token Transfer whose source and destination token accounts are both loaded from a lookup table
+        let owner = Pubkey::new_from_array([1; 32]);
+        let table = Pubkey::new_from_array([2; 32]);
+        let message = v0::Message {
+            header: MessageHeader {
+                num_required_signatures: 1,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![owner, Pubkey::from_str_const(TOKEN_PROGRAM_KEY)],
+            recent_blockhash: Hash::new_from_array([7; 32]),
+            instructions: vec![CompiledInstruction {
+                program_id_index: 1,
+                accounts: vec![2, 3, 0],
+                data: [vec![3], 1000u64.to_le_bytes().to_vec()].concat(),
+            }],
+            address_table_lookups: vec![MessageAddressTableLookup {
+                account_key: table,
+                writable_indexes: vec![5, 6],
+                readonly_indexes: vec![],
+            }],
+        };
+        let message_hex = hex::encode(VersionedMessage::V0(message).serialize());
+
+        let parse = |lookup_tables: HashMap<String, AddressLookupTableContents>| {
+            parse_transaction_with_lookup_tables(
+                message_hex.clone(),
+                TransactionEncoding::Hex,
+                false,
+                None,
+                Some(&lookup_tables),
+            )
+            .unwrap()
+            .solana_parsed_transaction
+            .payload
+            .unwrap()
+            .transaction_metadata
+            .unwrap()
+            .spl_transfers
+        };
+
+        let unresolved = parse(HashMap::new());
+        assert_eq!(unresolved[0].from, "ADDRESS_TABLE_LOOKUP");
+        assert_eq!(unresolved[0].to, "ADDRESS_TABLE_LOOKUP");
+
+        let addresses = table_addresses(7).iter().map(Pubkey::to_string).collect();
+        let resolved = parse(HashMap::from([(
+            table.to_string(),
+            AddressLookupTableContents::from_addresses(addresses),
+        )]));
+        assert_eq!(
+            resolved[0].from,
+            Pubkey::new_from_array([5; 32]).to_string()
+        );
+        assert_eq!(resolved[0].to, Pubkey::new_from_array([6; 32]).to_string());
+        assert_eq!(resolved[0].owner, owner.to_string());
+        assert_eq!(resolved[0].amount, "1000");
+
+        // The instruction's accounts keep their compiled order: two lookups followed by the static owner
+        let metadata = parse_transaction_with_lookup_tables(
+            message_hex.clone(),
+            TransactionEncoding::Hex,
+            false,
+            None,
+            Some(&HashMap::from([(
+                table.to_string(),
+                AddressLookupTableContents::from_addresses(
+                    table_addresses(7).iter().map(Pubkey::to_string).collect(),
+                ),
+            )])),
+        )
+        .unwrap()
+        .solana_parsed_transaction
+        .payload
+        .unwrap()
+        .transaction_metadata
+        .unwrap();
+        let lookup_meta = |index: u8| SolanaAccountMeta {
+            source: SolanaAccountSource::AddressTableLookup {
+                address_table_key: table.to_string(),
+                index: i32::from(index),
+            },
+            account_key: Some(Pubkey::new_from_array([index; 32]).to_string()),
+            signer: false,
+            writable: true,
+        };
+        assert_eq!(
+            metadata.instructions[0].account_metas,
+            vec![
+                lookup_meta(5),
+                lookup_meta(6),
+                SolanaAccountMeta {
+                    source: SolanaAccountSource::Static,
+                    account_key: Some(owner.to_string()),
+                    signer: true,
+                    writable: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_lookup_indexes_past_end_of_table() {
+        // indexes 194, 193 and 191 do not fit in a table of 190 addresses
+        let addresses = table_addresses(190).iter().map(Pubkey::to_string).collect();
+        let metadata = parse_with_table(
+            JUPITER_LOOKUP_TABLE,
+            AddressLookupTableContents::from_addresses(addresses),
+        );
+        assert_eq!(
+            metadata.address_table_lookups[0].out_of_range_indexes,
+            vec![194, 193, 191]
+        );
+
+        // lookups past the end stay unresolved, while the others still resolve
+        let lookups: Vec<_> = metadata
+            .instructions
+            .iter()
+            .flat_map(|i| &i.address_table_lookups)
+            .collect();
+        assert!(lookups
+            .iter()
+            .filter(|l| l.index >= 190)
+            .all(|l| l.resolved_address.is_none()));
+        assert!(lookups
+            .iter()
+            .filter(|l| l.index < 190)
+            .all(|l| l.resolved_address.is_some()));
+
+        // tables that were not supplied have no known length
+        let metadata = parse_with_table(
+            &Pubkey::new_from_array([9; 32]).to_string(),
+            AddressLookupTableContents::from_addresses(vec![]),
+        );
+        assert!(metadata.address_table_lookups[0]
+            .out_of_range_indexes
+            .is_empty());
+    }
+
+    // Fixture store that records which tables the parser asked for
+    struct RecordingProvider {
+        tables: HashMap<String, AddressLookupTableContents>,
+        requested: RefCell<Vec<String>>,
+    }
+
+    impl AddressLookupTableProvider for RecordingProvider {
+        fn lookup_table(
+            &self,
+            table: &str,
+        ) -> Result<Option<AddressLookupTableContents>, Box<dyn Error + Send + Sync>> {
+            self.requested.borrow_mut().push(table.to_string());
+            Ok(self.tables.get(table).cloned())
+        }
+    }
+
+    #[test]
+    fn consults_provider_for_referenced_tables_only() {
+        let addresses = table_addresses(195).iter().map(Pubkey::to_string).collect();
+        let other_table = Pubkey::new_from_array([9; 32]).to_string();
+        let provider = RecordingProvider {
+            tables: HashMap::from([
+                (
+                    JUPITER_LOOKUP_TABLE.to_string(),
+                    AddressLookupTableContents::from_addresses(addresses),
+                ),
+                (
+                    other_table,
+                    AddressLookupTableContents::from_addresses(vec![]),
+                ),
+            ]),
+            requested: RefCell::new(vec![]),
+        };
+        let metadata = parse_with_provider(&provider);
+        assert_lookups_resolved(&metadata);
+        assert_eq!(
+            provider.requested.into_inner(),
+            vec![JUPITER_LOOKUP_TABLE.to_string()]
+        );
+    }
+
+    struct FailingProvider;
+
+    impl AddressLookupTableProvider for FailingProvider {
+        fn lookup_table(
+            &self,
+            _table: &str,
+        ) -> Result<Option<AddressLookupTableContents>, Box<dyn Error + Send + Sync>> {
+            Err("connection refused".into())
+        }
+    }
+
+    #[test]
+    fn surfaces_provider_errors() {
+        let err = parse_transaction_with_lookup_tables(
+            JUPITER_V0_TRANSACTION.to_string(),
+            TransactionEncoding::Hex,
+            true,
+            None,
+            Some(&FailingProvider),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            TransactionParseError::InvalidAddressLookupTable {
+                table: JUPITER_LOOKUP_TABLE.to_string(),
+                error: "connection refused".to_string(),
+            }
+        );
+    }
+
+    // Writes `solana address-lookup-table get --output json` style dumps into a fresh directory
+    fn lookup_table_dump_dir(name: &str, dumps: &[(&str, Value)]) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("solana-parser-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        for (table, dump) in dumps {
+            std::fs::write(dir.join(format!("{table}.json")), dump.to_string()).unwrap();
+        }
+        dir
+    }
+
+    fn lookup_table_dump(table: &str, addresses: &[Pubkey]) -> Value {
+        serde_json::json!({
+            "lookupTableAddress": table,
+            "authority": Pubkey::new_from_array([3; 32]).to_string(),
+            "deactivationSlot": u64::MAX,
+            "lastExtendedSlot": 284_019_301u64,
+            "addresses": addresses.iter().map(Pubkey::to_string).collect::<Vec<_>>(),
+        })
+    }
+
+    #[test]
+    fn resolves_lookups_from_directory_of_dumps() {
+        let dir = lookup_table_dump_dir(
+            "alt-dumps",
+            &[(
+                JUPITER_LOOKUP_TABLE,
+                lookup_table_dump(JUPITER_LOOKUP_TABLE, &table_addresses(195)),
+            )],
+        );
+        let provider = DirectoryAddressLookupTableProvider::new(&dir);
+        assert_lookups_resolved(&parse_with_provider(&provider));
+
+        // dumps are cached after the first read
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_lookups_resolved(&parse_with_provider(&provider));
+        assert_eq!(
+            DirectoryAddressLookupTableProvider::new(&dir)
+                .lookup_table(JUPITER_LOOKUP_TABLE)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_dump_for_a_different_table() {
+        let other_table = Pubkey::new_from_array([9; 32]).to_string();
+        let dir = lookup_table_dump_dir(
+            "alt-mismatched-dump",
+            &[(
+                JUPITER_LOOKUP_TABLE,
+                lookup_table_dump(&other_table, &table_addresses(195)),
+            )],
+        );
+        let err = parse_transaction_with_lookup_tables(
+            JUPITER_V0_TRANSACTION.to_string(),
+            TransactionEncoding::Hex,
+            true,
+            None,
+            Some(&DirectoryAddressLookupTableProvider::new(&dir)),
+        )
+        .unwrap_err();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(matches!(
+            err,
+            TransactionParseError::InvalidAddressLookupTable { ref table, ref error }
+                if table == JUPITER_LOOKUP_TABLE && error.contains(&other_table)
+        ));
+    }
+
+    fn lookup_table_account_data(authority: Option<Pubkey>, deactivation_slot: u64) -> Vec<u8> {
+        let table = AddressLookupTable {
+            meta: LookupTableMeta {
+                deactivation_slot,
+                last_extended_slot: 284_019_301,
+                last_extended_slot_start_index: 190,
+                authority,
+                ..LookupTableMeta::default()
+            },
+            addresses: Cow::Owned(table_addresses(195)),
+        };
+        table.serialize_for_tests().unwrap()
+    }
+
+    #[test]
+    fn decodes_lookup_table_account_data() {
+        let authority = Pubkey::new_from_array([3; 32]);
+        let decoded = DecodedAddressLookupTable::decode(&lookup_table_account_data(
+            Some(authority),
+            u64::MAX,
+        ))
+        .unwrap();
+        assert_eq!(decoded.deactivation_slot, u64::MAX);
+        assert_eq!(decoded.last_extended_slot, 284_019_301);
+        assert_eq!(decoded.last_extended_slot_start_index, 190);
+        assert_eq!(decoded.authority, Some(authority.to_string()));
+        assert_eq!(decoded.addresses.len(), 195);
+        assert_eq!(
+            decoded.addresses[151],
+            Pubkey::new_from_array([151; 32]).to_string()
+        );
+        assert_eq!(decoded.status(), AddressLookupTableStatus::Active);
+
+        let frozen =
+            DecodedAddressLookupTable::decode(&lookup_table_account_data(None, u64::MAX)).unwrap();
+        assert!(frozen.is_frozen());
+        assert_eq!(frozen.status(), AddressLookupTableStatus::Frozen);
+
+        let deactivating = DecodedAddressLookupTable::decode(&lookup_table_account_data(
+            Some(authority),
+            300_000_000,
+        ))
+        .unwrap();
+        assert!(deactivating.is_deactivating());
+        assert_eq!(
+            deactivating.status(),
+            AddressLookupTableStatus::Deactivating {
+                deactivation_slot: 300_000_000
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_lookup_table_account_data() {
+        let data = lookup_table_account_data(None, u64::MAX);
+        // shorter than the metadata header
+        assert_eq!(
+            DecodedAddressLookupTable::decode(&data[..40]),
+            Err(AddressLookupTableDecodeError::Truncated(40))
+        );
+        // a trailing partial address
+        assert_eq!(
+            DecodedAddressLookupTable::decode(&data[..data.len() - 1]),
+            Err(AddressLookupTableDecodeError::MisalignedAddresses(
+                data.len() - 57
+            ))
+        );
+        // an uninitialized account
+        let mut uninitialized = data.clone();
+        uninitialized[0] = 0;
+        assert_eq!(
+            DecodedAddressLookupTable::decode(&uninitialized),
+            Err(AddressLookupTableDecodeError::InvalidDiscriminator(0))
+        );
+        // an authority option tag that is neither None nor Some
+        let mut invalid_authority = data.clone();
+        invalid_authority[21] = 2;
+        assert_eq!(
+            DecodedAddressLookupTable::decode(&invalid_authority),
+            Err(AddressLookupTableDecodeError::InvalidAuthorityTag(2))
+        );
+    }
+
+    #[test]
+    fn reports_status_of_lookup_tables_supplied_as_account_data() {
+        let metadata = parse_with_table(
+            JUPITER_LOOKUP_TABLE,
+            AddressLookupTableContents::from_account_data(lookup_table_account_data(
+                None,
+                u64::MAX,
+            )),
+        );
+        assert_eq!(
+            metadata.address_table_lookups[0].table_status,
+            Some(AddressLookupTableStatus::Frozen)
+        );
+
+        let addresses = table_addresses(195).iter().map(Pubkey::to_string).collect();
+        let metadata = parse_with_table(
+            JUPITER_LOOKUP_TABLE,
+            AddressLookupTableContents::from_addresses(addresses),
+        );
+        assert_eq!(metadata.address_table_lookups[0].table_status, None);
+    }
+
+    #[test]
+    fn resolves_lookups_into_tables_extended_earlier_in_a_bundle() {
+        use crate::solana::lookup_tables::ExtendedAddressLookupTableProvider;
+        use solana_sdk::address_lookup_table::instruction::{
+            create_lookup_table, extend_lookup_table,
+        };
+        use solana_sdk::message::Message;
+
+        // The first transaction of the bundle creates a table and fills it
+        let owner = Pubkey::new_from_array([1; 32]);
+        let (create, table) = create_lookup_table(owner, owner, 123);
+        let extend = extend_lookup_table(table, owner, Some(owner), table_addresses(7));
+        let setup = Message::new_with_blockhash(
+            &[create, extend],
+            Some(&owner),
+            &Hash::new_from_array([7; 32]),
+        );
+        let setup_metadata = parse_transaction(hex::encode(setup.serialize()), false, None)
+            .unwrap()
+            .solana_parsed_transaction
+            .payload
+            .unwrap()
+            .transaction_metadata
+            .unwrap();
+        let mut provider = ExtendedAddressLookupTableProvider::new(None);
+        provider.record_transaction(&setup_metadata);
+
+        // The second transaction loads its token accounts from the new table
+        let message = v0::Message {
+            header: MessageHeader {
+                num_required_signatures: 1,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![owner, Pubkey::from_str_const(TOKEN_PROGRAM_KEY)],
+            recent_blockhash: Hash::new_from_array([7; 32]),
+            instructions: vec![CompiledInstruction {
+                program_id_index: 1,
+                accounts: vec![2, 3, 0],
+                data: [vec![3], 1000u64.to_le_bytes().to_vec()].concat(),
+            }],
+            address_table_lookups: vec![MessageAddressTableLookup {
+                account_key: table,
+                writable_indexes: vec![5, 6],
+                readonly_indexes: vec![],
+            }],
+        };
+        let transfers = parse_transaction_with_lookup_tables(
+            hex::encode(VersionedMessage::V0(message).serialize()),
+            TransactionEncoding::Hex,
+            false,
+            None,
+            Some(&provider),
+        )
+        .unwrap()
+        .solana_parsed_transaction
+        .payload
+        .unwrap()
+        .transaction_metadata
+        .unwrap()
+        .spl_transfers;
+        assert_eq!(
+            transfers[0].from,
+            Pubkey::new_from_array([5; 32]).to_string()
+        );
+        assert_eq!(transfers[0].to, Pubkey::new_from_array([6; 32]).to_string());
+
+        // Extensions of tables that already exist are appended to the base provider's contents
+        let existing_table = Pubkey::new_from_array([3; 32]);
+        let base = HashMap::from([(
+            existing_table.to_string(),
+            AddressLookupTableContents::from_addresses(
+                table_addresses(2).iter().map(Pubkey::to_string).collect(),
+            ),
+        )]);
+        let extend = extend_lookup_table(
+            existing_table,
+            owner,
+            None,
+            vec![Pubkey::new_from_array([2; 32])],
+        );
+        let extend_message =
+            Message::new_with_blockhash(&[extend], Some(&owner), &Hash::new_from_array([7; 32]));
+        let extend_metadata =
+            parse_transaction(hex::encode(extend_message.serialize()), false, None)
+                .unwrap()
+                .solana_parsed_transaction
+                .payload
+                .unwrap()
+                .transaction_metadata
+                .unwrap();
+
+        let mut extended = ExtendedAddressLookupTableProvider::new(Some(&base));
+        extended.record_transaction(&extend_metadata);
+        assert_eq!(
+            extended.lookup_table(&existing_table.to_string()).unwrap(),
+            Some(AddressLookupTableContents::from_addresses(
+                table_addresses(3).iter().map(Pubkey::to_string).collect()
+            ))
+        );
+
+        // Without a base provider, a table that was only extended has unknown leading addresses
+        let mut unknown = ExtendedAddressLookupTableProvider::new(None);
+        unknown.record_transaction(&extend_metadata);
+        assert_eq!(
+            unknown.lookup_table(&existing_table.to_string()).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn skips_lookup_tables_loaded_through_unresolved_lookups() {
+        use crate::solana::lookup_tables::ExtendedAddressLookupTableProvider;
+        use solana_sdk::address_lookup_table::{
+            instruction::{create_lookup_table, extend_lookup_table},
+            AddressLookupTableAccount,
+        };
+
+        // The created and extended table is itself loaded from another table, which is not supplied
+        let owner = Pubkey::new_from_array([1; 32]);
+        let (create, table) = create_lookup_table(owner, owner, 123);
+        let extend = extend_lookup_table(table, owner, Some(owner), table_addresses(7));
+        let message = v0::Message::try_compile(
+            &owner,
+            &[create, extend],
+            &[AddressLookupTableAccount {
+                key: Pubkey::new_from_array([3; 32]),
+                addresses: vec![table],
+            }],
+            Hash::new_from_array([7; 32]),
+        )
+        .unwrap();
+        let metadata = parse_transaction(
+            hex::encode(VersionedMessage::V0(message).serialize()),
+            false,
+            None,
+        )
+        .unwrap()
+        .solana_parsed_transaction
+        .payload
+        .unwrap()
+        .transaction_metadata
+        .unwrap();
+        let placeholder = metadata.instructions[0]
+            .parsed_instruction
+            .as_ref()
+            .unwrap()
+            .named_accounts["lookup_table_account"]
+            .clone();
+        assert_eq!(placeholder, "ADDRESS_TABLE_LOOKUP");
+
+        let mut provider = ExtendedAddressLookupTableProvider::new(None);
+        provider.record_transaction(&metadata);
+        assert_eq!(provider.lookup_table(&placeholder).unwrap(), None);
+        assert_eq!(provider.lookup_table(&table.to_string()).unwrap(), None);
+    }
+}
+
+#[cfg(test)]
+mod native_program_tests {
+    use super::*;
+    use solana_sdk::{
+        hash::Hash,
+        instruction::{AccountMeta, Instruction},
+        message::Message,
+        pubkey::Pubkey,
+        system_instruction,
+    };
+
+    const PAYER: Pubkey = Pubkey::new_from_array([1; 32]);
+    const TOKEN_PROGRAM: Pubkey = Pubkey::from_str_const(TOKEN_PROGRAM_KEY);
+    const TOKEN_2022_PROGRAM: Pubkey = Pubkey::from_str_const(TOKEN_2022_PROGRAM_KEY);
+    const ZK_ELGAMAL_PROOF_PROGRAM: Pubkey = Pubkey::from_str_const(ZK_ELGAMAL_PROOF_PROGRAM_KEY);
+
+    fn key(seed: u8) -> Pubkey {
+        Pubkey::new_from_array([seed; 32])
+    }
+
+    // An instruction whose accounts are all writable, with PAYER as the only signer
+    fn instruction(program_id: Pubkey, data: Vec<u8>, accounts: &[Pubkey]) -> Instruction {
+        Instruction {
+            program_id,
+            accounts: accounts
+                .iter()
+                .map(|k| AccountMeta::new(*k, *k == PAYER))
+                .collect(),
+            data,
+        }
+    }
+
+    fn args(parsed: &SolanaParsedInstructionData) -> Value {
+        Value::Object(parsed.program_call_args.clone())
+    }
+
+    // Compile the instructions into a legacy message paid for by PAYER and parse it
+    fn parse_instructions(instructions: &[Instruction]) -> SolanaMetadata {
+        let message =
+            Message::new_with_blockhash(instructions, Some(&PAYER), &Hash::new_from_array([7; 32]));
+        parse_transaction(hex::encode(message.serialize()), false, None)
+            .unwrap()
+            .solana_parsed_transaction
+            .payload
+            .unwrap()
+            .transaction_metadata
+            .unwrap()
+    }
+
+    fn parsed(instruction: &SolanaInstruction) -> &SolanaParsedInstructionData {
+        instruction
+            .parsed_instruction
+            .as_ref()
+            .expect("instruction should be decoded")
+    }
+
+    fn named(parsed: &SolanaParsedInstructionData, names: &[(&str, Pubkey)]) -> bool {
+        parsed.named_accounts
+            == names
+                .iter()
+                .map(|(name, key)| (name.to_string(), key.to_string()))
+                .collect::<HashMap<String, String>>()
+    }
+
+    #[test]
+    fn decodes_system_account_creation() {
+        let new_account = Pubkey::new_from_array([2; 32]);
+        let base = Pubkey::new_from_array([3; 32]);
+        let owner = Pubkey::new_from_array([4; 32]);
+        let seeded = Pubkey::create_with_seed(&base, "vault", &owner).unwrap();
+        let metadata = parse_instructions(&[
+            system_instruction::create_account(&PAYER, &new_account, 1_461_600, 82, &owner),
+            system_instruction::create_account_with_seed(
+                &PAYER, &seeded, &base, "vault", 2_039_280, 165, &owner,
+            ),
+            system_instruction::allocate(&new_account, 200),
+            system_instruction::assign(&new_account, &owner),
+        ]);
+
+        let create = parsed(&metadata.instructions[0]);
+        assert_eq!(create.instruction_name, "CreateAccount");
+        assert_eq!(create.discriminator, "00000000");
+        assert_eq!(create.idl_source, IdlSource::Native(NativeProgram::System));
+        assert!(named(
+            create,
+            &[("funding_account", PAYER), ("new_account", new_account)]
+        ));
+        assert_eq!(
+            Value::Object(create.program_call_args.clone()),
+            serde_json::json!({ "lamports": 1_461_600, "space": 82, "owner": owner.to_string() })
+        );
+
+        // the base account differs from the funding account, so it is included as the third account
+        let create_with_seed = parsed(&metadata.instructions[1]);
+        assert_eq!(create_with_seed.instruction_name, "CreateAccountWithSeed");
+        assert!(named(
+            create_with_seed,
+            &[
+                ("funding_account", PAYER),
+                ("created_account", seeded),
+                ("base_account", base)
+            ]
+        ));
+        assert_eq!(create_with_seed.program_call_args["seed"], "vault");
+        assert_eq!(create_with_seed.program_call_args["base"], base.to_string());
+
+        let allocate = parsed(&metadata.instructions[2]);
+        assert_eq!(allocate.instruction_name, "Allocate");
+        assert_eq!(allocate.program_call_args["space"], 200);
+
+        let assign = parsed(&metadata.instructions[3]);
+        assert_eq!(assign.instruction_name, "Assign");
+        assert!(named(assign, &[("assigned_account", new_account)]));
+        assert_eq!(assign.program_call_args["owner"], owner.to_string());
+        assert!(metadata.transfers.is_empty());
+    }
+
+    #[test]
+    fn decodes_system_seeded_instructions() {
+        let base = Pubkey::new_from_array([3; 32]);
+        let owner = Pubkey::new_from_array([4; 32]);
+        let recipient = Pubkey::new_from_array([5; 32]);
+        let seeded = Pubkey::create_with_seed(&base, "stash", &owner).unwrap();
+        let metadata = parse_instructions(&[
+            system_instruction::allocate_with_seed(&seeded, &base, "stash", 64, &owner),
+            system_instruction::assign_with_seed(&seeded, &base, "stash", &owner),
+            system_instruction::transfer_with_seed(
+                &seeded,
+                &base,
+                "stash".to_string(),
+                &owner,
+                &recipient,
+                5_000,
+            ),
+        ]);
+
+        let names: Vec<&str> = metadata
+            .instructions
+            .iter()
+            .map(|i| parsed(i).instruction_name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["AllocateWithSeed", "AssignWithSeed", "TransferWithSeed"]
+        );
+        let transfer_with_seed = parsed(&metadata.instructions[2]);
+        assert!(named(
+            transfer_with_seed,
+            &[
+                ("funding_account", seeded),
+                ("base_account", base),
+                ("recipient_account", recipient)
+            ]
+        ));
+        assert_eq!(
+            Value::Object(transfer_with_seed.program_call_args.clone()),
+            serde_json::json!({ "lamports": 5_000, "from_seed": "stash", "from_owner": owner.to_string() })
+        );
+    }
+
+    #[test]
+    fn decodes_system_nonce_instructions() {
+        let nonce = Pubkey::new_from_array([2; 32]);
+        let authority = Pubkey::new_from_array([3; 32]);
+        let new_authority = Pubkey::new_from_array([4; 32]);
+        let recipient = Pubkey::new_from_array([5; 32]);
+        let mut instructions =
+            system_instruction::create_nonce_account(&PAYER, &nonce, &authority, 1_447_680);
+        instructions.extend([
+            system_instruction::advance_nonce_account(&nonce, &authority),
+            system_instruction::withdraw_nonce_account(&nonce, &authority, &recipient, 1_000),
+            system_instruction::authorize_nonce_account(&nonce, &authority, &new_authority),
+            system_instruction::upgrade_nonce_account(nonce),
+        ]);
+        let metadata = parse_instructions(&instructions);
+
+        let names: Vec<&str> = metadata
+            .instructions
+            .iter()
+            .map(|i| parsed(i).instruction_name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "CreateAccount",
+                "InitializeNonceAccount",
+                "AdvanceNonceAccount",
+                "WithdrawNonceAccount",
+                "AuthorizeNonceAccount",
+                "UpgradeNonceAccount"
+            ]
+        );
+        assert_eq!(
+            parsed(&metadata.instructions[1]).program_call_args["nonce_authority"],
+            authority.to_string()
+        );
+        assert_eq!(
+            parsed(&metadata.instructions[2]).named_accounts["nonce_authority"],
+            authority.to_string()
+        );
+        let withdraw = parsed(&metadata.instructions[3]);
+        assert_eq!(
+            withdraw.named_accounts["recipient_account"],
+            recipient.to_string()
+        );
+        assert_eq!(withdraw.program_call_args["lamports"], 1_000);
+        let authorize = parsed(&metadata.instructions[4]);
+        assert!(named(
+            authorize,
+            &[("nonce_account", nonce), ("nonce_authority", authority)]
+        ));
+        assert_eq!(
+            authorize.program_call_args["new_nonce_authority"],
+            new_authority.to_string()
+        );
+    }
+
+    #[test]
+    fn reports_system_instruction_missing_accounts() {
+        let mut instruction =
+            system_instruction::advance_nonce_account(&Pubkey::new_from_array([2; 32]), &PAYER);
+        instruction.accounts.truncate(2);
+        let metadata = parse_instructions(&[instruction]);
+        assert_eq!(metadata.instructions[0].parsed_instruction, None);
+        assert_eq!(
+            metadata.instructions[0].instruction_parse_error,
+            Some(InstructionParseError::NotEnoughAccounts {
+                instruction_name: "AdvanceNonceAccount".to_string(),
+                expected: 3,
+                found: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn decodes_token_mint_and_account_initialization() {
+        let mint = key(2);
+        let account = key(3);
+        let owner = key(4);
+        let rent = solana_sdk::sysvar::rent::id();
+        let mint_data = |tag: u8, freeze_authority: Option<Pubkey>| {
+            let mut data = vec![tag, 6];
+            data.extend_from_slice(PAYER.as_ref());
+            match freeze_authority {
+                Some(k) => {
+                    data.push(1);
+                    data.extend_from_slice(k.as_ref());
+                }
+                None => data.push(0),
+            }
+            data
+        };
+        let metadata = parse_instructions(&[
+            instruction(TOKEN_PROGRAM, mint_data(0, Some(owner)), &[mint, rent]),
+            instruction(TOKEN_PROGRAM, mint_data(20, None), &[mint]),
+            instruction(TOKEN_PROGRAM, vec![1], &[account, mint, owner, rent]),
+            instruction(
+                TOKEN_PROGRAM,
+                [vec![18], owner.to_bytes().to_vec()].concat(),
+                &[account, mint],
+            ),
+            instruction(
+                TOKEN_PROGRAM,
+                vec![2, 2],
+                &[key(5), rent, key(6), key(7), key(8)],
+            ),
+            instruction(TOKEN_PROGRAM, vec![22], &[account]),
+        ]);
+
+        let initialize_mint = parsed(&metadata.instructions[0]);
+        assert_eq!(initialize_mint.instruction_name, "InitializeMint");
+        assert_eq!(initialize_mint.discriminator, "00");
+        assert_eq!(
+            initialize_mint.idl_source,
+            IdlSource::Native(NativeProgram::Token)
+        );
+        assert!(named(
+            initialize_mint,
+            &[("mint", mint), ("rent_sysvar", rent)]
+        ));
+        assert_eq!(
+            args(initialize_mint),
+            serde_json::json!({
+                "decimals": 6,
+                "mint_authority": PAYER.to_string(),
+                "freeze_authority": owner.to_string(),
+            })
+        );
+
+        let initialize_mint_2 = parsed(&metadata.instructions[1]);
+        assert_eq!(initialize_mint_2.instruction_name, "InitializeMint2");
+        assert_eq!(
+            initialize_mint_2.program_call_args["freeze_authority"],
+            Value::Null
+        );
+
+        let initialize_account = parsed(&metadata.instructions[2]);
+        assert_eq!(initialize_account.instruction_name, "InitializeAccount");
+        assert_eq!(
+            initialize_account.named_accounts["owner"],
+            owner.to_string()
+        );
+
+        let initialize_account_3 = parsed(&metadata.instructions[3]);
+        assert_eq!(initialize_account_3.instruction_name, "InitializeAccount3");
+        assert_eq!(
+            initialize_account_3.program_call_args["owner"],
+            owner.to_string()
+        );
+
+        let initialize_multisig = parsed(&metadata.instructions[4]);
+        assert_eq!(initialize_multisig.instruction_name, "InitializeMultisig");
+        assert!(named(
+            initialize_multisig,
+            &[
+                ("multisig", key(5)),
+                ("rent_sysvar", rent),
+                ("signer_1", key(6)),
+                ("signer_2", key(7)),
+                ("signer_3", key(8))
+            ]
+        ));
+        assert_eq!(initialize_multisig.program_call_args["m"], 2);
+
+        assert_eq!(
+            parsed(&metadata.instructions[5]).instruction_name,
+            "InitializeImmutableOwner"
+        );
+    }
+
+    #[test]
+    fn decodes_token_delegation_and_authority_changes() {
+        let source = key(2);
+        let delegate = key(3);
+        let mint = key(4);
+        let new_owner = key(5);
+        let amount = 1_000_000u64.to_le_bytes().to_vec();
+        let metadata = parse_instructions(&[
+            // approve with a 2 of n multisig owner
+            instruction(
+                TOKEN_PROGRAM,
+                [vec![4], amount.clone()].concat(),
+                &[source, delegate, key(9), PAYER, key(10)],
+            ),
+            instruction(
+                TOKEN_PROGRAM,
+                [vec![13], amount.clone(), vec![6]].concat(),
+                &[source, mint, delegate, PAYER],
+            ),
+            instruction(TOKEN_PROGRAM, vec![5], &[source, PAYER]),
+            instruction(
+                TOKEN_PROGRAM,
+                [vec![6, 2, 1], new_owner.to_bytes().to_vec()].concat(),
+                &[source, PAYER],
+            ),
+            instruction(TOKEN_PROGRAM, vec![6, 0, 0], &[mint, PAYER]),
+        ]);
+
+        let approve = parsed(&metadata.instructions[0]);
+        assert_eq!(approve.instruction_name, "Approve");
+        assert!(named(
+            approve,
+            &[
+                ("source", source),
+                ("delegate", delegate),
+                ("owner", key(9)),
+                ("signer_1", PAYER),
+                ("signer_2", key(10))
+            ]
+        ));
+        assert_eq!(approve.program_call_args["amount"], 1_000_000);
+
+        let approve_checked = parsed(&metadata.instructions[1]);
+        assert_eq!(approve_checked.instruction_name, "ApproveChecked");
+        assert_eq!(
+            args(approve_checked),
+            serde_json::json!({ "amount": 1_000_000, "decimals": 6 })
+        );
+        assert_eq!(approve_checked.named_accounts["mint"], mint.to_string());
+
+        let revoke = parsed(&metadata.instructions[2]);
+        assert_eq!(revoke.instruction_name, "Revoke");
+        assert!(named(revoke, &[("source", source), ("owner", PAYER)]));
+
+        let set_owner = parsed(&metadata.instructions[3]);
+        assert_eq!(set_owner.instruction_name, "SetAuthority");
+        assert_eq!(
+            args(set_owner),
+            serde_json::json!({ "authority_type": "AccountOwner", "new_authority": new_owner.to_string() })
+        );
+        assert!(named(
+            set_owner,
+            &[("account", source), ("current_authority", PAYER)]
+        ));
+
+        // removing the mint authority fixes the supply forever
+        let remove_mint_authority = parsed(&metadata.instructions[4]);
+        assert_eq!(
+            args(remove_mint_authority),
+            serde_json::json!({ "authority_type": "MintTokens", "new_authority": null })
+        );
+    }
+
+    #[test]
+    fn decodes_token_supply_and_account_lifecycle() {
+        let mint = key(2);
+        let account = key(3);
+        let destination = key(4);
+        let amount = 500u64.to_le_bytes().to_vec();
+        let metadata = parse_instructions(&[
+            instruction(
+                TOKEN_PROGRAM,
+                [vec![7], amount.clone()].concat(),
+                &[mint, account, PAYER],
+            ),
+            instruction(
+                TOKEN_PROGRAM,
+                [vec![14], amount.clone(), vec![9]].concat(),
+                &[mint, account, PAYER],
+            ),
+            instruction(
+                TOKEN_PROGRAM,
+                [vec![8], amount.clone()].concat(),
+                &[account, mint, PAYER],
+            ),
+            instruction(
+                TOKEN_PROGRAM,
+                [vec![15], amount.clone(), vec![9]].concat(),
+                &[account, mint, PAYER],
+            ),
+            instruction(TOKEN_PROGRAM, vec![10], &[account, mint, PAYER]),
+            instruction(TOKEN_PROGRAM, vec![11], &[account, mint, PAYER]),
+            instruction(TOKEN_PROGRAM, vec![9], &[account, destination, PAYER]),
+            instruction(TOKEN_PROGRAM, vec![17], &[account]),
+            instruction(TOKEN_PROGRAM, [vec![23], amount].concat(), &[mint]),
+            instruction(
+                TOKEN_PROGRAM,
+                [vec![24], b"0.0005".to_vec()].concat(),
+                &[mint],
+            ),
+        ]);
+
+        let names: Vec<&str> = metadata
+            .instructions
+            .iter()
+            .map(|i| parsed(i).instruction_name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "MintTo",
+                "MintToChecked",
+                "Burn",
+                "BurnChecked",
+                "FreezeAccount",
+                "ThawAccount",
+                "CloseAccount",
+                "SyncNative",
+                "AmountToUiAmount",
+                "UiAmountToAmount"
+            ]
+        );
+        assert!(named(
+            parsed(&metadata.instructions[0]),
+            &[
+                ("mint", mint),
+                ("destination", account),
+                ("mint_authority", PAYER)
+            ]
+        ));
+        assert_eq!(
+            args(parsed(&metadata.instructions[3])),
+            serde_json::json!({ "amount": 500, "decimals": 9 })
+        );
+        assert_eq!(
+            parsed(&metadata.instructions[4]).named_accounts["freeze_authority"],
+            PAYER.to_string()
+        );
+        assert_eq!(
+            parsed(&metadata.instructions[6]).named_accounts["destination"],
+            destination.to_string()
+        );
+        assert_eq!(
+            parsed(&metadata.instructions[9]).program_call_args["ui_amount"],
+            "0.0005"
+        );
+        assert!(metadata.spl_transfers.is_empty());
+    }
+
+    #[test]
+    fn reports_undecodable_token_instructions() {
+        let account = key(3);
+        let metadata = parse_instructions(&[
+            instruction(TOKEN_PROGRAM, vec![99], &[account]),
+            instruction(TOKEN_PROGRAM, vec![6, 2, 1, 7], &[account, PAYER]),
+            instruction(TOKEN_PROGRAM, vec![6, 42, 0], &[account, PAYER]),
+            // the pausable extension is not decoded
+            instruction(TOKEN_2022_PROGRAM, vec![44, 1], &[account]),
+            // TransferCheckedWithFee is a Token 2022 extension instruction, which the Token program does not have
+            instruction(
+                TOKEN_PROGRAM,
+                [
+                    vec![26, 1],
+                    1000u64.to_le_bytes().to_vec(),
+                    vec![6],
+                    10u64.to_le_bytes().to_vec(),
+                ]
+                .concat(),
+                &[account, key(4), key(5), PAYER],
+            ),
+        ]);
+
+        assert_eq!(
+            metadata.instructions[0].instruction_parse_error,
+            Some(InstructionParseError::DataParseError {
+                program_name: "Token Program".to_string(),
+                error: "unknown token instruction tag 99".to_string(),
+            })
+        );
+        assert_eq!(
+            metadata.instructions[1].instruction_parse_error,
+            Some(InstructionParseError::DataParseError {
+                program_name: "Token Program".to_string(),
+                error: "error while parsing SetAuthority instruction -- new_authority".to_string(),
+            })
+        );
+        assert!(matches!(
+            &metadata.instructions[2].instruction_parse_error,
+            Some(InstructionParseError::DataParseError { error, .. }) if error.contains("unknown authority type 42")
+        ));
+        assert_eq!(metadata.instructions[3].instruction_parse_error, None);
+        assert_eq!(metadata.instructions[3].parsed_instruction, None);
+
+        // an instruction that fails to decode is not reported as a transfer
+        assert_eq!(
+            metadata.instructions[4].instruction_parse_error,
+            Some(InstructionParseError::DataParseError {
+                program_name: "Token Program".to_string(),
+                error: "unknown token instruction tag 26".to_string(),
+            })
+        );
+        assert!(metadata.spl_transfers.is_empty());
+    }
+
+    #[test]
+    fn decodes_token_2022_transfer_fee_instructions() {
+        let mint = key(2);
+        let destination = key(3);
+        let authority = key(4);
+        let fee_config = [
+            vec![26, 0, 1],
+            authority.to_bytes().to_vec(),
+            vec![0],
+            50u16.to_le_bytes().to_vec(),
+            5_000u64.to_le_bytes().to_vec(),
+        ]
+        .concat();
+        let metadata = parse_instructions(&[
+            instruction(TOKEN_2022_PROGRAM, fee_config, &[mint]),
+            instruction(
+                TOKEN_2022_PROGRAM,
+                [
+                    vec![26, 1],
+                    1_000u64.to_le_bytes().to_vec(),
+                    vec![6],
+                    5u64.to_le_bytes().to_vec(),
+                ]
+                .concat(),
+                &[key(5), mint, destination, PAYER],
+            ),
+            // withdraw from two accounts with a multisig authority signed by PAYER
+            instruction(
+                TOKEN_2022_PROGRAM,
+                vec![26, 3, 2],
+                &[mint, destination, authority, PAYER, key(6), key(7)],
+            ),
+            instruction(TOKEN_2022_PROGRAM, vec![26, 4], &[mint, key(6), key(7)]),
+            instruction(
+                TOKEN_2022_PROGRAM,
+                [
+                    vec![26, 5],
+                    75u16.to_le_bytes().to_vec(),
+                    0u64.to_le_bytes().to_vec(),
+                ]
+                .concat(),
+                &[mint, PAYER],
+            ),
+        ]);
+
+        let initialize = parsed(&metadata.instructions[0]);
+        assert_eq!(initialize.instruction_name, "InitializeTransferFeeConfig");
+        assert_eq!(initialize.discriminator, "1a00");
+        assert_eq!(
+            initialize.idl_source,
+            IdlSource::Native(NativeProgram::Token2022)
+        );
+        assert_eq!(
+            args(initialize),
+            serde_json::json!({
+                "transfer_fee_config_authority": authority.to_string(),
+                "withdraw_withheld_authority": null,
+                "transfer_fee_basis_points": 50,
+                "maximum_fee": 5_000,
+            })
+        );
+
+        let transfer = parsed(&metadata.instructions[1]);
+        assert_eq!(transfer.instruction_name, "TransferCheckedWithFee");
+        assert_eq!(
+            args(transfer),
+            serde_json::json!({ "amount": 1_000, "decimals": 6, "fee": 5 })
+        );
+        assert_eq!(metadata.spl_transfers.len(), 1);
+
+        let withdraw = parsed(&metadata.instructions[2]);
+        assert_eq!(
+            withdraw.instruction_name,
+            "WithdrawWithheldTokensFromAccounts"
+        );
+        assert!(named(
+            withdraw,
+            &[
+                ("mint", mint),
+                ("destination", destination),
+                ("withdraw_withheld_authority", authority),
+                ("signer_1", PAYER),
+                ("source_1", key(6)),
+                ("source_2", key(7))
+            ]
+        ));
+        assert_eq!(withdraw.named_accounts.len(), 6);
+
+        let harvest = parsed(&metadata.instructions[3]);
+        assert_eq!(harvest.instruction_name, "HarvestWithheldTokensToMint");
+        assert!(named(
+            harvest,
+            &[("mint", mint), ("source_1", key(6)), ("source_2", key(7))]
+        ));
+
+        let set_fee = parsed(&metadata.instructions[4]);
+        assert_eq!(set_fee.instruction_name, "SetTransferFee");
+        assert!(named(
+            set_fee,
+            &[("mint", mint), ("transfer_fee_config_authority", PAYER)]
+        ));
+        assert_eq!(
+            args(set_fee),
+            serde_json::json!({ "transfer_fee_basis_points": 75, "maximum_fee": 0 })
+        );
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn decodes_token_2022_extension_instructions() {
+        let mint = key(2);
+        let account = key(3);
+        let hook_program = key(4);
+        let metadata_address = key(5);
+        let metadata = parse_instructions(&[
+            instruction(TOKEN_2022_PROGRAM, vec![28, 0, 2], &[mint]),
+            instruction(TOKEN_2022_PROGRAM, vec![28, 1, 1], &[mint, PAYER]),
+            instruction(
+                TOKEN_2022_PROGRAM,
+                [
+                    vec![29],
+                    7u16.to_le_bytes().to_vec(),
+                    8u16.to_le_bytes().to_vec(),
+                ]
+                .concat(),
+                &[account, PAYER, key(9), PAYER],
+            ),
+            instruction(TOKEN_2022_PROGRAM, vec![30, 0], &[account, PAYER]),
+            instruction(
+                TOKEN_2022_PROGRAM,
+                [
+                    vec![33, 0],
+                    [0; 32].to_vec(),
+                    (-25i16).to_le_bytes().to_vec(),
+                ]
+                .concat(),
+                &[mint],
+            ),
+            instruction(TOKEN_2022_PROGRAM, vec![34, 1], &[account, PAYER]),
+            instruction(
+                TOKEN_2022_PROGRAM,
+                [vec![35], PAYER.to_bytes().to_vec()].concat(),
+                &[mint],
+            ),
+            instruction(
+                TOKEN_2022_PROGRAM,
+                [vec![36, 1], hook_program.to_bytes().to_vec()].concat(),
+                &[mint, PAYER],
+            ),
+            instruction(
+                TOKEN_2022_PROGRAM,
+                [
+                    vec![39, 0],
+                    PAYER.to_bytes().to_vec(),
+                    metadata_address.to_bytes().to_vec(),
+                ]
+                .concat(),
+                &[mint],
+            ),
+            instruction(
+                TOKEN_2022_PROGRAM,
+                [vec![40, 1], [0; 32].to_vec()].concat(),
+                &[mint, PAYER],
+            ),
+        ]);
+
+        let default_state = parsed(&metadata.instructions[0]);
+        assert_eq!(
+            default_state.instruction_name,
+            "InitializeDefaultAccountState"
+        );
+        assert_eq!(default_state.program_call_args["state"], "Frozen");
+        let update_state = parsed(&metadata.instructions[1]);
+        assert_eq!(update_state.instruction_name, "UpdateDefaultAccountState");
+        assert!(named(
+            update_state,
+            &[("mint", mint), ("freeze_authority", PAYER)]
+        ));
+
+        let reallocate = parsed(&metadata.instructions[2]);
+        assert_eq!(reallocate.instruction_name, "Reallocate");
+        assert!(named(
+            reallocate,
+            &[
+                ("account", account),
+                ("payer", PAYER),
+                ("system_program", key(9)),
+                ("owner", PAYER)
+            ]
+        ));
+        assert_eq!(
+            reallocate.program_call_args["extension_types"],
+            serde_json::json!([7, 8])
+        );
+
+        let memo = parsed(&metadata.instructions[3]);
+        assert_eq!(memo.instruction_name, "EnableRequiredMemoTransfers");
+        assert_eq!(memo.discriminator, "1e00");
+        assert!(named(memo, &[("account", account), ("owner", PAYER)]));
+
+        let interest = parsed(&metadata.instructions[4]);
+        assert_eq!(interest.instruction_name, "InitializeInterestBearingMint");
+        assert_eq!(
+            args(interest),
+            serde_json::json!({ "rate_authority": null, "rate": -25 })
+        );
+
+        assert_eq!(
+            parsed(&metadata.instructions[5]).instruction_name,
+            "DisableCpiGuard"
+        );
+
+        let permanent_delegate = parsed(&metadata.instructions[6]);
+        assert_eq!(
+            permanent_delegate.instruction_name,
+            "InitializePermanentDelegate"
+        );
+        assert_eq!(
+            permanent_delegate.program_call_args["delegate"],
+            PAYER.to_string()
+        );
+
+        let transfer_hook = parsed(&metadata.instructions[7]);
+        assert_eq!(transfer_hook.instruction_name, "UpdateTransferHook");
+        assert!(named(
+            transfer_hook,
+            &[("mint", mint), ("authority", PAYER)]
+        ));
+        assert_eq!(
+            transfer_hook.program_call_args["program_id"],
+            hook_program.to_string()
+        );
+
+        let metadata_pointer = parsed(&metadata.instructions[8]);
+        assert_eq!(
+            metadata_pointer.instruction_name,
+            "InitializeMetadataPointer"
+        );
+        assert_eq!(
+            args(metadata_pointer),
+            serde_json::json!({
+                "authority": PAYER.to_string(),
+                "metadata_address": metadata_address.to_string(),
+            })
+        );
+
+        let group_pointer = parsed(&metadata.instructions[9]);
+        assert_eq!(group_pointer.instruction_name, "UpdateGroupPointer");
+        assert_eq!(group_pointer.discriminator, "2801");
+        assert_eq!(
+            args(group_pointer),
+            serde_json::json!({ "group_address": null })
+        );
+    }
+
+    #[test]
+    fn reports_undecodable_token_2022_extension_instructions() {
+        let mint = key(2);
+        let metadata = parse_instructions(&[
+            instruction(TOKEN_2022_PROGRAM, vec![26, 9], &[mint]),
+            instruction(TOKEN_2022_PROGRAM, vec![30], &[key(3)]),
+            instruction(TOKEN_2022_PROGRAM, vec![28, 0, 7], &[mint]),
+            instruction(
+                TOKEN_2022_PROGRAM,
+                vec![26, 3, 2],
+                &[mint, key(3), PAYER, key(4)],
+            ),
+            instruction(TOKEN_2022_PROGRAM, vec![200], &[mint]),
+        ]);
+
+        let errors: Vec<Option<InstructionParseError>> = metadata
+            .instructions
+            .iter()
+            .map(|i| i.instruction_parse_error.clone())
+            .collect();
+        let data_error = |error: &str| {
+            Some(InstructionParseError::DataParseError {
+                program_name: "Token 2022 Program".to_string(),
+                error: error.to_string(),
+            })
+        };
+        assert_eq!(
+            errors,
+            vec![
+                data_error("unknown TransferFee extension instruction tag 9"),
+                data_error("error while parsing MemoTransfer extension instruction -- instruction tag"),
+                data_error("error while parsing InitializeDefaultAccountState instruction -- unknown account state 7"),
+                Some(InstructionParseError::NotEnoughAccounts {
+                    instruction_name: "WithdrawWithheldTokensFromAccounts".to_string(),
+                    expected: 5,
+                    found: 4,
+                }),
+                data_error("unknown token 2022 instruction tag 200"),
+            ]
+        );
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn locates_confidential_transfer_proofs() {
+        let account = key(2);
+        let mint = key(3);
+        let destination = key(4);
+        let record = key(5);
+        let sysvar = solana_sdk::sysvar::instructions::id();
+        let (equality_context, validity_context, range_context) = (key(6), key(7), key(8));
+        let withdraw = [
+            vec![27, 6],
+            250u64.to_le_bytes().to_vec(),
+            vec![2],
+            vec![9; 36],
+            vec![(-2i8) as u8, (-1i8) as u8],
+        ]
+        .concat();
+        let transfer = [vec![27, 7], vec![9; 36 + 64 + 64], vec![0, 0, 0]].concat();
+        let metadata = parse_instructions(&[
+            // the equality proof is in the instruction data, the range proof is in a record account
+            instruction(
+                ZK_ELGAMAL_PROOF_PROGRAM,
+                [vec![3], vec![1; 192]].concat(),
+                &[],
+            ),
+            instruction(
+                ZK_ELGAMAL_PROOF_PROGRAM,
+                [vec![6], 8u32.to_le_bytes().to_vec()].concat(),
+                &[record],
+            ),
+            instruction(
+                TOKEN_2022_PROGRAM,
+                withdraw,
+                &[account, mint, sysvar, record, PAYER],
+            ),
+            // every proof was verified into a context state account ahead of time
+            instruction(
+                TOKEN_2022_PROGRAM,
+                transfer,
+                &[
+                    account,
+                    mint,
+                    destination,
+                    equality_context,
+                    validity_context,
+                    range_context,
+                    PAYER,
+                ],
+            ),
+            instruction(
+                ZK_ELGAMAL_PROOF_PROGRAM,
+                vec![0],
+                &[equality_context, account, PAYER],
+            ),
+        ]);
+
+        let equality = parsed(&metadata.instructions[0]);
+        assert_eq!(
+            equality.instruction_name,
+            "VerifyCiphertextCommitmentEquality"
+        );
+        assert_eq!(
+            equality.idl_source,
+            IdlSource::Native(NativeProgram::ZkElGamalProof)
+        );
+        assert_eq!(
+            args(equality),
+            serde_json::json!({
+                "proof_location": "InstructionData",
+                "proof_data_length": 192,
+                "creates_context_state": false,
+            })
+        );
+        let range = parsed(&metadata.instructions[1]);
+        assert_eq!(range.instruction_name, "VerifyBatchedRangeProofU64");
+        assert!(named(range, &[("record_account", record)]));
+        assert_eq!(range.program_call_args["record_account_offset"], 8);
+
+        let withdraw = parsed(&metadata.instructions[2]);
+        assert_eq!(withdraw.instruction_name, "ConfidentialWithdraw");
+        assert_eq!(withdraw.discriminator, "1b06");
+        assert!(named(
+            withdraw,
+            &[
+                ("account", account),
+                ("mint", mint),
+                ("instructions_sysvar", sysvar),
+                ("range_proof_record", record),
+                ("owner", PAYER)
+            ]
+        ));
+        assert_eq!(withdraw.program_call_args["amount"], 250);
+        assert_eq!(
+            withdraw.program_call_args["proofs"],
+            serde_json::json!([
+                {
+                    "proof": "equality",
+                    "verify_instruction": "VerifyCiphertextCommitmentEquality",
+                    "instruction_offset": -2,
+                    "instruction_index": 0,
+                    "proof_instruction": "VerifyCiphertextCommitmentEquality",
+                    "proof_instruction_matches": true,
+                },
+                {
+                    "proof": "range",
+                    "verify_instruction": "VerifyBatchedRangeProofU64",
+                    "instruction_offset": -1,
+                    "instruction_index": 1,
+                    "proof_instruction": "VerifyBatchedRangeProofU64",
+                    "proof_instruction_matches": true,
+                    "record_account": record.to_string(),
+                },
+            ])
+        );
+
+        let transfer = parsed(&metadata.instructions[3]);
+        assert_eq!(transfer.instruction_name, "ConfidentialTransfer");
+        assert!(named(
+            transfer,
+            &[
+                ("source", account),
+                ("mint", mint),
+                ("destination", destination),
+                ("equality_proof_context_state", equality_context),
+                ("ciphertext_validity_proof_context_state", validity_context),
+                ("range_proof_context_state", range_context),
+                ("authority", PAYER)
+            ]
+        ));
+        assert_eq!(
+            transfer.program_call_args["proofs"][2],
+            serde_json::json!({
+                "proof": "range",
+                "verify_instruction": "VerifyBatchedRangeProofU128",
+                "context_state_account": range_context.to_string(),
+            })
+        );
+
+        let close = parsed(&metadata.instructions[4]);
+        assert_eq!(close.instruction_name, "CloseContextState");
+        assert!(named(
+            close,
+            &[
+                ("context_state", equality_context),
+                ("destination", account),
+                ("context_state_owner", PAYER)
+            ]
+        ));
+    }
+
+    #[test]
+    fn flags_missing_confidential_transfer_proofs() {
+        let account = key(2);
+        let mint = key(3);
+        let sysvar = solana_sdk::sysvar::instructions::id();
+        let configure = |offset: i8| {
+            [
+                vec![27, 2],
+                vec![0; 36],
+                65_536u64.to_le_bytes().to_vec(),
+                vec![offset as u8],
+            ]
+            .concat()
+        };
+        let metadata = parse_instructions(&[
+            // the proof instruction is not the one the account configuration needs
+            instruction(
+                ZK_ELGAMAL_PROOF_PROGRAM,
+                [vec![1], vec![1; 96]].concat(),
+                &[key(6), PAYER],
+            ),
+            instruction(
+                TOKEN_2022_PROGRAM,
+                configure(-1),
+                &[account, mint, sysvar, PAYER],
+            ),
+            instruction(
+                TOKEN_2022_PROGRAM,
+                configure(5),
+                &[account, mint, sysvar, PAYER],
+            ),
+            instruction(TOKEN_2022_PROGRAM, vec![27, 4, 0], &[account]),
+        ]);
+
+        let zero_ciphertext = parsed(&metadata.instructions[0]);
+        assert_eq!(
+            zero_ciphertext.program_call_args["creates_context_state"],
+            true
+        );
+        assert!(named(
+            zero_ciphertext,
+            &[
+                ("context_state", key(6)),
+                ("context_state_authority", PAYER)
+            ]
+        ));
+
+        let configure = parsed(&metadata.instructions[1]);
+        assert_eq!(
+            configure.instruction_name,
+            "ConfigureConfidentialTransferAccount"
+        );
+        assert_eq!(
+            configure.program_call_args["proofs"][0]["proof_instruction"],
+            "VerifyZeroCiphertext"
+        );
+        assert_eq!(
+            configure.program_call_args["proofs"][0]["proof_instruction_matches"],
+            false
+        );
+
+        // an offset past the end of the transaction points at no instruction
+        let out_of_range = parsed(&metadata.instructions[2]);
+        assert_eq!(
+            out_of_range.program_call_args["proofs"][0]["instruction_index"],
+            Value::Null
+        );
+        assert_eq!(out_of_range.named_accounts["owner"], PAYER.to_string());
+
+        assert_eq!(
+            metadata.instructions[3].instruction_parse_error,
+            Some(InstructionParseError::NotEnoughAccounts {
+                instruction_name: "EmptyConfidentialTransferAccount".to_string(),
+                expected: 2,
+                found: 1,
+            })
+        );
+    }
+
+    // Instruction data of an SPL interface instruction: its discriminator followed by the borsh encoded fields
+    fn interface_data(hash_input: &str, fields: &[&[u8]]) -> Vec<u8> {
+        let discriminator =
+            crate::solana::native_programs::spl_interfaces::compute_interface_discriminator(
+                hash_input,
+            );
+        [&discriminator[..], &fields.concat()].concat()
+    }
+
+    fn borsh_string(value: &str) -> Vec<u8> {
+        [&(value.len() as u32).to_le_bytes()[..], value.as_bytes()].concat()
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn decodes_spl_interface_instructions_for_any_program() {
+        let metadata_program = key(20);
+        let hook_program = key(21);
+        let (metadata_account, mint, group) = (key(2), key(3), key(4));
+        let metadata = parse_instructions(&[
+            instruction(
+                TOKEN_2022_PROGRAM,
+                interface_data(
+                    "spl_token_metadata_interface:initialize_account",
+                    &[
+                        &borsh_string("Turnkey"),
+                        &borsh_string("TKHQ"),
+                        &borsh_string("https://turnkey.com"),
+                    ],
+                ),
+                &[mint, PAYER, mint, PAYER],
+            ),
+            instruction(
+                metadata_program,
+                interface_data(
+                    "spl_token_metadata_interface:updating_field",
+                    &[&[3], &borsh_string("website"), &borsh_string("turnkey.com")],
+                ),
+                &[metadata_account, PAYER],
+            ),
+            instruction(
+                metadata_program,
+                interface_data(
+                    "spl_token_metadata_interface:remove_key_ix",
+                    &[&[1], &borsh_string("website")],
+                ),
+                &[metadata_account, PAYER],
+            ),
+            instruction(
+                metadata_program,
+                interface_data(
+                    "spl_token_metadata_interface:update_the_authority",
+                    &[&[0; 32]],
+                ),
+                &[metadata_account, PAYER],
+            ),
+            instruction(
+                metadata_program,
+                interface_data(
+                    "spl_token_metadata_interface:emitter",
+                    &[&[1], &10u64.to_le_bytes(), &[0]],
+                ),
+                &[metadata_account],
+            ),
+            instruction(
+                TOKEN_2022_PROGRAM,
+                interface_data(
+                    "spl_token_group_interface:initialize_token_group",
+                    &[PAYER.as_ref(), &100u64.to_le_bytes()],
+                ),
+                &[group, mint, PAYER],
+            ),
+            instruction(
+                TOKEN_2022_PROGRAM,
+                interface_data("spl_token_group_interface:initialize_member", &[]),
+                &[key(5), key(6), PAYER, group, PAYER],
+            ),
+            instruction(
+                hook_program,
+                interface_data(
+                    "spl-transfer-hook-interface:execute",
+                    &[&42u64.to_le_bytes()],
+                ),
+                &[key(7), mint, key(8), PAYER, key(9), key(10)],
+            ),
+        ]);
+
+        let initialize = parsed(&metadata.instructions[0]);
+        assert_eq!(initialize.instruction_name, "Initialize");
+        assert_eq!(initialize.discriminator, "d2e11ea258b84d8d");
+        assert_eq!(
+            initialize.idl_source,
+            IdlSource::Interface(SplInterface::TokenMetadata)
+        );
+        assert_eq!(
+            args(initialize),
+            serde_json::json!({ "name": "Turnkey", "symbol": "TKHQ", "uri": "https://turnkey.com" })
+        );
+        assert!(metadata.instructions[0].instruction_parse_error.is_none());
+
+        let update_field = parsed(&metadata.instructions[1]);
+        assert_eq!(update_field.instruction_name, "UpdateField");
+        assert_eq!(
+            args(update_field),
+            serde_json::json!({ "field": { "Key": "website" }, "value": "turnkey.com" })
+        );
+        assert!(named(
+            update_field,
+            &[("metadata", metadata_account), ("update_authority", PAYER)]
+        ));
+
+        assert_eq!(
+            args(parsed(&metadata.instructions[2])),
+            serde_json::json!({ "idempotent": true, "key": "website" })
+        );
+        assert_eq!(
+            args(parsed(&metadata.instructions[3])),
+            serde_json::json!({ "new_authority": null })
+        );
+        assert_eq!(
+            args(parsed(&metadata.instructions[4])),
+            serde_json::json!({ "start": 10, "end": null })
+        );
+
+        let initialize_group = parsed(&metadata.instructions[5]);
+        assert_eq!(initialize_group.instruction_name, "InitializeGroup");
+        assert_eq!(
+            initialize_group.idl_source,
+            IdlSource::Interface(SplInterface::TokenGroup)
+        );
+        assert_eq!(
+            args(initialize_group),
+            serde_json::json!({ "update_authority": PAYER.to_string(), "max_size": 100 })
+        );
+
+        let initialize_member = parsed(&metadata.instructions[6]);
+        assert_eq!(initialize_member.instruction_name, "InitializeMember");
+        assert_eq!(initialize_member.named_accounts["group"], group.to_string());
+
+        let execute = parsed(&metadata.instructions[7]);
+        assert_eq!(execute.instruction_name, "Execute");
+        assert_eq!(execute.discriminator, "692565c54bfb661a");
+        assert!(named(
+            execute,
+            &[
+                ("source", key(7)),
+                ("mint", mint),
+                ("destination", key(8)),
+                ("authority", PAYER),
+                ("extra_account_metas", key(9)),
+                ("extra_account_1", key(10))
+            ]
+        ));
+        assert_eq!(execute.program_call_args["amount"], 42);
+    }
+
+    #[test]
+    fn reports_undecodable_spl_interface_instructions() {
+        let metadata = parse_instructions(&[
+            instruction(
+                key(20),
+                interface_data(
+                    "spl_token_metadata_interface:initialize_account",
+                    &[&borsh_string("Turnkey"), &10u32.to_le_bytes()],
+                ),
+                &[key(2), PAYER, key(3), PAYER],
+            ),
+            // the Token program does not implement any interface
+            instruction(
+                TOKEN_PROGRAM,
+                interface_data(
+                    "spl-transfer-hook-interface:execute",
+                    &[&42u64.to_le_bytes()],
+                ),
+                &[key(2)],
+            ),
+        ]);
+
+        assert_eq!(
+            metadata.instructions[0].instruction_parse_error,
+            Some(InstructionParseError::DataParseError {
+                program_name: "Token Metadata Interface".to_string(),
+                error: "error while parsing Initialize instruction -- symbol".to_string(),
+            })
+        );
+        assert_eq!(metadata.instructions[0].parsed_instruction, None);
+        assert!(matches!(
+            metadata.instructions[1].instruction_parse_error,
+            Some(InstructionParseError::DataParseError { .. })
+        ));
+    }
+
+    #[test]
+    fn decodes_associated_token_account_instructions() {
+        let ata_program = Pubkey::from_str_const(ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_KEY);
+        let system_program = Pubkey::from_str_const(SOL_SYSTEM_PROGRAM_KEY);
+        let (wallet, mint, nested_mint) = (key(2), key(3), key(4));
+        let ata = |wallet: &Pubkey, mint: &Pubkey| {
+            crate::solana::native_programs::associated_token_account::associated_token_address(
+                wallet,
+                mint,
+                &TOKEN_PROGRAM,
+            )
+        };
+        let owner_ata = ata(&wallet, &mint);
+        let nested_ata = ata(&owner_ata, &nested_mint);
+        let destination_ata = ata(&wallet, &nested_mint);
+        let metadata = parse_instructions(&[
+            // the original Create instruction has no data
+            instruction(
+                ata_program,
+                vec![],
+                &[
+                    PAYER,
+                    owner_ata,
+                    wallet,
+                    mint,
+                    system_program,
+                    TOKEN_PROGRAM,
+                ],
+            ),
+            instruction(
+                ata_program,
+                vec![2],
+                &[
+                    nested_ata,
+                    nested_mint,
+                    destination_ata,
+                    owner_ata,
+                    mint,
+                    wallet,
+                    TOKEN_PROGRAM,
+                ],
+            ),
+        ]);
+
+        let create = parsed(&metadata.instructions[0]);
+        assert_eq!(create.instruction_name, "Create");
+        assert_eq!(
+            create.idl_source,
+            IdlSource::Native(NativeProgram::AssociatedTokenAccount)
+        );
+        assert!(named(
+            create,
+            &[
+                ("payer", PAYER),
+                ("associated_token_account", owner_ata),
+                ("wallet", wallet),
+                ("mint", mint),
+                ("system_program", system_program),
+                ("token_program", TOKEN_PROGRAM)
+            ]
+        ));
+
+        let recover = parsed(&metadata.instructions[1]);
+        assert_eq!(recover.instruction_name, "RecoverNested");
+        assert_eq!(recover.discriminator, "02");
+        assert_eq!(
+            recover.named_accounts["nested_associated_token_account"],
+            nested_ata.to_string()
+        );
+        assert!(metadata.warnings.is_empty());
+    }
+
+    #[test]
+    fn flags_associated_token_accounts_that_are_not_derived() {
+        let ata_program = Pubkey::from_str_const(ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_KEY);
+        let system_program = Pubkey::from_str_const(SOL_SYSTEM_PROGRAM_KEY);
+        let (wallet, mint, attacker_account) = (key(2), key(3), key(9));
+        let expected =
+            crate::solana::native_programs::associated_token_account::associated_token_address(
+                &wallet,
+                &mint,
+                &TOKEN_2022_PROGRAM,
+            );
+        let metadata = parse_instructions(&[
+            instruction(
+                ata_program,
+                vec![1],
+                &[
+                    PAYER,
+                    attacker_account,
+                    wallet,
+                    mint,
+                    system_program,
+                    TOKEN_2022_PROGRAM,
+                ],
+            ),
+            instruction(ata_program, vec![7], &[PAYER]),
+        ]);
+
+        assert_eq!(
+            metadata.warnings,
+            vec![TransactionWarning::AssociatedTokenAccountMismatch {
+                instruction_index: 0,
+                account_name: "associated_token_account".to_string(),
+                account: attacker_account.to_string(),
+                expected: expected.to_string(),
+            }]
+        );
+        // the instruction is still decoded
+        assert_eq!(
+            parsed(&metadata.instructions[0]).instruction_name,
+            "CreateIdempotent"
+        );
+        assert_eq!(
+            metadata.instructions[1].instruction_parse_error,
+            Some(InstructionParseError::DataParseError {
+                program_name: "Associated Token Account Program".to_string(),
+                error: "unknown associated token account instruction 07".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn decodes_compute_budget_instructions_and_summarizes_fees() {
+        let compute_budget_program = Pubkey::from_str_const(COMPUTE_BUDGET_PROGRAM_KEY);
+        let metadata = parse_instructions(&[
+            instruction(compute_budget_program, vec![1, 0, 0, 4, 0], &[]),
+            instruction(compute_budget_program, vec![2, 0xe0, 0x93, 4, 0], &[]),
+            instruction(
+                compute_budget_program,
+                [vec![3], 10_001u64.to_le_bytes().to_vec()].concat(),
+                &[],
+            ),
+            instruction(compute_budget_program, vec![4, 0, 0, 1, 0], &[]),
+            system_instruction::transfer(&PAYER, &key(2), 1_000),
+        ]);
+
+        let expected = [
+            (
+                "RequestHeapFrame",
+                "01",
+                serde_json::json!({ "bytes": 262_144 }),
+            ),
+            (
+                "SetComputeUnitLimit",
+                "02",
+                serde_json::json!({ "units": 300_000 }),
+            ),
+            (
+                "SetComputeUnitPrice",
+                "03",
+                serde_json::json!({ "micro_lamports": 10_001 }),
+            ),
+            (
+                "SetLoadedAccountsDataSizeLimit",
+                "04",
+                serde_json::json!({ "bytes": 65_536 }),
+            ),
+        ];
+        for (instruction, (name, discriminator, expected_args)) in
+            metadata.instructions.iter().zip(expected)
+        {
+            let decoded = parsed(instruction);
+            assert_eq!(decoded.instruction_name, name);
+            assert_eq!(decoded.discriminator, discriminator);
+            assert_eq!(args(decoded), expected_args);
+            assert_eq!(
+                decoded.idl_source,
+                IdlSource::Native(NativeProgram::ComputeBudget)
+            );
+            assert!(decoded.named_accounts.is_empty());
+        }
+
+        // 300,000 compute units at 10,001 micro-lamports each is 3,000.3 lamports, rounded up
+        assert_eq!(
+            metadata.fee_summary,
+            SolanaFeeSummary {
+                signature_count: 1,
+                base_fee_lamports: 5_000,
+                compute_unit_limit: 300_000,
+                compute_unit_price_micro_lamports: 10_001,
+                priority_fee_lamports: 3_001,
+                total_max_fee_lamports: 8_001,
+            }
+        );
+        assert!(metadata.warnings.is_empty());
+    }
+
+    #[test]
+    fn defaults_compute_unit_limit_and_flags_duplicate_compute_budget_instructions() {
+        let compute_budget_program = Pubkey::from_str_const(COMPUTE_BUDGET_PROGRAM_KEY);
+        let price = |micro_lamports: u64| {
+            instruction(
+                compute_budget_program,
+                [vec![3], micro_lamports.to_le_bytes().to_vec()].concat(),
+                &[],
+            )
+        };
+        let metadata = parse_instructions(&[
+            price(1_000_000),
+            system_instruction::transfer(&PAYER, &key(2), 1_000),
+            price(5_000_000),
+            instruction(compute_budget_program, vec![0, 1, 2], &[]),
+            system_instruction::transfer(&PAYER, &key(3), 1_000),
+        ]);
+
+        // without a SetComputeUnitLimit instruction, each of the two transfers gets the default 200,000 compute units
+        // the repeated price is ignored in the summary, since the runtime would reject the transaction anyway
+        assert_eq!(
+            metadata.fee_summary,
+            SolanaFeeSummary {
+                signature_count: 1,
+                base_fee_lamports: 5_000,
+                compute_unit_limit: 400_000,
+                compute_unit_price_micro_lamports: 1_000_000,
+                priority_fee_lamports: 400_000,
+                total_max_fee_lamports: 405_000,
+            }
+        );
+        assert_eq!(
+            metadata.warnings,
+            vec![TransactionWarning::DuplicateComputeBudgetInstruction {
+                instruction_index: 2,
+                instruction_name: "SetComputeUnitPrice".to_string(),
+            }]
+        );
+        assert_eq!(
+            metadata.instructions[3].instruction_parse_error,
+            Some(InstructionParseError::DataParseError {
+                program_name: "Compute Budget Program".to_string(),
+                error: "unknown compute budget instruction tag 0".to_string(),
+            })
+        );
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn decodes_stake_account_lifecycle() {
+        use solana_sdk::stake::{
+            instruction as stake_instruction,
+            state::{Authorized, Lockup},
+        };
+        let (stake_account, vote_account, withdrawer, custodian) = (key(2), key(3), key(4), key(5));
+        let (split_account, merged_account) = (key(6), key(7));
+        let lockup = Lockup {
+            unix_timestamp: 1_700_000_000,
+            epoch: 600,
+            custodian,
+        };
+        let mut instructions = stake_instruction::create_account_and_delegate_stake(
+            &PAYER,
+            &stake_account,
+            &vote_account,
+            &Authorized {
+                staker: PAYER,
+                withdrawer,
+            },
+            &lockup,
+            2_000_000_000,
+        );
+        instructions.extend(stake_instruction::split(
+            &stake_account,
+            &PAYER,
+            500_000_000,
+            &split_account,
+        ));
+        instructions.extend(stake_instruction::merge(
+            &stake_account,
+            &merged_account,
+            &PAYER,
+        ));
+        instructions.push(stake_instruction::deactivate_stake(&stake_account, &PAYER));
+        instructions.push(stake_instruction::move_stake(
+            &stake_account,
+            &split_account,
+            &PAYER,
+            100,
+        ));
+        let metadata = parse_instructions(&instructions);
+
+        let initialize = parsed(&metadata.instructions[1]);
+        assert_eq!(initialize.instruction_name, "Initialize");
+        assert_eq!(initialize.discriminator, "00000000");
+        assert_eq!(
+            initialize.idl_source,
+            IdlSource::Native(NativeProgram::Stake)
+        );
+        assert_eq!(
+            args(initialize),
+            serde_json::json!({
+                "staker": PAYER.to_string(),
+                "withdrawer": withdrawer.to_string(),
+                "lockup": {
+                    "unix_timestamp": 1_700_000_000,
+                    "epoch": 600,
+                    "custodian": custodian.to_string(),
+                },
+            })
+        );
+
+        let delegate = parsed(&metadata.instructions[2]);
+        assert_eq!(delegate.instruction_name, "DelegateStake");
+        assert!(named(
+            delegate,
+            &[
+                ("stake_account", stake_account),
+                ("vote_account", vote_account),
+                ("clock_sysvar", solana_sdk::sysvar::clock::id()),
+                (
+                    "stake_history_sysvar",
+                    solana_sdk::sysvar::stake_history::id()
+                ),
+                (
+                    "stake_config",
+                    Pubkey::from_str_const("StakeConfig11111111111111111111111111111111")
+                ),
+                ("stake_authority", PAYER),
+            ]
+        ));
+
+        // split allocates and assigns the new stake account with the System program first
+        let split = parsed(&metadata.instructions[5]);
+        assert_eq!(split.instruction_name, "Split");
+        assert_eq!(args(split), serde_json::json!({ "lamports": 500_000_000 }));
+        assert_eq!(
+            split.named_accounts["split_stake_account"],
+            split_account.to_string()
+        );
+
+        let merge = parsed(&metadata.instructions[6]);
+        assert_eq!(merge.instruction_name, "Merge");
+        assert_eq!(
+            merge.named_accounts["source_stake_account"],
+            merged_account.to_string()
+        );
+        assert_eq!(
+            parsed(&metadata.instructions[7]).instruction_name,
+            "Deactivate"
+        );
+        let move_stake = parsed(&metadata.instructions[8]);
+        assert_eq!(move_stake.instruction_name, "MoveStake");
+        assert_eq!(move_stake.discriminator, "10000000");
+
+        assert_eq!(
+            metadata.stake_summary.delegations,
+            vec![StakeDelegation {
+                instruction_index: 2,
+                stake_account: stake_account.to_string(),
+                vote_account: vote_account.to_string(),
+                stake_authority: PAYER.to_string(),
+            }]
+        );
+        assert!(metadata.stake_summary.authority_changes.is_empty());
+        assert!(metadata.stake_summary.withdrawals.is_empty());
+        assert!(metadata.warnings.is_empty());
+    }
+
+    #[test]
+    fn surfaces_stake_authority_changes_and_withdrawals() {
+        use solana_sdk::stake::{instruction as stake_instruction, state::StakeAuthorize};
+        let stake_program = Pubkey::from_str_const(STAKE_PROGRAM_KEY);
+        let (stake_account, new_staker, new_withdrawer, recipient, custodian) =
+            (key(2), key(3), key(4), key(5), key(6));
+        let metadata = parse_instructions(&[
+            stake_instruction::authorize(
+                &stake_account,
+                &PAYER,
+                &new_staker,
+                StakeAuthorize::Staker,
+                None,
+            ),
+            stake_instruction::authorize_checked_with_seed(
+                &stake_account,
+                &PAYER,
+                "stake".to_string(),
+                &stake_program,
+                &new_withdrawer,
+                StakeAuthorize::Withdrawer,
+                Some(&custodian),
+            ),
+            stake_instruction::withdraw(&stake_account, &PAYER, &recipient, 1_500_000_000, None),
+            instruction(stake_program, vec![99, 0, 0, 0], &[stake_account]),
+        ]);
+
+        let authorize_with_seed = parsed(&metadata.instructions[1]);
+        assert_eq!(
+            authorize_with_seed.instruction_name,
+            "AuthorizeCheckedWithSeed"
+        );
+        assert_eq!(
+            args(authorize_with_seed),
+            serde_json::json!({
+                "authority_type": "Withdrawer",
+                "authority_seed": "stake",
+                "authority_owner": STAKE_PROGRAM_KEY,
+            })
+        );
+        assert!(named(
+            authorize_with_seed,
+            &[
+                ("stake_account", stake_account),
+                ("authority_base", PAYER),
+                ("clock_sysvar", solana_sdk::sysvar::clock::id()),
+                ("new_authority", new_withdrawer),
+                ("lockup_authority", custodian),
+            ]
+        ));
+
+        assert_eq!(
+            metadata.stake_summary.authority_changes,
+            vec![
+                StakeAuthorityChange {
+                    instruction_index: 0,
+                    stake_account: stake_account.to_string(),
+                    authority_type: "Staker".to_string(),
+                    new_authority: new_staker.to_string(),
+                },
+                StakeAuthorityChange {
+                    instruction_index: 1,
+                    stake_account: stake_account.to_string(),
+                    authority_type: "Withdrawer".to_string(),
+                    new_authority: new_withdrawer.to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            metadata.stake_summary.withdrawals,
+            vec![StakeWithdrawal {
+                instruction_index: 2,
+                stake_account: stake_account.to_string(),
+                recipient: recipient.to_string(),
+                lamports: 1_500_000_000,
+            }]
+        );
+        assert_eq!(
+            metadata.warnings,
+            vec![
+                TransactionWarning::StakeAuthorize {
+                    instruction_index: 0,
+                    stake_account: stake_account.to_string(),
+                    authority_type: "Staker".to_string(),
+                    new_authority: new_staker.to_string(),
+                },
+                TransactionWarning::StakeAuthorize {
+                    instruction_index: 1,
+                    stake_account: stake_account.to_string(),
+                    authority_type: "Withdrawer".to_string(),
+                    new_authority: new_withdrawer.to_string(),
+                },
+                TransactionWarning::StakeWithdraw {
+                    instruction_index: 2,
+                    stake_account: stake_account.to_string(),
+                    recipient: recipient.to_string(),
+                    lamports: 1_500_000_000,
+                },
+            ]
+        );
+        assert!(matches!(
+            &metadata.instructions[3].instruction_parse_error,
+            Some(InstructionParseError::DataParseError { program_name, .. }) if program_name == "Stake Program"
+        ));
+    }
+
+    #[test]
+    fn decodes_vote_account_management_instructions() {
+        use solana_sdk::vote::{instruction as vote_instruction, state::VoteAuthorize};
+        let vote_program = Pubkey::from_str_const(VOTE_PROGRAM_KEY);
+        let (vote_account, recipient, new_voter, new_identity) = (key(2), key(3), key(4), key(5));
+        let metadata = parse_instructions(&[
+            vote_instruction::withdraw(&vote_account, &PAYER, 250_000_000, &recipient),
+            vote_instruction::authorize(&vote_account, &PAYER, &new_voter, VoteAuthorize::Voter),
+            vote_instruction::update_commission(&vote_account, &PAYER, 7),
+            vote_instruction::update_validator_identity(&vote_account, &PAYER, &new_identity),
+            instruction(vote_program, vec![42, 0, 0, 0], &[vote_account]),
+        ]);
+
+        let withdraw = parsed(&metadata.instructions[0]);
+        assert_eq!(withdraw.instruction_name, "Withdraw");
+        assert_eq!(withdraw.discriminator, "03000000");
+        assert_eq!(withdraw.idl_source, IdlSource::Native(NativeProgram::Vote));
+        assert_eq!(
+            args(withdraw),
+            serde_json::json!({ "lamports": 250_000_000 })
+        );
+        assert!(named(
+            withdraw,
+            &[
+                ("vote_account", vote_account),
+                ("recipient_account", recipient),
+                ("withdraw_authority", PAYER),
+            ]
+        ));
+
+        let authorize = parsed(&metadata.instructions[1]);
+        assert_eq!(authorize.instruction_name, "Authorize");
+        assert_eq!(
+            args(authorize),
+            serde_json::json!({ "new_authority": new_voter.to_string(), "authority_type": "Voter" })
+        );
+        assert!(named(
+            authorize,
+            &[
+                ("vote_account", vote_account),
+                ("clock_sysvar", solana_sdk::sysvar::clock::id()),
+                ("authority", PAYER),
+            ]
+        ));
+
+        let commission = parsed(&metadata.instructions[2]);
+        assert_eq!(commission.instruction_name, "UpdateCommission");
+        assert_eq!(args(commission), serde_json::json!({ "commission": 7 }));
+
+        let identity = parsed(&metadata.instructions[3]);
+        assert_eq!(identity.instruction_name, "UpdateValidatorIdentity");
+        assert!(named(
+            identity,
+            &[
+                ("vote_account", vote_account),
+                ("new_validator_identity", new_identity),
+                ("withdraw_authority", PAYER),
+            ]
+        ));
+
+        assert!(matches!(
+            &metadata.instructions[4].instruction_parse_error,
+            Some(InstructionParseError::DataParseError { program_name, .. }) if program_name == "Vote Program"
+        ));
+    }
+
+    #[test]
+    fn summarizes_consensus_vote_instructions() {
+        use solana_sdk::vote::{
+            instruction as vote_instruction,
+            state::{TowerSync, Vote},
+        };
+        let vote_account = key(2);
+        let bank_hash = Hash::new_from_array([9; 32]);
+        let metadata = parse_instructions(&[
+            vote_instruction::vote(
+                &vote_account,
+                &PAYER,
+                Vote::new(vec![100, 101, 102], bank_hash),
+            ),
+            vote_instruction::tower_sync(
+                &vote_account,
+                &PAYER,
+                TowerSync::new_from_slots(vec![200, 201], bank_hash, Some(150)),
+            ),
+        ]);
+
+        let vote = parsed(&metadata.instructions[0]);
+        assert_eq!(vote.instruction_name, "Vote");
+        assert_eq!(
+            args(vote),
+            serde_json::json!({
+                "votes": 3,
+                "last_voted_slot": 102,
+                "hash": bank_hash.to_string(),
+                "timestamp": null,
+            })
+        );
+        assert!(named(
+            vote,
+            &[
+                ("vote_account", vote_account),
+                ("slot_hashes_sysvar", solana_sdk::sysvar::slot_hashes::id()),
+                ("clock_sysvar", solana_sdk::sysvar::clock::id()),
+                ("vote_authority", PAYER),
+            ]
+        ));
+
+        let tower_sync = parsed(&metadata.instructions[1]);
+        assert_eq!(tower_sync.instruction_name, "TowerSync");
+        assert_eq!(tower_sync.discriminator, "0e000000");
+        assert_eq!(
+            args(tower_sync),
+            serde_json::json!({
+                "lockouts": 2,
+                "last_voted_slot": 201,
+                "root": 150,
+                "hash": bank_hash.to_string(),
+                "timestamp": null,
+                "block_id": Hash::default().to_string(),
+            })
+        );
+        assert!(named(
+            tower_sync,
+            &[("vote_account", vote_account), ("vote_authority", PAYER)]
+        ));
+    }
+
+    #[test]
+    fn decodes_address_lookup_table_instructions() {
+        use solana_sdk::address_lookup_table::instruction as lookup_table_instruction;
+        let lookup_table_program = Pubkey::from_str_const(ADDRESS_LOOKUP_TABLE_PROGRAM_KEY);
+        let (authority, recipient) = (key(2), key(3));
+        let (create, table) =
+            lookup_table_instruction::create_lookup_table_signed(authority, PAYER, 321);
+        let (_, bump_seed) = Pubkey::find_program_address(
+            &[authority.as_ref(), &321u64.to_le_bytes()],
+            &lookup_table_program,
+        );
+        let metadata = parse_instructions(&[
+            create,
+            lookup_table_instruction::extend_lookup_table(
+                table,
+                authority,
+                Some(PAYER),
+                vec![key(4), key(5)],
+            ),
+            lookup_table_instruction::freeze_lookup_table(table, authority),
+            lookup_table_instruction::deactivate_lookup_table(table, authority),
+            lookup_table_instruction::close_lookup_table(table, authority, recipient),
+        ]);
+
+        let create = parsed(&metadata.instructions[0]);
+        assert_eq!(create.instruction_name, "CreateLookupTable");
+        assert_eq!(create.discriminator, "00000000");
+        assert_eq!(
+            create.idl_source,
+            IdlSource::Native(NativeProgram::AddressLookupTable)
+        );
+        assert_eq!(
+            args(create),
+            serde_json::json!({
+                "recent_slot": 321,
+                "bump_seed": bump_seed,
+                "derived_lookup_table_address": table.to_string(),
+            })
+        );
+        assert!(named(
+            create,
+            &[
+                ("lookup_table_account", table),
+                ("authority", authority),
+                ("payer", PAYER),
+                (
+                    "system_program",
+                    Pubkey::from_str_const(SOL_SYSTEM_PROGRAM_KEY)
+                ),
+            ]
+        ));
+
+        let extend = parsed(&metadata.instructions[1]);
+        assert_eq!(extend.instruction_name, "ExtendLookupTable");
+        assert_eq!(
+            args(extend),
+            serde_json::json!({ "new_addresses": [key(4).to_string(), key(5).to_string()] })
+        );
+        assert_eq!(extend.named_accounts["payer"], PAYER.to_string());
+
+        assert_eq!(
+            parsed(&metadata.instructions[2]).instruction_name,
+            "FreezeLookupTable"
+        );
+        assert_eq!(
+            parsed(&metadata.instructions[3]).instruction_name,
+            "DeactivateLookupTable"
+        );
+        let close = parsed(&metadata.instructions[4]);
+        assert_eq!(close.instruction_name, "CloseLookupTable");
+        assert!(named(
+            close,
+            &[
+                ("lookup_table_account", table),
+                ("authority", authority),
+                ("recipient", recipient),
+            ]
+        ));
+    }
+
+    #[test]
+    fn decodes_memos_and_lists_them_on_the_transaction() {
+        let memo_program = Pubkey::from_str_const(MEMO_PROGRAM_KEY);
+        let memo_v1_program = Pubkey::from_str_const(MEMO_V1_PROGRAM_KEY);
+        let co_signer = key(2);
+        let metadata = parse_instructions(&[
+            system_instruction::transfer(&PAYER, &key(3), 1_000_000),
+            Instruction {
+                program_id: memo_program,
+                accounts: vec![
+                    AccountMeta::new_readonly(PAYER, true),
+                    AccountMeta::new_readonly(co_signer, true),
+                ],
+                data: b"deposit 12345 \xe2\x9c\x93".to_vec(),
+            },
+            instruction(memo_v1_program, b"legacy".to_vec(), &[key(4)]),
+            instruction(memo_program, vec![0x66, 0x6f, 0xff], &[PAYER, key(4)]),
+        ]);
+
+        let memo = parsed(&metadata.instructions[1]);
+        assert_eq!(memo.instruction_name, "Memo");
+        assert_eq!(memo.discriminator, "");
+        assert_eq!(memo.idl_source, IdlSource::Native(NativeProgram::Memo));
+        assert_eq!(
+            args(memo),
+            serde_json::json!({ "memo": "deposit 12345 \u{2713}" })
+        );
+        assert!(named(memo, &[("signer_1", PAYER), ("signer_2", co_signer)]));
+
+        let legacy_memo = parsed(&metadata.instructions[2]);
+        assert_eq!(
+            legacy_memo.idl_source,
+            IdlSource::Native(NativeProgram::MemoV1)
+        );
+        assert_eq!(args(legacy_memo), serde_json::json!({ "memo": "legacy" }));
+        // key(4) is passed to the memo but does not sign the transaction
+        assert!(named(legacy_memo, &[("account_1", key(4))]));
+
+        assert_eq!(
+            metadata.instructions[3].instruction_parse_error,
+            Some(InstructionParseError::DataParseError {
+                program_name: "Memo Program".to_string(),
+                error: "memo is not valid UTF-8: invalid utf-8 sequence of 1 bytes from index 2"
+                    .to_string(),
+            })
+        );
+
+        assert_eq!(
+            metadata.memos,
+            vec![
+                SolanaMemo {
+                    instruction_index: 1,
+                    program_id: MEMO_PROGRAM_KEY.to_string(),
+                    memo: Some("deposit 12345 \u{2713}".to_string()),
+                    memo_hex: hex::encode(b"deposit 12345 \xe2\x9c\x93"),
+                    signers: vec![PAYER.to_string(), co_signer.to_string()],
+                },
+                SolanaMemo {
+                    instruction_index: 2,
+                    program_id: MEMO_V1_PROGRAM_KEY.to_string(),
+                    memo: Some("legacy".to_string()),
+                    memo_hex: hex::encode(b"legacy"),
+                    signers: vec![],
+                },
+                // key(4) is passed to the memo but does not sign the transaction
+                SolanaMemo {
+                    instruction_index: 3,
+                    program_id: MEMO_PROGRAM_KEY.to_string(),
+                    memo: None,
+                    memo_hex: "666fff".to_string(),
+                    signers: vec![PAYER.to_string()],
+                },
+            ]
+        );
+    }
+}